@@ -3,7 +3,9 @@ use std::time::Duration;
 
 fn main() {
     #[cfg(feature = "channels-console")]
-    let _channels_guard = channels_console::ChannelsGuard::new();
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .full_labels()
+        .build();
 
     println!("Creating channels in loops...\n");
 
@@ -0,0 +1,28 @@
+#[allow(unused_mut)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard =
+        channels_console::ChannelsGuard::new().format(channels_console::Format::JsonLines);
+
+    let (txa, rxa) = crossbeam_channel::unbounded::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (txa, rxa) = channels_console::channel!((txa, rxa), label = "unbounded");
+
+    let (txb, rxb) = crossbeam_channel::bounded::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (txb, rxb) = channels_console::channel!((txb, rxb), label = "bounded");
+
+    for i in 1..=3 {
+        txa.send(i).expect("Failed to send");
+        txb.send(i).expect("Failed to send");
+    }
+
+    for _ in 1..=3 {
+        let _ = rxa.recv();
+        let _ = rxb.recv();
+    }
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    println!("Example completed!");
+}
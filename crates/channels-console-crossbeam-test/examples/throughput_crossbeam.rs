@@ -0,0 +1,37 @@
+use std::thread;
+use std::time::Instant;
+
+const MESSAGES: u64 = 1_000_000;
+
+/// Measures how many messages per second pass through a channel.
+/// Run with `--release`, with and without the `channels-console` feature, to compare.
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (tx, rx) = crossbeam_channel::bounded::<u64>(1024);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "throughput");
+
+    let start = Instant::now();
+
+    let producer = thread::spawn(move || {
+        for i in 0..MESSAGES {
+            tx.send(i).expect("Failed to send");
+        }
+    });
+
+    let mut received = 0;
+    while rx.recv().is_ok() {
+        received += 1;
+    }
+    producer.join().unwrap();
+
+    let elapsed = start.elapsed();
+    println!(
+        "Received {} messages in {:.2?} ({:.0} msgs/sec)",
+        received,
+        elapsed,
+        received as f64 / elapsed.as_secs_f64()
+    );
+}
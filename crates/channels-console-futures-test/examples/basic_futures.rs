@@ -10,7 +10,7 @@ struct Actor {
 #[allow(unused_mut)]
 fn main() {
     smol::block_on(async {
-        let actor1 = Actor {
+        let _actor1 = Actor {
             name: "Actor 1".to_string(),
         };
 
@@ -19,7 +19,7 @@ fn main() {
 
         let (txa, mut _rxa) = futures_channel::mpsc::unbounded::<i32>();
         #[cfg(feature = "channels-console")]
-        let (txa, mut _rxa) = channels_console::channel!((txa, _rxa), label = actor1.name);
+        let (txa, mut _rxa) = channels_console::channel!((txa, _rxa), label = _actor1.name);
 
         let (mut txb, mut rxb) = futures_channel::mpsc::channel::<i32>(10);
         #[cfg(feature = "channels-console")]
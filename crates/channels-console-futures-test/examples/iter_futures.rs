@@ -14,7 +14,9 @@ fn main() {
         };
 
         #[cfg(feature = "channels-console")]
-        let _channels_guard = channels_console::ChannelsGuard::new();
+        let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+            .full_labels()
+            .build();
 
         println!("Creating channels in loops...\n");
 
@@ -29,7 +31,7 @@ fn main() {
 
             smol::spawn(async move {
                 tx.unbounded_send(i).expect("Failed to send");
-                let _ = rx.try_next();
+                let _ = rx.try_recv();
             })
             .detach();
         }
@@ -46,7 +48,7 @@ fn main() {
 
             smol::spawn(async move {
                 tx.try_send(i).expect("Failed to send");
-                let _ = rx.try_next();
+                let _ = rx.try_recv();
             })
             .detach();
         }
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .duplicate_channels(channels_console::DuplicateChannels::Aggregate)
+        .build();
+
+    let mut workers = Vec::new();
+    for i in 0..3 {
+        let (tx, rx) = std::sync::mpsc::channel::<i32>();
+        #[cfg(feature = "channels-console")]
+        let (tx, rx) = channels_console::channel!((tx, rx), label = "worker");
+
+        for n in 0..=i {
+            tx.send(n).expect("Failed to send");
+        }
+        workers.push((tx, rx));
+    }
+
+    for (_, rx) in &workers {
+        rx.recv().expect("Failed to receive");
+    }
+
+    // Give the forwarders a moment to record the messages
+    std::thread::sleep(Duration::from_millis(100));
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+#[allow(unused_variables)]
+fn main() {
+    let (burst_tx, burst_rx) = std::sync::mpsc::sync_channel::<i32>(2);
+    #[cfg(feature = "channels-console")]
+    let (burst_tx, burst_rx) =
+        channels_console::channel!((burst_tx, burst_rx), label = "burst", capacity = 2);
+
+    let (backlog_tx, backlog_rx) = std::sync::mpsc::sync_channel::<i32>(2);
+    #[cfg(feature = "channels-console")]
+    let (backlog_tx, backlog_rx) =
+        channels_console::channel!((backlog_tx, backlog_rx), label = "backlog", capacity = 2);
+
+    // A burst drained right away, and a stalled consumer whose queue stays full
+    for i in 0..2 {
+        burst_tx.send(i).expect("Failed to send");
+    }
+    for _ in 0..2 {
+        burst_rx.recv().expect("Failed to receive");
+    }
+    std::thread::spawn(move || {
+        for i in 0..10 {
+            if backlog_tx.send(i).is_err() {
+                break;
+            }
+        }
+    });
+    std::thread::sleep(Duration::from_millis(500));
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[AvgQueued] {} avg_queued={:.2} queued={}",
+            channel.label, channel.avg_queued, channel.queued
+        );
+        println!(
+            "[AvgQueued] {} below_one={}",
+            channel.label,
+            channel.avg_queued < 1.0
+        );
+    }
+}
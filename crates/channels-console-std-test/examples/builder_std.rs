@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard =
+        channels_console::ChannelsGuard::new().format(channels_console::Format::Json);
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::InstrumentBuilder::new((tx, rx))
+        .id("ingest-queue")
+        .label("ingest")
+        .build();
+
+    // Channels created at different locations, merged into one entry
+    let (tx_a, rx_a) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_a, rx_a) = channels_console::InstrumentBuilder::new((tx_a, rx_a))
+        .group("workers")
+        .build();
+
+    let (tx_b, rx_b) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_b, rx_b) = channels_console::InstrumentBuilder::new((tx_b, rx_b))
+        .group("workers")
+        .build();
+
+    // Identified by the file and line of the call
+    let (tx_c, rx_c) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_c, rx_c) = channels_console::InstrumentBuilder::new((tx_c, rx_c)).build();
+
+    tx.send(1).expect("Failed to send");
+    tx_a.send(1).expect("Failed to send");
+    tx_b.send(2).expect("Failed to send");
+    tx_b.send(3).expect("Failed to send");
+    tx_c.send(1).expect("Failed to send");
+
+    rx.recv().expect("Failed to receive");
+    for _ in 0..2 {
+        rx_b.recv().expect("Failed to receive");
+    }
+    rx_a.recv().expect("Failed to receive");
+    rx_c.recv().expect("Failed to receive");
+
+    // Give the forwarders a moment to record the messages
+    std::thread::sleep(Duration::from_millis(100));
+}
@@ -0,0 +1,34 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (ingest_tx, ingest_rx) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (ingest_tx, ingest_rx) =
+        channels_console::channel!((ingest_tx, ingest_rx), id = "ingest-queue");
+
+    let (results_tx, results_rx) = std::sync::mpsc::sync_channel::<u64>(4);
+    #[cfg(feature = "channels-console")]
+    let (results_tx, results_rx) = channels_console::channel!(
+        (results_tx, results_rx),
+        capacity = 4,
+        id = "results",
+        label = "Results",
+        log = true
+    );
+
+    for i in 0..3 {
+        ingest_tx.send(i).expect("Failed to send");
+        ingest_rx.recv().expect("Failed to receive");
+        results_tx.send(i * 2).expect("Failed to send");
+        results_rx.recv().expect("Failed to receive");
+    }
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::channels_matching(|_| true) {
+        println!(
+            "[Id] source={} label={} sent={}",
+            channel.source, channel.label, channel.sent_count
+        );
+    }
+}
@@ -0,0 +1,29 @@
+fn main() {
+    let (tx, rx) = std::sync::mpsc::sync_channel::<u64>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "views", capacity = 10);
+
+    for i in 0..5 {
+        tx.send(i).expect("Failed to send");
+    }
+    for _ in 0..2 {
+        rx.recv().expect("Failed to receive");
+    }
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    for view in channels_console::channel_views() {
+        println!(
+            "[Views] {} {} sent={} first_sent={} sent_logs={}",
+            view.label(),
+            view.channel_type(),
+            view.sent_count(),
+            view.first_sent_at().is_some(),
+            view.sent_logs().len()
+        );
+        println!(
+            "[Views] max_queued_within_capacity={}",
+            view.queued_percentile(1.0) <= 10
+        );
+    }
+}
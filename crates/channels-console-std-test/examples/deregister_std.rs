@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .deregister_closed(true)
+        .build();
+
+    for i in 0..3 {
+        let (tx, rx) = std::sync::mpsc::channel::<i32>();
+        #[cfg(feature = "channels-console")]
+        let (tx, rx) = channels_console::channel!((tx, rx), label = "short-lived");
+
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+
+    let (tx_live, rx_live) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_live, rx_live) = channels_console::channel!((tx_live, rx_live), label = "live");
+
+    tx_live.send(1).expect("Failed to send");
+    rx_live.recv().expect("Failed to receive");
+
+    // Give the forwarders of the dropped channels a moment to finish
+    std::thread::sleep(Duration::from_millis(100));
+
+    // Print the statistics while the live channel is still open
+    #[cfg(feature = "channels-console")]
+    drop(_channels_guard);
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+#[allow(unused_variables)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (noisy_tx, noisy_rx) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (noisy_tx, noisy_rx) = channels_console::channel!((noisy_tx, noisy_rx), label = "noisy");
+
+    let (quiet_tx, quiet_rx) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (quiet_tx, quiet_rx) = channels_console::channel!((quiet_tx, quiet_rx), label = "quiet");
+
+    for i in 0..3 {
+        noisy_tx.send(i).expect("Failed to send");
+        noisy_rx.recv().expect("Failed to receive");
+    }
+
+    // Messages passing while disabled are not counted
+    #[cfg(feature = "channels-console")]
+    let found = channels_console::disable_channel("noisy");
+    #[cfg(feature = "channels-console")]
+    println!("[Disable] found={found}");
+
+    for i in 0..100 {
+        noisy_tx.send(i).expect("Failed to send");
+        noisy_rx.recv().expect("Failed to receive");
+        quiet_tx.send(i).expect("Failed to send");
+        quiet_rx.recv().expect("Failed to receive");
+    }
+
+    std::thread::sleep(Duration::from_millis(50));
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::channels_matching(|c| c.label == "noisy") {
+        println!(
+            "[Disable] disabled={} sent={} received={}",
+            channel.disabled, channel.sent_count, channel.received_count
+        );
+    }
+
+    #[cfg(feature = "channels-console")]
+    channels_console::enable_channel("noisy");
+
+    for i in 0..2 {
+        noisy_tx.send(i).expect("Failed to send");
+        noisy_rx.recv().expect("Failed to receive");
+    }
+
+    std::thread::sleep(Duration::from_millis(50));
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::channels_matching(|c| c.label == "noisy") {
+        println!(
+            "[Enable] disabled={} sent={} received={}",
+            channel.disabled, channel.sent_count, channel.received_count
+        );
+    }
+
+    #[cfg(feature = "channels-console")]
+    channels_console::disable_channel("noisy");
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(Duration::from_millis(50));
+}
@@ -0,0 +1,24 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    #[cfg(feature = "channels-console")]
+    let worker = 1;
+    let (tx, rx) = std::sync::mpsc::sync_channel::<i32>(2);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!(
+        (tx, rx),
+        label = format!("worker-{worker}"),
+        capacity = 2,
+        log = true
+    );
+
+    let (tx, rx): (
+        std::sync::mpsc::SyncSender<i32>,
+        std::sync::mpsc::Receiver<i32>,
+    ) = (tx, rx);
+    tx.send(1).expect("Failed to send");
+    assert_eq!(rx.recv().expect("Failed to receive"), 1);
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .hide_empty()
+        .min_queued(1)
+        .build();
+
+    let (_idle_tx, _idle_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (_idle_tx, _idle_rx) = channels_console::channel!((_idle_tx, _idle_rx), label = "idle");
+
+    let (drained_tx, drained_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (drained_tx, drained_rx) =
+        channels_console::channel!((drained_tx, drained_rx), label = "drained");
+
+    let (backlog_tx, _backlog_rx) = std::sync::mpsc::sync_channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (backlog_tx, _backlog_rx) =
+        channels_console::channel!((backlog_tx, _backlog_rx), label = "backlog", capacity = 4);
+
+    for i in 0..4 {
+        drained_tx.send(i).expect("Failed to send");
+        drained_rx.recv().expect("Failed to receive");
+    }
+
+    // Fills the receiver's buffer, the remaining messages wait in the proxy
+    for i in 0..8 {
+        backlog_tx.send(i).expect("Failed to send");
+    }
+
+    // Give the forwarders a moment to record the messages
+    std::thread::sleep(Duration::from_millis(100));
+
+    // The filters only apply to the printed summary
+    #[cfg(feature = "channels-console")]
+    println!(
+        "[Filter] tracked={}",
+        channels_console::snapshot().channels.len()
+    );
+}
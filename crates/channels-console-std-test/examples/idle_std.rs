@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard =
+        channels_console::ChannelsGuard::new().format(channels_console::Format::Json);
+
+    let (tx_stale, rx_stale) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_stale, rx_stale) = channels_console::channel!((tx_stale, rx_stale), label = "stale");
+
+    tx_stale.send(1).expect("Failed to send");
+    rx_stale.recv().expect("Failed to receive");
+
+    std::thread::sleep(Duration::from_millis(300));
+
+    let (tx_busy, rx_busy) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_busy, rx_busy) = channels_console::channel!((tx_busy, rx_busy), label = "busy");
+
+    let (_tx_unused, _rx_unused) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (_tx_unused, _rx_unused) =
+        channels_console::channel!((_tx_unused, _rx_unused), label = "unused");
+
+    tx_busy.send(2).expect("Failed to send");
+    rx_busy.recv().expect("Failed to receive");
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(Duration::from_millis(50));
+}
@@ -0,0 +1,22 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .max_label_width(24)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!(
+        (tx, rx),
+        label = "orders::fulfillment::warehouse-replenishment"
+    );
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+
+    // Only the table is shortened
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!("[Full] {}", channel.label);
+    }
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+fn main() {
+    let (_idle_tx, _idle_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (_idle_tx, _idle_rx) = channels_console::channel!((_idle_tx, _idle_rx), label = "idle");
+
+    let (backlog_tx, _backlog_rx) = std::sync::mpsc::sync_channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (backlog_tx, _backlog_rx) =
+        channels_console::channel!((backlog_tx, _backlog_rx), label = "backlog", capacity = 4);
+
+    // Fills the receiver's buffer, the remaining messages wait in the proxy
+    for i in 0..8 {
+        backlog_tx.send(i).expect("Failed to send");
+    }
+
+    // Give the forwarders a moment to record the messages
+    std::thread::sleep(Duration::from_millis(100));
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::channels_matching(|c| c.queued > 0) {
+        println!("[Matching] {} queued={}", channel.label, channel.queued);
+    }
+}
@@ -0,0 +1,21 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    println!("Port before: {:?}", channels_console::metrics_port());
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx));
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+
+    // The server binds in the background
+    #[cfg(feature = "channels-console")]
+    for _ in 0..50 {
+        if let Some(port) = channels_console::metrics_port() {
+            println!("Port after: {}", port);
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+}
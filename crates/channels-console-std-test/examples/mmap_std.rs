@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+/// Counters of one channel record, see the layout in the README.
+struct Record {
+    sent: u64,
+    capacity: u64,
+    kind: u8,
+    label: String,
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Decode a consistent snapshot, `None` if one was being written meanwhile.
+fn read_snapshot(path: &std::path::Path) -> Option<(u32, Vec<Record>)> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() < 64 || &bytes[..8] != b"CHCONMAP" {
+        return None;
+    }
+    let sequence = u64_at(&bytes, 16);
+    if sequence == 0 || !sequence.is_multiple_of(2) {
+        return None;
+    }
+    let version = u32_at(&bytes, 8);
+    let header_size = u32_at(&bytes, 12) as usize;
+    let count = u32_at(&bytes, 40) as usize;
+    let record_size = u32_at(&bytes, 44) as usize;
+
+    let mut records = Vec::new();
+    for i in 0..count {
+        let record = bytes.get(header_size + i * record_size..)?;
+        let label_len = u16::from_le_bytes([record[66], record[67]]) as usize;
+        records.push(Record {
+            sent: u64_at(record, 8),
+            capacity: u64_at(record, 40),
+            kind: record[64],
+            label: String::from_utf8_lossy(&record[72..72 + label_len]).into_owned(),
+        });
+    }
+    // A write started while copying if the sequence moved
+    let check = std::fs::read(path).ok()?;
+    if check.len() < 24 || u64_at(&check, 16) != sequence {
+        return None;
+    }
+    Some((version, records))
+}
+
+#[allow(unused_variables)]
+fn main() {
+    let path = std::env::temp_dir().join(format!("channels-console-{}.stats", std::process::id()));
+
+    #[cfg(feature = "mmap")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .mmap_path(&path)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::sync_channel::<i32>(10);
+    #[cfg(feature = "mmap")]
+    let (tx, rx) = channels_console::channel!((tx, rx), capacity = 10, label = "mapped");
+
+    for i in 1..=3 {
+        tx.send(i).expect("Failed to send");
+    }
+    rx.recv().expect("Failed to receive");
+
+    // Poll the file like an external reader until the counts show up
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        if let Some((version, records)) = read_snapshot(&path) {
+            if let Some(record) = records
+                .iter()
+                .find(|record| record.label == "mapped" && record.sent == 3)
+            {
+                println!("[Reader] version={} records={}", version, records.len());
+                println!(
+                    "[Reader] {} sent={} capacity={} kind={}",
+                    record.label, record.sent, record.capacity, record.kind
+                );
+                break;
+            }
+        }
+        if Instant::now() > deadline {
+            println!("[Reader] No snapshot found");
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,27 @@
+mod net {
+    pub fn open() -> (std::sync::mpsc::Sender<u8>, std::sync::mpsc::Receiver<u8>) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        #[cfg(feature = "channels-console")]
+        let (tx, rx) = channels_console::channel!((tx, rx));
+        (tx, rx)
+    }
+}
+
+mod db {
+    pub fn open() -> (std::sync::mpsc::Sender<u8>, std::sync::mpsc::Receiver<u8>) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        #[cfg(feature = "channels-console")]
+        let (tx, rx) = channels_console::channel!((tx, rx));
+        (tx, rx)
+    }
+}
+
+fn main() {
+    let _net = net::open();
+    let _db = db::open();
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!("[Modules] {}", channel.label);
+    }
+}
@@ -0,0 +1,14 @@
+fn main() {
+    // Too narrow for any label, raised to the minimum width
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .max_label_width(0)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "replenishment");
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+}
@@ -0,0 +1,18 @@
+mod east;
+mod west;
+
+fn main() {
+    let _east = east::ingest::open();
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!("[Before] {}", channel.label);
+    }
+
+    let _west = west::ingest::open();
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!("[Nested] {}", channel.label);
+    }
+}
@@ -0,0 +1,6 @@
+pub fn open() -> (std::sync::mpsc::Sender<u8>, std::sync::mpsc::Receiver<u8>) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx));
+    (tx, rx)
+}
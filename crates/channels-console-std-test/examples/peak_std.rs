@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::sync_channel::<i32>(5);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), capacity = 5, label = "burst");
+
+    // Let a backlog build up before the consumer catches up
+    for i in 0..8 {
+        tx.send(i).expect("Failed to send");
+    }
+    std::thread::sleep(Duration::from_millis(200));
+
+    for _ in 0..8 {
+        rx.recv().expect("Failed to receive");
+    }
+    std::thread::sleep(Duration::from_millis(200));
+}
@@ -0,0 +1,45 @@
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Job {
+    id: u32,
+    name: String,
+}
+
+fn main() {
+    let (jobs_tx, jobs_rx) = std::sync::mpsc::channel::<Job>();
+    #[cfg(feature = "channels-console")]
+    let (jobs_tx, jobs_rx) =
+        channels_console::channel!((jobs_tx, jobs_rx), label = "jobs", preview = true);
+
+    for id in 1..=3 {
+        let name = format!("job-{id}");
+        jobs_tx.send(Job { id, name }).expect("Failed to send");
+        jobs_rx.recv().expect("Failed to receive");
+    }
+
+    let (logs_tx, logs_rx) = std::sync::mpsc::channel::<String>();
+    #[cfg(feature = "channels-console")]
+    let (logs_tx, logs_rx) = channels_console::channel!(
+        (logs_tx, logs_rx),
+        preview = true,
+        label = "logs",
+        log = true
+    );
+
+    logs_tx.send("x".repeat(200)).expect("Failed to send");
+    logs_rx.recv().expect("Failed to receive");
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        let preview = channel.preview.unwrap_or_default();
+        println!(
+            "[Preview] {} chars={} preview={}",
+            channel.label,
+            preview.chars().count(),
+            preview
+        );
+    }
+}
@@ -0,0 +1,17 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::JsonLines)
+        .print_interval(std::time::Duration::from_millis(100))
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "periodic");
+
+    for i in 0..5 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
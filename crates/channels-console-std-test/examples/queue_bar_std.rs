@@ -0,0 +1,29 @@
+#[allow(unused_mut)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (tx_full, rx_full) = std::sync::mpsc::sync_channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx_full, rx_full) =
+        channels_console::channel!((tx_full, rx_full), capacity = 4, label = "full-queue");
+
+    let (tx_idle, rx_idle) = std::sync::mpsc::sync_channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx_idle, rx_idle) =
+        channels_console::channel!((tx_idle, rx_idle), capacity = 4, label = "idle-queue");
+
+    // Nobody reads from the full queue, so it backs up until sends start failing
+    for i in 0..20 {
+        let _ = tx_full.try_send(i);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    tx_idle.send(1).expect("Failed to send");
+    rx_idle.recv().expect("Failed to receive");
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    drop(rx_full);
+}
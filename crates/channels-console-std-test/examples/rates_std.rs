@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+fn main() {
+    let (tx, rx) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "burst");
+
+    for i in 0..1000 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+
+    // Wait for the collector to update the decaying rates
+    std::thread::sleep(Duration::from_millis(1500));
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::channels_matching(|c| c.label == "burst") {
+        println!(
+            "[Rates] sent_rate_1m={:.1} received_rate_1m={:.1} sent_rate_avg={:.1}",
+            channel.sent_rate_1m, channel.received_rate_1m, channel.sent_rate_avg
+        );
+        // A one-minute average ramps up slowly after a short burst
+        println!(
+            "[Rates] decaying={} below_lifetime_avg={}",
+            channel.sent_rate_1m > 0.0 && channel.received_rate_1m > 0.0,
+            channel.sent_rate_1m < channel.sent_rate_avg
+        );
+    }
+}
@@ -0,0 +1,19 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "connection");
+    #[cfg(feature = "channels-console")]
+    let id = channels_console::last_channel_id().expect("Channel not instrumented");
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+
+    // The peer address is only known once the handshake is done
+    #[cfg(feature = "channels-console")]
+    channels_console::set_label(id, "connection-10.0.0.7:5432");
+}
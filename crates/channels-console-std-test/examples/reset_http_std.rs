@@ -0,0 +1,27 @@
+use std::time::{Duration, Instant};
+
+fn main() {
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "jobs");
+
+    for i in 0..3 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+
+    // Wait for a client to reset the statistics with POST /reset
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+
+        #[cfg(feature = "channels-console")]
+        if channels_console::channels_matching(|c| c.label == "jobs" && c.sent_count == 0).len()
+            == 1
+        {
+            println!("[Reset] jobs reset over HTTP");
+            return;
+        }
+    }
+    println!("[Reset] jobs not reset");
+}
@@ -0,0 +1,46 @@
+fn main() {
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "phases");
+
+    for i in 0..3 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    {
+        print_stats("first phase");
+        let generation = channels_console::snapshot().generation;
+        println!(
+            "[Reset] found={} missing={}",
+            channels_console::reset_channel("phases"),
+            channels_console::reset_channel("missing")
+        );
+        print_stats("after reset");
+        println!(
+            "[Reset] generation before={generation} after={}",
+            channels_console::snapshot().generation
+        );
+    }
+
+    for i in 0..2 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    print_stats("second phase");
+}
+
+#[cfg(feature = "channels-console")]
+fn print_stats(phase: &str) {
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Reset] {phase}: {} sent={} received={}",
+            channel.label, channel.sent_count, channel.received_count
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::thread;
+
+#[allow(unused_mut)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard =
+        channels_console::ChannelsGuard::new().format(channels_console::Format::Json);
+
+    // Sample first, then label
+    let (txa, rxa) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (txa, rxa) = channels_console::channel!((txa, rxa), sample = 10, label = "sampled");
+
+    // Sample last, mixed with capacity and logging
+    let (txb, rxb) = std::sync::mpsc::sync_channel::<u64>(100);
+    #[cfg(feature = "channels-console")]
+    let (txb, rxb) = channels_console::channel!(
+        (txb, rxb),
+        label = "sampled-log",
+        capacity = 100,
+        log = true,
+        sample = 5
+    );
+
+    // A rate of 0 reports every message
+    let (txc, rxc) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (txc, rxc) = channels_console::channel!((txc, rxc), sample = 0, label = "sampled-zero");
+
+    let (txd, rxd) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (txd, rxd) = channels_console::InstrumentBuilder::new((txd, rxd))
+        .label("sampled-zero-builder")
+        .sample(0)
+        .build();
+
+    let sender_handle = thread::spawn(move || {
+        for i in 0..1000 {
+            txa.send(i).expect("Failed to send");
+            txb.send(i).expect("Failed to send");
+            txc.send(i).expect("Failed to send");
+            txd.send(i).expect("Failed to send");
+        }
+    });
+
+    for _ in 0..1000 {
+        rxa.recv().expect("Failed to receive");
+        rxb.recv().expect("Failed to receive");
+        rxc.recv().expect("Failed to receive");
+        rxd.recv().expect("Failed to receive");
+    }
+    sender_handle.join().unwrap();
+
+    // Give the collector a moment to process the last events
+    thread::sleep(std::time::Duration::from_millis(100));
+
+    println!("\nSampled example completed!");
+}
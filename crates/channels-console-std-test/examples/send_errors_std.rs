@@ -0,0 +1,26 @@
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::sync_channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "send-errors", capacity = 10);
+
+    for i in 1..=3 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+
+    drop(rx);
+    thread::sleep(Duration::from_millis(100));
+
+    // Accepted by the proxy, dropped once it finds the receiver gone
+    let _ = tx.send(999);
+
+    thread::sleep(Duration::from_millis(100));
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .send_wait_buckets([0.001, 1.0])
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::sync_channel::<i32>(1);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), capacity = 1, label = "slow-consumer");
+
+    let consumer = std::thread::spawn(move || {
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(20));
+            rx.recv().expect("Failed to receive");
+        }
+    });
+
+    for i in 0..10 {
+        tx.send(i).expect("Failed to send");
+    }
+    consumer.join().expect("Consumer panicked");
+
+    // Give the forwarders a moment to record the last messages
+    std::thread::sleep(Duration::from_millis(50));
+}
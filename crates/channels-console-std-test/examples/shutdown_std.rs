@@ -0,0 +1,34 @@
+#[allow(unused_mut)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard =
+        channels_console::ChannelsGuard::new().format(channels_console::Format::Json);
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "before-shutdown");
+
+    for i in 1..=3 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    channels_console::shutdown();
+    println!("[Main] Collector shut down");
+
+    #[cfg(feature = "channels-console")]
+    {
+        let stopped = std::net::TcpStream::connect("127.0.0.1:6770").is_err();
+        println!("[Main] Metrics server stopped: {}", stopped);
+    }
+
+    // Channels keep working, but are no longer counted
+    tx.send(4).expect("Failed to send");
+    assert_eq!(rx.recv().expect("Failed to receive"), 4);
+
+    println!("\nShutdown example completed!");
+}
@@ -0,0 +1,30 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    channels_console::register_sizer::<Vec<u8>>(|msg| msg.len());
+
+    #[cfg(feature = "channels-console")]
+    let _channels_guard =
+        channels_console::ChannelsGuard::new().format(channels_console::Format::Json);
+
+    let (tx_sized, rx_sized) = std::sync::mpsc::sync_channel::<Vec<u8>>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx_sized, rx_sized) =
+        channels_console::channel!((tx_sized, rx_sized), capacity = 4, label = "sized");
+
+    let (tx_plain, rx_plain) = std::sync::mpsc::sync_channel::<String>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx_plain, rx_plain) =
+        channels_console::channel!((tx_plain, rx_plain), capacity = 4, label = "plain");
+
+    // Nobody reads, so both queues back up until sends start failing
+    for _ in 0..20 {
+        let _ = tx_sized.try_send(vec![0; 1000]);
+        let _ = tx_plain.try_send("x".repeat(1000));
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    drop((rx_sized, rx_plain));
+}
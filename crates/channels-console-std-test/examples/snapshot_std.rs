@@ -0,0 +1,22 @@
+fn main() {
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "snapshot");
+
+    for i in 0..3 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+    tx.send(3).expect("Failed to send");
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Snapshot] {} sent={} received={}",
+            channel.label, channel.sent_count, channel.received_count
+        );
+    }
+}
@@ -0,0 +1,30 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .sort_by(channels_console::SortKey::Sent)
+        .build();
+
+    let (tx_one, rx_one) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_one, rx_one) = channels_console::channel!((tx_one, rx_one), label = "a-one");
+
+    let (tx_three, rx_three) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_three, rx_three) = channels_console::channel!((tx_three, rx_three), label = "b-three");
+
+    let (tx_two, rx_two) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx_two, rx_two) = channels_console::channel!((tx_two, rx_two), label = "c-two");
+
+    for (tx, rx, count) in [
+        (tx_one, rx_one, 1),
+        (tx_three, rx_three, 3),
+        (tx_two, rx_two, 2),
+    ] {
+        for i in 0..count {
+            tx.send(i).expect("Failed to send");
+            rx.recv().expect("Failed to receive");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::net::UdpSocket;
+use std::time::Duration;
+
+fn main() {
+    // Stand-in for a StatsD agent
+    let agent = UdpSocket::bind("127.0.0.1:0").expect("Failed to bind agent socket");
+    agent
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("Failed to set read timeout");
+
+    #[cfg(feature = "statsd")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .statsd_addr(agent.local_addr().unwrap().to_string())
+        .statsd_interval(Duration::from_millis(100))
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "statsd")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "pushed");
+
+    // Counters are pushed as deltas, sum them up until all messages are accounted for
+    let mut sent = 0;
+    let mut received = 0;
+    let mut buf = [0u8; 1500];
+    let mut read_until = |sent: &mut u64, received: &mut u64, until: (u64, u64)| {
+        while *sent < until.0 || *received < until.1 {
+            let len = agent.recv(&mut buf).expect("No StatsD packet received");
+            let packet = String::from_utf8_lossy(&buf[..len]);
+            for line in packet.lines() {
+                println!("[Agent] {}", line);
+                let counter = |name: &str| {
+                    line.strip_prefix(name)
+                        .and_then(|rest| rest.split('|').next())
+                        .map(|value| value.parse::<u64>().expect("Invalid counter value"))
+                };
+                *sent += counter("channels_console.sent:").unwrap_or_default();
+                *received += counter("channels_console.received:").unwrap_or_default();
+            }
+        }
+    };
+
+    for i in 1..=5 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+    read_until(&mut sent, &mut received, (5, 5));
+
+    // The counts drop to 0, the next push must not report it as a huge increase
+    #[cfg(feature = "statsd")]
+    channels_console::reset_all();
+    for i in 6..=7 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+    read_until(&mut sent, &mut received, (7, 7));
+
+    println!("[Agent] Total sent: {}", sent);
+    println!("[Agent] Total received: {}", received);
+}
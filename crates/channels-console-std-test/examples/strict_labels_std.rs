@@ -0,0 +1,29 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .strict_labels(true)
+        .build();
+
+    // Channels created at the same source can share a label
+    for _ in 0..2 {
+        let (tx, rx) = std::sync::mpsc::channel::<i32>();
+        #[cfg(feature = "channels-console")]
+        let (tx, rx) = channels_console::channel!((tx, rx), label = "worker");
+        tx.send(1).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+    println!("[Strict] worker channels registered");
+
+    let (jobs_tx, jobs_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (jobs_tx, jobs_rx) = channels_console::channel!((jobs_tx, jobs_rx), label = "jobs");
+
+    // Copy-pasted label, panics in debug builds
+    let (results_tx, results_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (results_tx, results_rx) =
+        channels_console::channel!((results_tx, results_rx), label = "jobs");
+
+    println!("[Strict] results channel registered");
+    drop((jobs_tx, jobs_rx, results_tx, results_rx));
+}
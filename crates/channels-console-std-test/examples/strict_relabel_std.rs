@@ -0,0 +1,25 @@
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .strict_labels(true)
+        .build();
+
+    let (jobs_tx, jobs_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (jobs_tx, jobs_rx) = channels_console::channel!((jobs_tx, jobs_rx), label = "jobs");
+
+    let (results_tx, results_rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (results_tx, results_rx) =
+        channels_console::channel!((results_tx, results_rx), label = "results");
+    println!("[Strict] results channel registered");
+
+    // Relabeled to a label already in use, panics in debug builds
+    #[cfg(feature = "channels-console")]
+    if let Some(id) = channels_console::last_channel_id() {
+        channels_console::set_label(id, "jobs");
+    }
+
+    println!("[Strict] results channel relabeled");
+    drop((jobs_tx, jobs_rx, results_tx, results_rx));
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Summary)
+        .build();
+
+    let (events_tx, events_rx) = std::sync::mpsc::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (events_tx, events_rx) =
+        channels_console::channel!((events_tx, events_rx), label = "events");
+
+    let (backlog_tx, _backlog_rx) = std::sync::mpsc::sync_channel::<u64>(4);
+    #[cfg(feature = "channels-console")]
+    let (backlog_tx, _backlog_rx) =
+        channels_console::channel!((backlog_tx, _backlog_rx), label = "backlog", capacity = 4);
+
+    for i in 0..1500 {
+        events_tx.send(i).expect("Failed to send");
+        events_rx.recv().expect("Failed to receive");
+    }
+
+    // Fills the receiver's buffer, the remaining messages wait in the proxy
+    for i in 0..8 {
+        backlog_tx.send(i).expect("Failed to send");
+    }
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(Duration::from_millis(50));
+}
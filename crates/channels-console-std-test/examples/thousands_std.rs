@@ -0,0 +1,26 @@
+fn main() {
+    // Optional separator override, e.g. `cargo run --example thousands_std -- .`
+    let _separator = std::env::args().nth(1).and_then(|arg| arg.chars().next());
+
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = {
+        let builder = channels_console::ChannelsGuardBuilder::new();
+        match _separator {
+            Some(separator) => builder.thousands_separator(separator),
+            None => builder,
+        }
+        .build()
+    };
+
+    let (tx, rx) = std::sync::mpsc::channel::<u32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "busy");
+
+    for i in 0..1234 {
+        tx.send(i).expect("Failed to send");
+        rx.recv().expect("Failed to receive");
+    }
+
+    // Give the forwarders a moment to record the last events
+    std::thread::sleep(std::time::Duration::from_millis(50));
+}
@@ -0,0 +1,20 @@
+fn main() {
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx));
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+
+    // Give the metrics server thread a moment to start
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // Linux exposes thread names (truncated to 15 bytes) under /proc
+    if let Ok(tasks) = std::fs::read_dir("/proc/self/task") {
+        for task in tasks.flatten() {
+            if let Ok(name) = std::fs::read_to_string(task.path().join("comm")) {
+                println!("[Thread] {}", name.trim());
+            }
+        }
+    }
+}
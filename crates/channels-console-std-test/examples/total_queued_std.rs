@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+#[allow(unused_variables)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    // Two stalled consumers, each channel holds a backlog
+    let mut receivers = Vec::new();
+    for label in ["orders", "payments"] {
+        let (tx, rx) = std::sync::mpsc::sync_channel::<u64>(2);
+        #[cfg(feature = "channels-console")]
+        let (tx, rx) = channels_console::channel!((tx, rx), capacity = 2, label = label);
+
+        std::thread::spawn(move || {
+            for i in 0..10 {
+                if tx.send(i).is_err() {
+                    break;
+                }
+            }
+        });
+        receivers.push(rx);
+    }
+
+    // Give the forwarders a moment to fill the queues
+    std::thread::sleep(Duration::from_millis(200));
+
+    #[cfg(feature = "channels-console")]
+    {
+        let snapshot = channels_console::snapshot();
+        let sum: u64 = snapshot.channels.iter().map(|c| c.queued_bytes).sum();
+        println!(
+            "[Total] total_queued_bytes={} sum={}",
+            snapshot.total_queued_bytes(),
+            sum
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(unix)]
+fn main() {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let socket_path =
+        std::env::temp_dir().join(format!("channels-console-{}.sock", std::process::id()));
+
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .metrics_unix_socket(&socket_path)
+        .build();
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "over-uds");
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+
+    // Wait for the server to bind the socket
+    let mut stream = None;
+    for _ in 0..20 {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        if let Ok(s) = UnixStream::connect(&socket_path) {
+            stream = Some(s);
+            break;
+        }
+    }
+    let mut stream = stream.expect("Failed to connect to the metrics socket");
+
+    stream
+        .write_all(b"GET /channels HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .expect("Failed to write request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("Failed to read response");
+    println!("[Client] {}", response.lines().next().unwrap_or_default());
+    if let Some((_, body)) = response.split_once("\r\n\r\n") {
+        println!("[Client] {}", body);
+    }
+
+    let tcp_listening = std::net::TcpStream::connect("127.0.0.1:6770").is_ok();
+    println!("[Client] TCP listening: {}", tcp_listening);
+
+    #[cfg(feature = "channels-console")]
+    channels_console::shutdown();
+    println!("[Client] Socket removed: {}", !socket_path.exists());
+}
+
+#[cfg(not(unix))]
+fn main() {}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "signals");
+
+    for _ in 0..3 {
+        tx.send(()).expect("Failed to send");
+    }
+    rx.recv().expect("Failed to receive");
+
+    // Give the forwarders a moment to record the messages
+    std::thread::sleep(Duration::from_millis(100));
+}
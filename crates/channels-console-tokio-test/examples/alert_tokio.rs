@@ -0,0 +1,34 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .alert_on(
+            |stats| stats.queued >= 5,
+            |stats| println!("[Alert] {} queued={}", stats.label, stats.queued),
+        )
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "backlog");
+
+    // Fill the queue past the threshold twice, draining it in between
+    for round in 1..=2 {
+        for i in 1..=10 {
+            tx.send(i).await.expect("Failed to send");
+        }
+        println!("[Producer] Round {} queued", round);
+        sleep(Duration::from_millis(300)).await;
+
+        for _ in 1..=10 {
+            rx.recv().await.expect("Failed to receive");
+        }
+        println!("[Consumer] Round {} drained", round);
+        sleep(Duration::from_millis(300)).await;
+    }
+
+    println!("\nAlert example completed!");
+}
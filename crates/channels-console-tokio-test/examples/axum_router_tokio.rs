@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use axum::routing::get;
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    // Served by the application's own server, no second socket
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .metrics_server(false)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<u64>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "jobs");
+
+    for i in 0..3 {
+        tx.send(i).await.expect("Failed to send");
+        rx.recv().await.expect("Failed to receive");
+    }
+
+    #[cfg(feature = "channels-console")]
+    println!(
+        "[Axum] embedded server port={:?}",
+        channels_console::metrics_port()
+    );
+
+    let app = axum::Router::new().route("/", get(|| async { "my app" }));
+    #[cfg(feature = "axum")]
+    let app = app.nest(
+        "/admin/channels",
+        channels_console::channels_metrics_router(),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:6788")
+        .await
+        .expect("Failed to bind");
+    println!("[Axum] serving on http://127.0.0.1:6788");
+
+    // Serve until a client resets the statistics with POST /admin/channels/reset
+    let reset = async {
+        for _ in 0..200 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            #[cfg(feature = "channels-console")]
+            if channels_console::channels_matching(|c| c.label == "jobs" && c.sent_count == 0).len()
+                == 1
+            {
+                println!("[Axum] jobs reset over HTTP");
+                return;
+            }
+        }
+        println!("[Axum] jobs not reset");
+    };
+    axum::serve(listener, app)
+        .with_graceful_shutdown(reset)
+        .await
+        .expect("Server failed");
+}
@@ -0,0 +1,27 @@
+use tokio::time::{sleep, timeout, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "backpressure");
+
+    // Count the sends accepted without a receiver before one blocks
+    let mut accepted = 0;
+    for i in 0..100 {
+        match timeout(Duration::from_millis(50), tx.send(i)).await {
+            Ok(result) => {
+                result.expect("Channel closed");
+                accepted += 1;
+            }
+            Err(_) => break,
+        }
+    }
+    println!("[Sender] accepted before blocking: {}", accepted);
+
+    for _ in 0..accepted {
+        rx.recv().await.expect("Failed to receive");
+    }
+    sleep(Duration::from_millis(50)).await;
+}
@@ -0,0 +1,35 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "blocking");
+
+    // Like with plain tokio channels, blocking calls must run outside the async runtime
+    let producer = std::thread::spawn(move || {
+        for i in 0..10 {
+            tx.blocking_send(i).expect("Failed to send");
+        }
+    });
+    let consumer = std::thread::spawn(move || {
+        let mut received = 0;
+        while rx.blocking_recv().is_some() {
+            received += 1;
+        }
+        received
+    });
+
+    producer.join().unwrap();
+    let received = consumer.join().unwrap();
+    println!("[Consumer] received: {}", received);
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(50)).await;
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Summary)
+        .build();
+
+    // Answered request
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (reply_tx, reply_rx) = channels_console::channel!((reply_tx, reply_rx), label = "answered");
+
+    reply_tx.send(42).expect("Failed to send");
+    let reply = reply_rx.await.expect("Failed to receive");
+    println!("Received reply {reply}");
+
+    // The caller gives up before the reply is sent
+    let (slow_tx, slow_rx) = tokio::sync::oneshot::channel::<u64>();
+    #[cfg(feature = "channels-console")]
+    let (slow_tx, slow_rx) = channels_console::channel!((slow_tx, slow_rx), label = "timed-out");
+
+    let responder = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let _ = slow_tx.send(7);
+    });
+    if tokio::time::timeout(Duration::from_millis(20), slow_rx)
+        .await
+        .is_err()
+    {
+        println!("Request timed out");
+    }
+    responder.await.expect("Responder panicked");
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Cancelled] {} state={} cancelled={}",
+            channel.label, channel.state, channel.cancelled
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use tokio::time::{sleep, timeout, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(2);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "cancelled-sends");
+
+    // Fill the channel until a send can't complete, its future is dropped by the timeout
+    let mut delivered = Vec::new();
+    for i in 0..20 {
+        match timeout(Duration::from_millis(20), tx.send(i)).await {
+            Ok(result) => {
+                result.expect("Channel closed");
+                delivered.push(i);
+            }
+            Err(_) => break,
+        }
+    }
+
+    // More cancelled sends while the channel stays full, none of them may be delivered
+    let mut cancelled = 0;
+    for i in 100..105 {
+        tokio::select! {
+            result = tx.send(i) => panic!("Send to a full channel completed: {:?}", result),
+            _ = sleep(Duration::from_millis(10)) => cancelled += 1,
+        }
+    }
+
+    // The channel keeps working once there is room again
+    let first = rx.recv().await.expect("Failed to receive");
+    tx.send(1000).await.expect("Failed to send");
+    delivered.push(1000);
+    drop(tx);
+
+    let mut received = vec![first];
+    while let Some(msg) = rx.recv().await {
+        received.push(msg);
+    }
+    assert_eq!(
+        received, delivered,
+        "Messages lost, duplicated or reordered"
+    );
+
+    println!("[Sender] delivered: {}", delivered.len());
+    println!("[Sender] cancelled: {}", cancelled);
+    println!("[Receiver] received: {}", received.len());
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(50)).await;
+}
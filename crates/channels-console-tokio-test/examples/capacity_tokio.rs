@@ -0,0 +1,30 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(5);
+
+    // Messages sent before the channel is instrumented
+    tx.send(1).await.expect("Failed to send");
+    tx.send(2).await.expect("Failed to send");
+
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "prefilled");
+
+    tx.send(3).await.expect("Failed to send");
+    for _ in 0..3 {
+        rx.recv().await.expect("Failed to receive");
+    }
+    println!("[Capacity] received 3 messages");
+
+    sleep(Duration::from_millis(50)).await;
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Capacity] {} {} capacity={:?}",
+            channel.label, channel.channel_type, channel.capacity
+        );
+    }
+}
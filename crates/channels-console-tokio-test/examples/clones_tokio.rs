@@ -0,0 +1,37 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<usize>(8);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "fan-in");
+
+    // Clones of the instrumented sender feed the same proxy, so they share one entry
+    for task in 0..10 {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            for i in 0..5 {
+                tx.send(task * 5 + i).await.expect("Failed to send");
+            }
+        });
+    }
+    drop(tx);
+
+    let mut received = 0;
+    while rx.recv().await.is_some() {
+        received += 1;
+    }
+    println!("[Clones] received {received} messages");
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(50)).await;
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Clones] {} sent={} received={} state={}",
+            channel.label, channel.sent_count, channel.received_count, channel.state
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use tokio::time::{sleep, timeout, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(1);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "dropped-sends");
+
+    // Hold the only slot, so sends stay pending without any message being sent
+    let permit = tx.reserve().await.expect("Failed to reserve");
+
+    let timed_out = timeout(Duration::ZERO, tx.send(1)).await.is_err();
+    println!("[Sender] timed out: {}", timed_out);
+
+    let lost_race = tokio::select! {
+        biased;
+        _ = std::future::ready(()) => true,
+        _ = tx.send(2) => false,
+    };
+    println!("[Sender] lost race: {}", lost_race);
+
+    drop(permit);
+    drop(tx);
+    assert_eq!(rx.recv().await, None, "A dropped send was delivered");
+
+    // Give the forwarders a moment to record any events
+    sleep(Duration::from_millis(50)).await;
+}
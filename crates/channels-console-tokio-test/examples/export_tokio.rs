@@ -0,0 +1,20 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "exported");
+
+    for i in 0..5 {
+        tx.send(i).await.expect("Failed to send");
+    }
+    rx.recv().await.expect("Failed to receive");
+
+    // Keep the metrics server up long enough to be scraped
+    sleep(Duration::from_secs(5)).await;
+}
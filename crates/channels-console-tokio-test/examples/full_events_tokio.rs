@@ -0,0 +1,32 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "refilled");
+
+    // The outer receiver and the receive forwarder take 2 messages, the next 4 fill the
+    // observed queue. Fill it and drain it completely 3 times.
+    for _ in 0..3 {
+        for i in 0..6 {
+            tx.send(i).await.expect("Failed to send");
+        }
+        sleep(Duration::from_millis(20)).await;
+        for _ in 0..6 {
+            rx.recv().await.expect("Failed to receive");
+        }
+        sleep(Duration::from_millis(20)).await;
+    }
+
+    sleep(Duration::from_millis(150)).await;
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Full] {} full_events={} queued={}",
+            channel.label, channel.full_events, channel.queued
+        );
+    }
+}
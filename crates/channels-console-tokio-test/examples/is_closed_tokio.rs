@@ -0,0 +1,41 @@
+use tokio::time::{sleep, timeout, Duration};
+
+/// Poll `is_closed` until it reports true, giving the proxies time to propagate the close.
+async fn wait_until(mut is_closed: impl FnMut() -> bool) -> bool {
+    timeout(Duration::from_secs(2), async {
+        while !is_closed() {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .is_ok()
+}
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    // Sender detects the receiver going away without attempting a send
+    let (tx, rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "receiver-dropped");
+
+    drop(rx);
+    let closed = wait_until(|| tx.is_closed()).await;
+    println!("[Sender] is_closed: {}", closed);
+
+    // Receiver detects all senders going away
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "sender-dropped");
+
+    drop(tx);
+    let closed = wait_until(|| rx.is_closed()).await;
+    println!("[Receiver] is_closed: {}", closed);
+
+    println!("\nIs closed example completed!");
+}
@@ -6,7 +6,7 @@ struct Actor {
 #[allow(unused_mut)]
 #[tokio::main]
 async fn main() {
-    let actor1 = Actor {
+    let _actor1 = Actor {
         name: "Actor 1".to_string(),
     };
 
@@ -20,7 +20,7 @@ async fn main() {
         let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
 
         #[cfg(feature = "channels-console")]
-        let (tx, mut rx) = channels_console::channel!((tx, rx), label = actor1.name.clone());
+        let (tx, mut rx) = channels_console::channel!((tx, rx), label = _actor1.name.clone());
 
         println!("  - Created bounded channel {}", i);
 
@@ -0,0 +1,28 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    println!("Leak Example:");
+    println!("- Bounded channel with capacity 500");
+    println!("- Producer sends 1 message every 10ms");
+    println!("- Consumer never reads, so the queue keeps growing\n");
+
+    let (tx, mut _rx) = tokio::sync::mpsc::channel::<i32>(500);
+    #[cfg(feature = "channels-console")]
+    let (tx, _rx) = channels_console::channel!((tx, _rx), label = "leaky");
+
+    for i in 1..=350 {
+        tx.send(i).await.expect("Failed to send");
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    println!("\nLeak example completed!");
+
+    // Print statistics while the channel is still open
+    #[cfg(feature = "channels-console")]
+    drop(_channels_guard);
+}
@@ -0,0 +1,36 @@
+// Instruments channels from a plain `main`, without a running tokio runtime
+#[allow(unused_mut)]
+fn main() {
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "std");
+
+    let (mpsc_tx, mut mpsc_rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (mpsc_tx, mut mpsc_rx) = channels_console::channel!((mpsc_tx, mpsc_rx), label = "mpsc");
+
+    let (oneshot_tx, oneshot_rx) = tokio::sync::oneshot::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (oneshot_tx, oneshot_rx) =
+        channels_console::channel!((oneshot_tx, oneshot_rx), label = "oneshot");
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+    mpsc_tx.blocking_send(1).expect("Failed to send");
+    mpsc_rx.blocking_recv().expect("Failed to receive");
+    oneshot_tx.send(1).expect("Failed to send");
+    oneshot_rx.blocking_recv().expect("Failed to receive");
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[NoRuntime] {} sent={} received={} runtime={}",
+            channel.label,
+            channel.sent_count,
+            channel.received_count,
+            channel.runtime.is_some()
+        );
+    }
+}
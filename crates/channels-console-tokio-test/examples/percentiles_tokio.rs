@@ -0,0 +1,36 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "bursty");
+
+    // The outer receiver takes one message and the receive forwarder holds another, so it
+    // takes capacity + 2 sends to fill the observed queue
+    for _ in 0..20 {
+        for i in 0..12 {
+            tx.send(i).await.expect("Failed to send");
+        }
+        sleep(Duration::from_millis(5)).await;
+        for _ in 0..12 {
+            rx.recv().await.expect("Failed to receive");
+        }
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    sleep(Duration::from_millis(50)).await;
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Percentiles] {} capacity={:?} p50={} p95={} p99={}",
+            channel.label,
+            channel.capacity,
+            channel.queued_p50,
+            channel.queued_p95,
+            channel.queued_p99
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "paused");
+
+    let (steady_tx, mut steady_rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (steady_tx, mut steady_rx) =
+        channels_console::channel!((steady_tx, steady_rx), label = "steady");
+
+    // A burst before disabling, its queue depths are forgotten once enabled again
+    for i in 0..8 {
+        steady_tx.send(i).await.expect("Failed to send");
+    }
+    sleep(Duration::from_millis(50)).await;
+    for _ in 0..8 {
+        steady_rx.recv().await.expect("Failed to receive");
+    }
+    sleep(Duration::from_millis(50)).await;
+    #[cfg(feature = "channels-console")]
+    channels_console::disable_channel("steady");
+    #[cfg(feature = "channels-console")]
+    channels_console::enable_channel("steady");
+    for i in 0..20 {
+        steady_tx.send(i).await.expect("Failed to send");
+        steady_rx.recv().await.expect("Failed to receive");
+    }
+
+    // Queue up messages, then receive them while the channel is disabled
+    for i in 0..5 {
+        tx.send(i).await.expect("Failed to send");
+    }
+    sleep(Duration::from_millis(300)).await;
+    #[cfg(feature = "channels-console")]
+    channels_console::disable_channel("paused");
+    for _ in 0..5 {
+        rx.recv().await.expect("Failed to receive");
+    }
+    sleep(Duration::from_millis(50)).await;
+    #[cfg(feature = "channels-console")]
+    channels_console::enable_channel("paused");
+
+    // Received right away, these must not be paired with the sends from before
+    for i in 0..5 {
+        tx.send(i).await.expect("Failed to send");
+        rx.recv().await.expect("Failed to receive");
+    }
+    sleep(Duration::from_millis(150)).await;
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        if let Some(dwell) = channel.dwell_time {
+            println!(
+                "[Dwell] {} max_ms={}",
+                channel.label,
+                dwell.max_ns / 1_000_000
+            );
+        }
+        println!("[Depth] {} p99={}", channel.label, channel.queued_p99);
+    }
+}
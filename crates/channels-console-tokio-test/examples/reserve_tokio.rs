@@ -0,0 +1,36 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "permits");
+
+    for i in 0..3 {
+        let permit = tx.reserve().await.expect("Failed to reserve");
+        permit.send(i);
+    }
+
+    // A permit that is dropped without sending releases its slot and sends nothing
+    let unused = tx.reserve().await.expect("Failed to reserve");
+    drop(unused);
+
+    let permit = tx.try_reserve().expect("Failed to try_reserve");
+    permit.send(3);
+
+    drop(tx);
+    let mut received = Vec::new();
+    while let Some(msg) = rx.recv().await {
+        received.push(msg);
+    }
+    println!("[Receiver] received: {:?}", received);
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(50)).await;
+}
@@ -0,0 +1,27 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "reserved");
+
+    let first = tx.reserve().await.expect("Failed to reserve");
+    let second = tx.try_reserve().expect("Failed to try_reserve");
+
+    // Keep the permits while the metrics server is scraped
+    sleep(Duration::from_secs(3)).await;
+
+    first.send(1);
+    drop(second);
+    rx.recv().await.expect("Failed to receive");
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(200)).await;
+}
@@ -0,0 +1,35 @@
+#[allow(unused_mut)]
+fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime");
+    println!("Runtime id: {}", runtime.handle().id());
+
+    runtime.block_on(async {
+        tokio::spawn(async {
+            let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+            #[cfg(feature = "channels-console")]
+            let (tx, mut rx) = channels_console::channel!((tx, rx), label = "on-runtime");
+
+            tx.send(1).await.expect("Failed to send");
+            rx.recv().await.expect("Failed to receive");
+        })
+        .await
+        .expect("Task failed");
+    });
+
+    let (tx, rx) = std::sync::mpsc::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, rx) = channels_console::channel!((tx, rx), label = "off-runtime");
+    tx.send(1).expect("Failed to send");
+    rx.recv().expect("Failed to receive");
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+}
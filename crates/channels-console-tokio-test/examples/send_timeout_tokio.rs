@@ -0,0 +1,46 @@
+#[cfg(feature = "channels-console")]
+use channels_console::SendTimeoutExt;
+use tokio::sync::mpsc::error::SendTimeoutError;
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(2);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "timed-sends");
+
+    // Nobody receives yet, so sends start timing out once the queue is full
+    let mut delivered = 0;
+    let mut timed_out = 0;
+    for i in 0..20 {
+        #[cfg(feature = "channels-console")]
+        let result = tx
+            .instrumented_send_timeout(i, Duration::from_millis(20))
+            .await;
+        #[cfg(not(feature = "channels-console"))]
+        let result = tx.send_timeout(i, Duration::from_millis(20)).await;
+        match result {
+            Ok(()) => delivered += 1,
+            Err(SendTimeoutError::Timeout(_)) => timed_out += 1,
+            Err(SendTimeoutError::Closed(_)) => panic!("Channel closed"),
+        }
+    }
+    println!("[Sender] delivered: {}", delivered);
+    println!("[Sender] timed out: {}", timed_out);
+
+    drop(tx);
+    let mut received = 0;
+    while rx.recv().await.is_some() {
+        received += 1;
+    }
+    println!("[Receiver] received: {}", received);
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(50)).await;
+}
@@ -0,0 +1,41 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "producers");
+
+    // One long-lived producer next to the original sender
+    let long_lived = tx.clone();
+
+    // Short-lived producers one after another, clones are counted by polling, so each
+    // one lives, and is gone, long enough to be noticed
+    for i in 0..3 {
+        let producer = tx.clone();
+        tokio::spawn(async move {
+            producer.send(i).expect("Failed to send");
+            sleep(Duration::from_millis(250)).await;
+        })
+        .await
+        .expect("Producer task failed");
+        sleep(Duration::from_millis(250)).await;
+    }
+    long_lived.send(3).expect("Failed to send");
+
+    for _ in 0..4 {
+        rx.recv().await.expect("Failed to receive");
+    }
+
+    // Give the forwarders a moment to notice the dropped clones
+    sleep(Duration::from_millis(250)).await;
+
+    #[cfg(feature = "channels-console")]
+    for channel in channels_console::snapshot().channels {
+        println!(
+            "[Senders] {} active={:?} created={:?}",
+            channel.label, channel.active_senders, channel.total_senders_created
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use tokio::time::{sleep, Duration, Instant};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    #[cfg(feature = "channels-console")]
+    let mut updates = channels_console::subscribe();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(16);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "hot");
+
+    let producer = tokio::spawn(async move {
+        // Keep the channel busy for a second so the collector updates on every tick
+        let start = Instant::now();
+        let mut i = 0;
+        while start.elapsed() < Duration::from_secs(1) {
+            tx.send(i).await.expect("Failed to send");
+            i += 1;
+            sleep(Duration::from_millis(1)).await;
+        }
+    });
+    let consumer = tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+    #[cfg(feature = "channels-console")]
+    {
+        let mut snapshots = 0;
+        let mut last_sent = 0;
+        let deadline = sleep(Duration::from_millis(1200));
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                update = updates.recv() => {
+                    let snapshot = update.expect("Failed to receive snapshot");
+                    snapshots += 1;
+                    if let Some(channel) = snapshot.channels.iter().find(|c| c.label == "hot") {
+                        last_sent = channel.sent_count;
+                    }
+                }
+                _ = &mut deadline => break,
+            }
+        }
+        println!("[Subscriber] snapshots={snapshots} last_sent={last_sent}");
+    }
+
+    producer.await.expect("Producer failed");
+    consumer.await.expect("Consumer failed");
+
+    // Give the forwarders a moment to record the last events
+    sleep(Duration::from_millis(50)).await;
+}
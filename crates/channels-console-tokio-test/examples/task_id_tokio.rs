@@ -0,0 +1,29 @@
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "outside-task");
+
+    let handle = tokio::spawn(async move {
+        println!("Task id: {}", tokio::task::id());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+        #[cfg(feature = "channels-console")]
+        let (tx, mut rx) = channels_console::channel!((tx, rx), label = "inside-task");
+
+        tx.send(1).await.expect("Failed to send");
+        rx.recv().await.expect("Failed to receive");
+    });
+    handle.await.expect("Task failed");
+
+    tx.send(1).expect("Failed to send");
+    rx.recv().await.expect("Failed to receive");
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+}
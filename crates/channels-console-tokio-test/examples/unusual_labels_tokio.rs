@@ -0,0 +1,20 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuardBuilder::new()
+        .format(channels_console::Format::Json)
+        .build();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(10);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "say \"hi\"\nback\\slash, a=b");
+
+    tx.send(1).await.expect("Failed to send");
+    rx.recv().await.expect("Failed to receive");
+
+    // Keep the metrics server up long enough to be scraped
+    sleep(Duration::from_secs(3)).await;
+}
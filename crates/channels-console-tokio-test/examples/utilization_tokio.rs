@@ -0,0 +1,36 @@
+use tokio::time::{sleep, Duration};
+
+#[allow(unused_mut)]
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "channels-console")]
+    let _channels_guard = channels_console::ChannelsGuard::new();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<i32>(4);
+    #[cfg(feature = "channels-console")]
+    let (tx, mut rx) = channels_console::channel!((tx, rx), label = "half-full");
+
+    let (unbounded_tx, mut unbounded_rx) = tokio::sync::mpsc::unbounded_channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (unbounded_tx, mut unbounded_rx) =
+        channels_console::channel!((unbounded_tx, unbounded_rx), label = "unbounded");
+
+    let (oneshot_tx, oneshot_rx) = tokio::sync::oneshot::channel::<i32>();
+    #[cfg(feature = "channels-console")]
+    let (oneshot_tx, oneshot_rx) =
+        channels_console::channel!((oneshot_tx, oneshot_rx), label = "oneshot");
+
+    // The outer receiver takes one message and the receive forwarder holds another, which
+    // leaves 2 of the 4 slots of the observed queue in use
+    for i in 0..4 {
+        tx.send(i).await.expect("Failed to send");
+    }
+    for i in 0..3 {
+        unbounded_tx.send(i).expect("Failed to send");
+    }
+    oneshot_tx.send(1).expect("Failed to send");
+
+    sleep(Duration::from_millis(200)).await;
+
+    drop((rx, unbounded_rx, oneshot_rx));
+}
@@ -4,3 +4,4 @@ pub(crate) mod views;
 pub(crate) mod widgets;
 
 pub use app::ConsoleArgs;
+pub(crate) use views::throughput::{DEFAULT_WINDOW, MAX_WINDOW};
@@ -8,12 +8,27 @@ use ratatui::{
     DefaultTerminal, Frame,
 };
 use std::time::{Duration, Instant};
-use std::{collections::HashMap, io};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
 
-use super::http::{fetch_channel_logs, fetch_channels};
+use super::http::{fetch_channel_logs, fetch_channels, metrics_url, post_reset};
 use super::views::bottom_bar::render_bottom_bar;
+use super::views::channels::channels_table_text;
+use super::views::help::render_help_popup;
 use super::views::main_view::render_main_view;
-use super::views::top_bar::render_top_bar;
+use super::views::throughput::{
+    render_throughput_chart, ThroughputHistory, DEFAULT_WINDOW, MAX_WINDOW,
+};
+use super::views::top_bar::{render_disconnected_banner, render_top_bar};
+
+/// Channels draining below this ratio are highlighted once it persists
+const LOW_DRAIN_RATIO: f64 = 0.9;
+const LOW_DRAIN_PERSISTENCE: Duration = Duration::from_secs(5);
+
+/// How long a notice stays in the bottom bar
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
 
 /// Represents which UI component has focus
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,6 +38,16 @@ pub(crate) enum Focus {
     Inspect,
 }
 
+/// State of the connection to the metrics server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Connection {
+    /// No scrape has succeeded yet
+    Connecting,
+    Connected,
+    /// The last scrape failed after earlier ones succeeded, the shown data is stale
+    Disconnected,
+}
+
 /// Cached logs with a lookup map for received entries
 pub(crate) struct CachedLogs {
     pub(crate) logs: ChannelLogs,
@@ -34,6 +59,14 @@ pub struct ConsoleArgs {
     /// Port for the metrics server
     #[arg(long, default_value = "6770")]
     pub metrics_port: u16,
+
+    /// Path prefix the metrics server serves its endpoints under
+    #[arg(long, default_value = "")]
+    pub metrics_path: String,
+
+    /// Seconds of history kept by the throughput chart, one sample per second
+    #[arg(long, default_value_t = DEFAULT_WINDOW, value_parser = clap::value_parser!(u64).range(2..=MAX_WINDOW))]
+    pub window: u64,
 }
 
 pub(crate) struct App {
@@ -43,6 +76,8 @@ pub(crate) struct App {
     last_refresh: Instant,
     last_successful_fetch: Option<Instant>,
     metrics_port: u16,
+    /// Base URL of the endpoints, including the metrics path
+    metrics_url: String,
     last_render_duration: Duration,
     table_state: TableState,
     logs_table_state: TableState,
@@ -53,6 +88,20 @@ pub(crate) struct App {
     inspected_log: Option<LogEntry>,
     agent: ureq::Agent,
     current_elapsed_ns: u64,
+    /// Channel shown next to the selected one in the compare panel
+    pinned_channel: Option<u64>,
+    /// Channels whose rows keep showing the snapshot taken when they were frozen
+    frozen: HashMap<u64, SerializableChannelStats>,
+    throughput: ThroughputHistory,
+    show_throughput: bool,
+    /// When each channel's drain ratio dropped below `LOW_DRAIN_RATIO`
+    low_drain_since: HashMap<u64, Instant>,
+    /// Waiting for the keypress confirming a reset of the statistics
+    confirming_reset: bool,
+    /// Message shown in the bottom bar, with the time it was shown
+    notice: Option<(String, Instant)>,
+    /// The help popup is open, other keys are ignored until it's closed
+    show_help: bool,
 }
 
 impl ConsoleArgs {
@@ -71,6 +120,7 @@ impl ConsoleArgs {
             last_refresh: Instant::now(),
             last_successful_fetch: None,
             metrics_port: self.metrics_port,
+            metrics_url: metrics_url(self.metrics_port, &self.metrics_path),
             last_render_duration: Duration::from_millis(0),
             table_state: TableState::default().with_selected(0),
             logs_table_state: TableState::default(),
@@ -81,6 +131,14 @@ impl ConsoleArgs {
             inspected_log: None,
             agent,
             current_elapsed_ns: 0,
+            pinned_channel: None,
+            frozen: HashMap::new(),
+            throughput: ThroughputHistory::new(self.window),
+            show_throughput: false,
+            low_drain_since: HashMap::new(),
+            confirming_reset: false,
+            notice: None,
+            show_help: false,
         };
 
         let mut terminal = ratatui::init();
@@ -121,10 +179,14 @@ impl App {
             .and_then(|idx| self.stats.get(idx))
             .map(|stat| stat.id);
 
-        match fetch_channels(&self.agent, self.metrics_port) {
+        match fetch_channels(&self.agent, &self.metrics_url) {
             Ok(channels) => {
                 self.current_elapsed_ns = channels.current_elapsed_ns;
                 self.stats = channels.channels;
+                self.throughput
+                    .record(self.current_elapsed_ns, channels.generation, &self.stats);
+                self.track_low_drain();
+                self.apply_frozen();
                 self.error = None;
                 self.last_successful_fetch = Some(Instant::now());
 
@@ -146,6 +208,12 @@ impl App {
                     }
                 }
 
+                if let Some(pinned_id) = self.pinned_channel {
+                    if !self.stats.iter().any(|stat| stat.id == pinned_id) {
+                        self.pinned_channel = None;
+                    }
+                }
+
                 if self.show_logs {
                     self.refresh_logs();
                 }
@@ -157,6 +225,40 @@ impl App {
         self.last_refresh = Instant::now();
     }
 
+    /// Replace the rows of frozen channels with their snapshots, forgetting removed channels
+    fn apply_frozen(&mut self) {
+        let live: HashSet<u64> = self.stats.iter().map(|stat| stat.id).collect();
+        self.frozen.retain(|id, _| live.contains(id));
+        for stat in &mut self.stats {
+            if let Some(snapshot) = self.frozen.get(&stat.id) {
+                *stat = snapshot.clone();
+            }
+        }
+    }
+
+    fn track_low_drain(&mut self) {
+        let now = Instant::now();
+        let low: HashSet<u64> = self
+            .stats
+            .iter()
+            .filter(|stat| stat.drain_ratio.is_some_and(|r| r < LOW_DRAIN_RATIO))
+            .map(|stat| stat.id)
+            .collect();
+        self.low_drain_since.retain(|id, _| low.contains(id));
+        for id in low {
+            self.low_drain_since.entry(id).or_insert(now);
+        }
+    }
+
+    /// Channels whose drain ratio has stayed low for `LOW_DRAIN_PERSISTENCE`
+    fn lagging_channels(&self) -> HashSet<u64> {
+        self.low_drain_since
+            .iter()
+            .filter(|(_, since)| since.elapsed() >= LOW_DRAIN_PERSISTENCE)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         self.render_ui(frame);
     }
@@ -173,14 +275,36 @@ impl App {
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.confirming_reset {
+            self.confirming_reset = false;
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.reset_stats(),
+                _ => self.show_notice("Reset cancelled"),
+            }
+            return;
+        }
+
+        if self.show_help {
+            match key_event.code {
+                KeyCode::Char('?') | KeyCode::Esc => self.show_help = false,
+                KeyCode::Char('q') | KeyCode::Char('Q') => self.exit(),
+                _ => {}
+            }
+            return;
+        }
+
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => self.exit(),
+            KeyCode::Char('?') => self.show_help = true,
             KeyCode::Char('o') | KeyCode::Char('O') => match self.focus {
                 Focus::Inspect => self.close_inspect_and_refocus_channels(),
                 Focus::Logs => self.hide_logs(),
                 Focus::Channels => self.toggle_logs(),
             },
             KeyCode::Char('p') | KeyCode::Char('P') => self.toggle_pause(),
+            KeyCode::Char('t') | KeyCode::Char('T') => self.show_throughput = !self.show_throughput,
+            KeyCode::Char('r') | KeyCode::Char('R') => self.confirming_reset = true,
+            KeyCode::Char('e') | KeyCode::Char('E') => self.export_view(),
             KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
                 if self.focus == Focus::Inspect {
                     self.close_inspect_only();
@@ -190,6 +314,12 @@ impl App {
             }
             KeyCode::Right | KeyCode::Char('l') => self.focus_logs(),
             KeyCode::Char('i') | KeyCode::Char('I') => self.toggle_inspect(),
+            KeyCode::Char('c') | KeyCode::Char('C') if self.focus == Focus::Channels => {
+                self.toggle_pin()
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') if self.focus == Focus::Channels => {
+                self.toggle_freeze()
+            }
             KeyCode::Up | KeyCode::Char('k') => match self.focus {
                 Focus::Channels => self.select_previous_channel(),
                 Focus::Logs | Focus::Inspect => self.select_previous_log(),
@@ -246,6 +376,7 @@ impl App {
                 self.hide_logs();
             } else {
                 self.show_logs = true;
+                self.pinned_channel = None;
                 if self.paused {
                     self.logs = None;
                 } else {
@@ -272,7 +403,7 @@ impl App {
         if let Some(selected) = self.table_state.selected() {
             if !self.stats.is_empty() && selected < self.stats.len() {
                 let channel_id = self.stats[selected].id;
-                if let Ok(logs) = fetch_channel_logs(&self.agent, self.metrics_port, channel_id) {
+                if let Ok(logs) = fetch_channel_logs(&self.agent, &self.metrics_url, channel_id) {
                     let received_map: std::collections::HashMap<u64, LogEntry> = logs
                         .received_logs
                         .iter()
@@ -295,6 +426,88 @@ impl App {
         }
     }
 
+    /// Pin the selected channel for comparison, or unpin it if it is already pinned
+    fn toggle_pin(&mut self) {
+        let Some(selected_id) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.stats.get(idx))
+            .map(|stat| stat.id)
+        else {
+            return;
+        };
+
+        if self.pinned_channel == Some(selected_id) {
+            self.pinned_channel = None;
+        } else {
+            self.pinned_channel = Some(selected_id);
+            if self.show_logs {
+                self.hide_logs();
+            }
+        }
+    }
+
+    /// Freeze the selected channel's row at its current values, or let it update again
+    fn toggle_freeze(&mut self) {
+        let Some(stat) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.stats.get(idx))
+        else {
+            return;
+        };
+
+        if self.frozen.remove(&stat.id).is_none() {
+            self.frozen.insert(stat.id, stat.clone());
+        }
+    }
+
+    fn selected_frozen(&self) -> bool {
+        self.table_state
+            .selected()
+            .and_then(|idx| self.stats.get(idx))
+            .is_some_and(|stat| self.frozen.contains_key(&stat.id))
+    }
+
+    /// Zero the statistics of the monitored process and show them right away
+    fn reset_stats(&mut self) {
+        match post_reset(&self.agent, &self.metrics_url) {
+            Ok(()) => {
+                self.show_notice("Statistics reset");
+                self.refresh_data();
+            }
+            Err(e) => self.show_notice(format!("Reset failed: {}", e)),
+        }
+    }
+
+    /// Save the channels table as shown to a text file in the working directory
+    fn export_view(&mut self) {
+        let frozen: HashSet<u64> = self.frozen.keys().copied().collect();
+        let text = channels_table_text(&self.stats, self.pinned_channel, &frozen);
+        let file_name = format!(
+            "channels-console-view-{}.txt",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = std::env::current_dir()
+            .map(|dir| dir.join(&file_name))
+            .unwrap_or_else(|_| file_name.into());
+        match std::fs::write(&path, text) {
+            Ok(()) => self.show_notice(format!("Saved view to {}", path.display())),
+            Err(e) => self.show_notice(format!("Export failed: {}", e)),
+        }
+    }
+
+    fn show_notice(&mut self, notice: impl Into<String>) {
+        self.notice = Some((notice.into(), Instant::now()));
+    }
+
+    fn current_notice(&self) -> Option<&str> {
+        self.notice
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < NOTICE_DURATION)
+            .map(|(notice, _)| notice.as_str())
+    }
+
     fn toggle_pause(&mut self) {
         self.paused = !self.paused;
     }
@@ -395,16 +608,31 @@ impl App {
 }
 
 impl App {
+    fn connection(&self) -> Connection {
+        match (self.last_successful_fetch, &self.error) {
+            (None, _) => Connection::Connecting,
+            (Some(_), None) => Connection::Connected,
+            (Some(_), Some(_)) => Connection::Disconnected,
+        }
+    }
+
     fn render_ui(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        let connection = self.connection();
 
-        // Create 3-row vertical layout: top bar, main view, bottom bar
+        // Create 4-row vertical layout: top bar, disconnection banner, main view, bottom bar
+        let banner_height = if connection == Connection::Disconnected {
+            1
+        } else {
+            0
+        };
         let chunks = Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Top bar
-                Constraint::Min(0),    // Main view (fills remaining space)
-                Constraint::Length(3), // Bottom bar
+                Constraint::Length(3),             // Top bar
+                Constraint::Length(banner_height), // Banner
+                Constraint::Min(0),                // Main view (fills remaining space)
+                Constraint::Length(3),             // Bottom bar
             ])
             .split(area);
 
@@ -414,14 +642,38 @@ impl App {
             chunks[0],
             self.paused,
             self.last_successful_fetch,
-            self.error.is_some(),
-            !self.stats.is_empty(),
+            connection,
         );
 
+        if connection == Connection::Disconnected {
+            render_disconnected_banner(
+                frame,
+                chunks[1],
+                self.metrics_port,
+                self.last_successful_fetch,
+            );
+        }
+
+        let main_area = if self.show_throughput {
+            let [chart_area, main_area] =
+                Layout::vertical([Constraint::Percentage(35), Constraint::Min(0)]).areas(chunks[2]);
+            render_throughput_chart(&self.throughput, chart_area, frame);
+            main_area
+        } else {
+            chunks[2]
+        };
+
+        let pinned = self
+            .pinned_channel
+            .and_then(|id| self.stats.iter().find(|stat| stat.id == id))
+            .cloned();
+        let lagging = self.lagging_channels();
+        let frozen: HashSet<u64> = self.frozen.keys().copied().collect();
+
         // Render main content area
         render_main_view(
             frame,
-            chunks[1],
+            main_area,
             &self.stats,
             &self.error,
             self.metrics_port,
@@ -433,8 +685,26 @@ impl App {
             self.paused,
             &self.inspected_log,
             self.current_elapsed_ns,
+            pinned.as_ref(),
+            &lagging,
+            &frozen,
         );
 
-        render_bottom_bar(frame, chunks[2], self.focus, self.last_render_duration);
+        render_bottom_bar(
+            frame,
+            chunks[3],
+            self.focus,
+            self.pinned_channel.is_some(),
+            self.selected_frozen(),
+            self.show_throughput,
+            self.confirming_reset,
+            self.current_notice(),
+            connection,
+            self.last_render_duration,
+        );
+
+        if self.show_help {
+            render_help_popup(area, frame);
+        }
     }
 }
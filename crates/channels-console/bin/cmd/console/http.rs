@@ -1,17 +1,21 @@
 use channels_console::{ChannelLogs, ChannelsJson, StreamsJson};
 use eyre::Result;
 
+/// Base URL of the metrics server, `path` being the prefix it serves its endpoints under
+pub(crate) fn metrics_url(port: u16, path: &str) -> String {
+    format!("http://127.0.0.1:{}{}", port, path.trim_end_matches('/'))
+}
+
 /// Fetches channel metrics from the HTTP server
-pub(crate) fn fetch_channels(agent: &ureq::Agent, port: u16) -> Result<ChannelsJson> {
-    let url = format!("http://127.0.0.1:{}/channels", port);
+pub(crate) fn fetch_channels(agent: &ureq::Agent, base_url: &str) -> Result<ChannelsJson> {
+    let url = format!("{}/channels", base_url);
     let channels: ChannelsJson = agent.get(&url).call()?.body_mut().read_json()?;
     Ok(channels)
 }
 
-#[allow(dead_code)]
 /// Fetches stream metrics from the HTTP server
-pub(crate) fn fetch_streams(agent: &ureq::Agent, port: u16) -> Result<StreamsJson> {
-    let url = format!("http://127.0.0.1:{}/streams", port);
+pub(crate) fn fetch_streams(agent: &ureq::Agent, base_url: &str) -> Result<StreamsJson> {
+    let url = format!("{}/streams", base_url);
     let streams: StreamsJson = agent.get(&url).call()?.body_mut().read_json()?;
     Ok(streams)
 }
@@ -19,10 +23,17 @@ pub(crate) fn fetch_streams(agent: &ureq::Agent, port: u16) -> Result<StreamsJso
 /// Fetches logs for a specific channel from the HTTP server
 pub(crate) fn fetch_channel_logs(
     agent: &ureq::Agent,
-    port: u16,
+    base_url: &str,
     channel_id: u64,
 ) -> Result<ChannelLogs> {
-    let url = format!("http://127.0.0.1:{}/channels/{}/logs", port, channel_id);
+    let url = format!("{}/channels/{}/logs", base_url, channel_id);
     let logs: ChannelLogs = agent.get(&url).call()?.body_mut().read_json()?;
     Ok(logs)
 }
+
+/// Zeroes the statistics of all channels of the monitored process
+pub(crate) fn post_reset(agent: &ureq::Agent, base_url: &str) -> Result<()> {
+    let url = format!("{}/reset", base_url);
+    agent.post(&url).send_empty()?;
+    Ok(())
+}
@@ -1,6 +1,9 @@
 pub(crate) mod bottom_bar;
 pub(crate) mod channels;
+pub(crate) mod compare;
+pub(crate) mod help;
 pub(crate) mod inspect;
 pub(crate) mod logs;
 pub(crate) mod main_view;
+pub(crate) mod throughput;
 pub(crate) mod top_bar;
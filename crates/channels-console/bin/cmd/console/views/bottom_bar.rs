@@ -8,29 +8,67 @@ use ratatui::{
 };
 use std::time::Duration;
 
-use crate::cmd::console::app::Focus;
+use crate::cmd::console::app::{Connection, Focus};
 
 /// Renders the bottom controls bar showing context-aware keybindings
+#[allow(clippy::too_many_arguments)]
 pub fn render_bottom_bar(
     frame: &mut Frame,
     area: Rect,
     focus: Focus,
+    comparing: bool,
+    frozen: bool,
+    showing_throughput: bool,
+    confirming_reset: bool,
+    notice: Option<&str>,
+    connection: Connection,
     _last_render_duration: Duration,
 ) {
     let controls_line = match focus {
+        _ if confirming_reset => Line::from(vec![
+            " Reset the statistics of all channels? ".yellow().bold(),
+            "<y> ".blue().bold(),
+            " confirm, any other key cancels".into(),
+        ]),
         Focus::Channels => Line::from(vec![
             " Quit ".into(),
             "<q> ".blue().bold(),
+            " | Help ".into(),
+            "<?> ".blue().bold(),
             " | Navigate ".into(),
             "<←↑↓→/hjkl> ".blue().bold(),
             " | Toggle Logs ".into(),
             "<o> ".blue().bold(),
             " | Pause ".into(),
             "<p> ".blue().bold(),
+            if comparing {
+                " | Unpin ".into()
+            } else {
+                " | Pin & Compare ".into()
+            },
+            "<c> ".blue().bold(),
+            if frozen {
+                " | Unfreeze Row ".into()
+            } else {
+                " | Freeze Row ".into()
+            },
+            "<f> ".blue().bold(),
+            if showing_throughput {
+                " | Hide Throughput ".into()
+            } else {
+                " | Throughput ".into()
+            },
+            "<t> ".blue().bold(),
+            " | Reset ".into(),
+            "<r> ".blue().bold(),
+            " | Export ".into(),
+            "<e> ".blue().bold(),
         ]),
         Focus::Logs => Line::from(vec![
             " Quit ".into(),
             "<q> ".blue().bold(),
+            " | Help ".into(),
+            "<?> ".blue().bold(),
             " | Navigate ".into(),
             "<←↑↓→/hjkl> ".blue().bold(),
             " | Toggle Logs ".into(),
@@ -43,6 +81,8 @@ pub fn render_bottom_bar(
         Focus::Inspect => Line::from(vec![
             " Quit ".into(),
             "<q> ".blue().bold(),
+            " | Help ".into(),
+            "<?> ".blue().bold(),
             " | Navigate ".into(),
             "<←↑↓→/hjkl> ".blue().bold(),
             " | Toggle Logs ".into(),
@@ -54,6 +94,19 @@ pub fn render_bottom_bar(
         ]),
     };
 
+    let connection_status = match connection {
+        Connection::Connecting => Line::from(" ⋯ connecting ".yellow()),
+        Connection::Connected => Line::from(" ● connected ".green()),
+        Connection::Disconnected => Line::from(" ● disconnected, retrying… ".red().bold()),
+    }
+    .right_aligned();
+    let notice = Line::from(
+        notice
+            .map(|notice| format!(" {} ", notice))
+            .unwrap_or_default()
+            .green(),
+    );
+
     #[cfg(feature = "dev")]
     let block = {
         use ratatui::text::Line;
@@ -63,6 +116,8 @@ pub fn render_bottom_bar(
 
         Block::bordered()
             .title(" Controls ")
+            .title(notice)
+            .title(connection_status)
             .title_bottom(Line::from(render_time_text).right_aligned())
             .border_set(border::PLAIN)
     };
@@ -70,6 +125,8 @@ pub fn render_bottom_bar(
     #[cfg(not(feature = "dev"))]
     let block = Block::bordered()
         .title(" Controls ")
+        .title(notice)
+        .title(connection_status)
         .border_set(border::PLAIN);
 
     let paragraph = Paragraph::new(controls_line).block(block).left_aligned();
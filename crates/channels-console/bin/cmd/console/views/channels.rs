@@ -1,5 +1,7 @@
+use std::collections::HashSet;
+
 use crate::cmd::console::app::Focus;
-use crate::cmd::console::widgets::formatters::{queue_status, truncate_left};
+use crate::cmd::console::widgets::formatters::{queue_status, queue_text, truncate_left};
 use channels_console::{format_bytes, ChannelState, ChannelType, SerializableChannelStats};
 use ratatui::{
     layout::{Constraint, Rect},
@@ -10,6 +12,10 @@ use ratatui::{
     Frame,
 };
 
+const COLUMNS: [&str; 8] = [
+    "Channel", "Type", "State", "Sent", "Received", "Queue", "Mem", "Drain",
+];
+
 /// Renders the channels table with channel statistics
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn render_channels_panel(
@@ -21,6 +27,9 @@ pub(crate) fn render_channels_panel(
     focus: Focus,
     channel_position: usize,
     total_channels: usize,
+    pinned_id: Option<u64>,
+    lagging: &HashSet<u64>,
+    frozen: &HashSet<u64>,
 ) {
     let available_width = area.width.saturating_sub(10);
     let channel_width = ((available_width as f32 * 0.22) as usize).max(36);
@@ -29,48 +38,55 @@ pub(crate) fn render_channels_panel(
         .fg(Color::Yellow)
         .add_modifier(Modifier::BOLD);
 
-    let header = Row::new(vec![
-        Cell::from("Channel"),
-        Cell::from("Type"),
-        Cell::from("State"),
-        Cell::from("Sent"),
-        Cell::from("Received"),
-        Cell::from("Queue"),
-        Cell::from("Mem"),
-    ])
-    .style(header_style)
-    .height(1);
+    let header = Row::new(COLUMNS.map(Cell::from))
+        .style(header_style)
+        .height(1);
 
     let rows: Vec<Row> = stats
         .iter()
         .map(|stat| {
-            let (state_text, state_style) = match stat.state {
-                ChannelState::Active => (stat.state.to_string(), Style::default().fg(Color::Green)),
-                ChannelState::Closed => {
-                    (stat.state.to_string(), Style::default().fg(Color::Yellow))
-                }
-                ChannelState::Full => {
-                    (format!("⚠ {}", stat.state), Style::default().fg(Color::Red))
-                }
-                ChannelState::Notified => {
-                    (stat.state.to_string(), Style::default().fg(Color::Blue))
-                }
+            let state_style = match stat.state {
+                ChannelState::Active | ChannelState::Received => Style::default().fg(Color::Green),
+                ChannelState::Closed
+                | ChannelState::SenderDropped
+                | ChannelState::ReceiverDropped => Style::default().fg(Color::Yellow),
+                ChannelState::Full => Style::default().fg(Color::Red),
+                ChannelState::Created | ChannelState::Sent => Style::default().fg(Color::Blue),
             };
 
-            let mem_cell = match &stat.channel_type {
-                ChannelType::Unbounded => Cell::from("N/A"),
-                _ => Cell::from(format_bytes(stat.queued_bytes)),
-            };
+            let mem_cell = Cell::from(mem_text(stat));
             let queue_cell = queue_status(stat.queued, &stat.channel_type, 8);
+            let drain_cell = if lagging.contains(&stat.id) {
+                Cell::from(drain_text(stat)).style(Style::default().fg(Color::Red))
+            } else {
+                Cell::from(drain_text(stat))
+            };
+
+            let label_cell = if pinned_id == Some(stat.id) {
+                Cell::from(format!(
+                    "* {}",
+                    truncate_left(&stat.label, channel_width.saturating_sub(2))
+                ))
+                .style(Style::default().fg(Color::Cyan))
+            } else if frozen.contains(&stat.id) {
+                Cell::from(format!(
+                    "‖ {}",
+                    truncate_left(&stat.label, channel_width.saturating_sub(2))
+                ))
+                .style(Style::default().fg(Color::Magenta))
+            } else {
+                Cell::from(truncate_left(&stat.label, channel_width))
+            };
 
             let row = Row::new(vec![
-                Cell::from(truncate_left(&stat.label, channel_width)),
-                Cell::from(stat.channel_type.to_string()),
-                Cell::from(state_text).style(state_style),
+                label_cell,
+                Cell::from(stat.type_label()),
+                Cell::from(state_text(stat)).style(state_style),
                 Cell::from(stat.sent_count.to_string()),
                 Cell::from(stat.received_count.to_string()),
                 queue_cell,
                 mem_cell,
+                drain_cell,
             ]);
 
             // Dim the row if logs are shown and channels table is not focused
@@ -83,13 +99,14 @@ pub(crate) fn render_channels_panel(
         .collect();
 
     let widths = [
-        Constraint::Percentage(30), // Channel
-        Constraint::Percentage(14), // Type
+        Constraint::Percentage(26), // Channel
+        Constraint::Percentage(12), // Type
         Constraint::Percentage(10), // State
         Constraint::Percentage(9),  // Sent
         Constraint::Percentage(11), // Received
-        Constraint::Percentage(16), // Queue
+        Constraint::Percentage(14), // Queue
         Constraint::Percentage(10), // Mem
+        Constraint::Percentage(8),  // Drain
     ];
 
     let selected_row_style = Style::default()
@@ -126,3 +143,74 @@ pub(crate) fn render_channels_panel(
 
     frame.render_stateful_widget(table, area, table_state);
 }
+
+fn state_text(stat: &SerializableChannelStats) -> String {
+    match stat.state {
+        ChannelState::Full => format!("⚠ {}", stat.state),
+        state => state.to_string(),
+    }
+}
+
+fn mem_text(stat: &SerializableChannelStats) -> String {
+    match &stat.channel_type {
+        ChannelType::Unbounded => "N/A".to_string(),
+        _ => format_bytes(stat.queued_bytes),
+    }
+}
+
+fn drain_text(stat: &SerializableChannelStats) -> String {
+    stat.drain_ratio
+        .map(|r| format!("{:.2}", r))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Formats the channels table as plain text, with the rows in the order they are shown
+/// and labels not truncated
+pub(crate) fn channels_table_text(
+    stats: &[SerializableChannelStats],
+    pinned_id: Option<u64>,
+    frozen: &HashSet<u64>,
+) -> String {
+    let rows: Vec<[String; 8]> = stats
+        .iter()
+        .map(|stat| {
+            let label = if pinned_id == Some(stat.id) {
+                format!("* {}", stat.label)
+            } else if frozen.contains(&stat.id) {
+                format!("‖ {}", stat.label)
+            } else {
+                stat.label.clone()
+            };
+            [
+                label,
+                stat.type_label(),
+                state_text(stat),
+                stat.sent_count.to_string(),
+                stat.received_count.to_string(),
+                queue_text(stat.queued, &stat.channel_type),
+                mem_text(stat),
+                drain_text(stat),
+            ]
+        })
+        .collect();
+
+    let mut widths = COLUMNS.map(|column| column.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut text = String::new();
+    let header = COLUMNS.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        text.push_str(line.join("  ").trim_end());
+        text.push('\n');
+    }
+    text
+}
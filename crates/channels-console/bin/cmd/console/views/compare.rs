@@ -0,0 +1,133 @@
+use channels_console::{format_bytes, SerializableChannelStats};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    symbols::border,
+    widgets::{Block, Cell, Row, Table},
+    Frame,
+};
+
+use crate::cmd::console::widgets::formatters::truncate_left;
+
+/// Renders the pinned and the selected channel side by side, with the difference per metric
+pub(crate) fn render_compare_panel(
+    pinned: &SerializableChannelStats,
+    selected: &SerializableChannelStats,
+    area: Rect,
+    frame: &mut Frame,
+) {
+    let label_width = (area.width.saturating_sub(4) as usize * 30 / 100).max(8);
+
+    let header_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from(truncate_left(&pinned.label, label_width)),
+        Cell::from(truncate_left(&selected.label, label_width)),
+        Cell::from("Delta"),
+    ])
+    .style(header_style)
+    .height(1);
+
+    let text_row = |name: &'static str, pinned: String, selected: String| {
+        let style = if pinned != selected {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(name),
+            Cell::from(pinned),
+            Cell::from(selected),
+            Cell::from(""),
+        ])
+        .style(style)
+    };
+
+    let count_row = |name: &'static str, pinned: u64, selected: u64, format: fn(u64) -> String| {
+        let delta = selected as i128 - pinned as i128;
+        let delta_text = match delta {
+            0 => "0".to_string(),
+            d if d > 0 => format!("+{}", format(d as u64)),
+            d => format!("-{}", format(d.unsigned_abs() as u64)),
+        };
+        let delta_style = match delta {
+            0 => Style::default().fg(Color::DarkGray),
+            d if d > 0 => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            _ => Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        };
+        Row::new(vec![
+            Cell::from(name),
+            Cell::from(format(pinned)),
+            Cell::from(format(selected)),
+            Cell::from(delta_text).style(delta_style),
+        ])
+    };
+
+    let rows = vec![
+        text_row("Type", pinned.type_label(), selected.type_label()),
+        text_row(
+            "State",
+            pinned.state.to_string(),
+            selected.state.to_string(),
+        ),
+        count_row("Sent", pinned.sent_count, selected.sent_count, |n| {
+            n.to_string()
+        }),
+        count_row(
+            "Received",
+            pinned.received_count,
+            selected.received_count,
+            |n| n.to_string(),
+        ),
+        count_row("Queued", pinned.queued, selected.queued, |n| n.to_string()),
+        count_row("Queued p95", pinned.queued_p95, selected.queued_p95, |n| {
+            n.to_string()
+        }),
+        text_row(
+            "Avg queued",
+            format!("{:.1}", pinned.avg_queued),
+            format!("{:.1}", selected.avg_queued),
+        ),
+        count_row(
+            "Mem",
+            pinned.queued_bytes,
+            selected.queued_bytes,
+            format_bytes,
+        ),
+        count_row(
+            "Received mem",
+            pinned.received_bytes,
+            selected.received_bytes,
+            format_bytes,
+        ),
+        count_row(
+            "Full events",
+            pinned.full_events,
+            selected.full_events,
+            |n| n.to_string(),
+        ),
+    ];
+
+    let widths = [
+        Constraint::Percentage(22),
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
+        Constraint::Percentage(18),
+    ];
+
+    let block = Block::bordered()
+        .title(" Compare (pinned vs selected) ")
+        .border_set(border::THICK);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
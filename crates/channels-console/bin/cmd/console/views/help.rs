@@ -0,0 +1,89 @@
+use ratatui::{
+    layout::Rect,
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+/// Keys and what they do, grouped by the view they apply to
+const SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Anywhere",
+        &[
+            ("q", "Quit"),
+            ("?", "Show or hide this help"),
+            ("p", "Pause or resume refreshing"),
+            ("t", "Show or hide the throughput chart"),
+            ("r", "Reset the statistics of all channels, confirm with y"),
+            ("e", "Save the channels table as shown to a text file"),
+            ("o", "Show or hide the logs of the selected channel"),
+        ],
+    ),
+    (
+        "Channels",
+        &[
+            ("↑↓ / jk", "Select a channel"),
+            ("→ / l", "Move to the logs"),
+            ("c", "Pin the selected channel to compare it with others"),
+            ("f", "Freeze the selected row, keeping its current numbers"),
+        ],
+    ),
+    (
+        "Logs",
+        &[
+            ("↑↓ / jk", "Select a log entry"),
+            ("← / h", "Back to the channels"),
+            ("i", "Inspect the full message of the selected entry"),
+        ],
+    ),
+    (
+        "Inspect",
+        &[
+            ("↑↓ / jk", "Inspect the previous or next entry"),
+            ("i / o / h", "Close the message"),
+        ],
+    ),
+];
+
+/// Width of the key column
+const KEY_WIDTH: usize = 11;
+
+/// Renders a centered popup listing all keybindings
+pub(crate) fn render_help_popup(area: Rect, frame: &mut Frame) {
+    let mut lines = Vec::new();
+    for (view, keys) in SECTIONS {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(format!(" {}", view).yellow().bold()));
+        for (key, action) in *keys {
+            lines.push(Line::from(vec![
+                format!("   {:<width$}", key, width = KEY_WIDTH)
+                    .blue()
+                    .bold(),
+                (*action).into(),
+            ]));
+        }
+    }
+
+    let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let popup_width = (content_width + 4).min(area.width);
+    let popup_height = (lines.len() as u16 + 2).min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::bordered()
+        .title(" Help ")
+        .title_bottom(Line::from(" <?/Esc> close ").right_aligned())
+        .border_set(border::DOUBLE);
+
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
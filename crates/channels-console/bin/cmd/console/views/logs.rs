@@ -4,7 +4,7 @@ use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     symbols::border,
-    text::Text,
+    text::{Line, Text},
     widgets::{Block, HighlightSpacing, Row, Table, TableState},
     Frame,
 };
@@ -34,10 +34,13 @@ pub(crate) fn render_logs_placeholder(
     }
 }
 
-/// Renders the logs panel with sent and received log entries
+/// Renders the logs panel with sent and received log entries, and the latest message at the
+/// bottom for channels instrumented with `preview = true`
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn render_logs_panel(
     cached_logs: &CachedLogs,
     channel_label: &str,
+    preview: Option<&str>,
     area: Rect,
     frame: &mut Frame,
     table_state: &mut TableState,
@@ -50,7 +53,7 @@ pub(crate) fn render_logs_panel(
         border::PLAIN
     };
 
-    let block = Block::bordered()
+    let mut block = Block::bordered()
         .title(format!(" {} ", channel_label))
         .border_set(border_set)
         .style(if is_focused {
@@ -58,6 +61,11 @@ pub(crate) fn render_logs_panel(
         } else {
             Style::default().fg(Color::DarkGray)
         });
+    if let Some(preview) = preview {
+        let max_chars = area.width.saturating_sub(14) as usize;
+        let preview: String = preview.chars().take(max_chars).collect();
+        block = block.title_bottom(Line::from(format!(" Latest: {} ", preview)));
+    }
 
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use channels_console::SerializableChannelStats;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
@@ -11,6 +13,7 @@ use ratatui::{
 use crate::cmd::console::app::{CachedLogs, Focus};
 
 use super::channels::render_channels_panel;
+use super::compare::render_compare_panel;
 use super::inspect::render_inspect_popup;
 use super::logs::{render_logs_panel, render_logs_placeholder};
 
@@ -30,6 +33,9 @@ pub fn render_main_view(
     paused: bool,
     inspected_log: &Option<channels_console::LogEntry>,
     current_elapsed_ns: u64,
+    pinned: Option<&SerializableChannelStats>,
+    lagging: &HashSet<u64>,
+    frozen: &HashSet<u64>,
 ) {
     if let Some(ref error_msg) = error {
         if stats.is_empty() {
@@ -68,8 +74,8 @@ pub fn render_main_view(
         return;
     }
 
-    // Split the area if logs are being shown
-    let (table_area, logs_area) = if show_logs {
+    // Split the area if logs or the compare panel are being shown
+    let (table_area, logs_area) = if show_logs || pinned.is_some() {
         let chunks = Layout::default()
             .direction(ratatui::layout::Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -92,8 +98,18 @@ pub fn render_main_view(
         focus,
         channel_position,
         total_channels,
+        pinned.map(|stat| stat.id),
+        lagging,
+        frozen,
     );
 
+    if let (Some(pinned), Some(side_area)) = (pinned, logs_area) {
+        if let Some(selected) = table_state.selected().and_then(|i| stats.get(i)) {
+            render_compare_panel(pinned, selected, side_area, frame);
+        }
+        return;
+    }
+
     // Render logs panel if visible
     if let Some(logs_area) = logs_area {
         let channel_label = table_state
@@ -119,9 +135,14 @@ pub fn render_main_view(
             } else {
                 channel_label
             };
+            let preview = table_state
+                .selected()
+                .and_then(|i| stats.get(i))
+                .and_then(|stat| stat.preview.as_deref());
             render_logs_panel(
                 cached_logs,
                 &display_label,
+                preview,
                 logs_area,
                 frame,
                 logs_table_state,
@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use channels_console::SerializableChannelStats;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    symbols::{self, border},
+    text::Line,
+    widgets::{Axis, Block, Chart, Dataset, GraphType},
+    Frame,
+};
+
+/// Number of one-second samples kept by default, one minute of history
+pub(crate) const DEFAULT_WINDOW: u64 = 60;
+
+/// Most samples `--window` accepts, an hour of history, so the chart stays small
+pub(crate) const MAX_WINDOW: u64 = 3600;
+
+const SAMPLE_INTERVAL_NS: u64 = 1_000_000_000;
+
+/// Aggregate sent/received per second across all channels, built from successive scrapes
+pub(crate) struct ThroughputHistory {
+    /// `(sent, received)` messages per second, oldest first
+    samples: VecDeque<(f64, f64)>,
+    /// Number of samples kept
+    window: usize,
+    /// Elapsed time and total counts at the last sample
+    last: Option<(u64, u64, u64)>,
+    /// Reset generation of the last scrape
+    generation: u64,
+}
+
+impl ThroughputHistory {
+    pub(crate) fn new(window: u64) -> Self {
+        let window = window.clamp(2, MAX_WINDOW) as usize;
+        Self {
+            samples: VecDeque::with_capacity(window),
+            window,
+            last: None,
+            generation: 0,
+        }
+    }
+
+    /// Record the totals of a scrape, adding a sample once a second has passed since the last one
+    pub(crate) fn record(
+        &mut self,
+        current_elapsed_ns: u64,
+        generation: u64,
+        stats: &[SerializableChannelStats],
+    ) {
+        let sent: u64 = stats.iter().map(|stat| stat.sent_count).sum();
+        let received: u64 = stats.iter().map(|stat| stat.received_count).sum();
+
+        let reset = generation != self.generation;
+        self.generation = generation;
+
+        let Some((last_ns, last_sent, last_received)) = self.last else {
+            self.last = Some((current_elapsed_ns, sent, received));
+            return;
+        };
+
+        // The monitored process restarted, its counters start over
+        if current_elapsed_ns < last_ns
+            || (!reset && (sent < last_sent || received < last_received))
+        {
+            self.samples.clear();
+            self.last = Some((current_elapsed_ns, sent, received));
+            return;
+        }
+
+        // Counters were reset since the last scrape, measure from the new values
+        if reset {
+            self.last = Some((current_elapsed_ns, sent, received));
+            return;
+        }
+
+        let elapsed_ns = current_elapsed_ns - last_ns;
+        if elapsed_ns < SAMPLE_INTERVAL_NS {
+            return;
+        }
+
+        let seconds = elapsed_ns as f64 / 1e9;
+        self.samples.push_back((
+            (sent - last_sent) as f64 / seconds,
+            (received - last_received) as f64 / seconds,
+        ));
+        if self.samples.len() > self.window {
+            self.samples.pop_front();
+        }
+        self.last = Some((current_elapsed_ns, sent, received));
+    }
+}
+
+/// Renders a full-width line chart of the aggregate throughput over the sample window
+pub(crate) fn render_throughput_chart(history: &ThroughputHistory, area: Rect, frame: &mut Frame) {
+    // The newest sample sits at x = 0, older ones to the left
+    let newest = history.samples.len() as f64 - 1.0;
+    let sent: Vec<(f64, f64)> = history
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(i, (sent, _))| (i as f64 - newest, *sent))
+        .collect();
+    let received: Vec<(f64, f64)> = history
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(i, (_, received))| (i as f64 - newest, *received))
+        .collect();
+
+    let max_rate = history
+        .samples
+        .iter()
+        .map(|(sent, received)| sent.max(*received))
+        .fold(0.0, f64::max);
+    let y_max = (max_rate * 1.1).max(1.0);
+
+    let (current_sent, current_received) = history.samples.back().copied().unwrap_or_default();
+    let datasets = vec![
+        Dataset::default()
+            .name(format!("sent {:.0}/s", current_sent))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&sent),
+        Dataset::default()
+            .name(format!("received {:.0}/s", current_received))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&received),
+    ];
+
+    let window = history.window as u64;
+    let axis_style = Style::default().fg(Color::DarkGray);
+    let x_axis = Axis::default()
+        .style(axis_style)
+        .bounds([-(history.window as f64 - 1.0), 0.0])
+        .labels([
+            format!("-{}", format_window(window)),
+            format!("-{}", format_window(window / 2)),
+            "now".to_string(),
+        ]);
+    let y_axis = Axis::default()
+        .style(axis_style)
+        .bounds([0.0, y_max])
+        .labels([
+            "0".to_string(),
+            format!("{:.0}", y_max / 2.0),
+            format!("{:.0}", y_max),
+        ]);
+
+    let block = Block::bordered()
+        .title(format!(
+            " Throughput (msg/s, all channels, last {}) ",
+            format_window(window)
+        ))
+        .title_bottom(Line::from(" <t> hide ").right_aligned())
+        .border_set(border::THICK);
+
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(x_axis)
+        .y_axis(y_axis);
+
+    frame.render_widget(chart, area);
+}
+
+/// Formats a number of seconds for the chart, e.g. `90s`, `5m` or `1h`
+fn format_window(seconds: u64) -> String {
+    if seconds >= 3600 && seconds.is_multiple_of(3600) {
+        format!("{}h", seconds / 3600)
+    } else if seconds >= 120 && seconds.is_multiple_of(60) {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
@@ -1,6 +1,6 @@
 use ratatui::{
     layout::Rect,
-    style::Stylize,
+    style::{Color, Style, Stylize},
     symbols::border,
     text::Line,
     widgets::{Block, Paragraph},
@@ -8,38 +8,36 @@ use ratatui::{
 };
 use std::time::Instant;
 
+use crate::cmd::console::app::Connection;
+
 /// Renders the top status bar showing connection status and refresh timer
 pub fn render_top_bar(
     frame: &mut Frame,
     area: Rect,
     is_paused: bool,
     last_successful_fetch: Option<Instant>,
-    has_error: bool,
-    has_data: bool,
+    connection: Connection,
 ) {
+    let seconds = last_successful_fetch
+        .map(|last_fetch| Instant::now().duration_since(last_fetch).as_secs())
+        .unwrap_or(0);
+
     let status_text = if is_paused {
         Line::from(vec!["⏸ ".yellow(), "PAUSED".yellow().bold()])
-    } else if let Some(last_fetch) = last_successful_fetch {
-        let elapsed = Instant::now().duration_since(last_fetch);
-        let seconds = elapsed.as_secs();
-
-        let is_stale = has_error && has_data;
-
-        if is_stale {
-            Line::from(vec![
-                "⚠ ".yellow(),
-                "Stale ".into(),
+    } else {
+        match connection {
+            Connection::Disconnected => Line::from(vec![
+                "✗ ".red(),
+                "Disconnected, retrying… ".red().bold(),
                 format!("(refreshed {}s ago)", seconds).yellow(),
-            ])
-        } else {
-            Line::from(vec![
+            ]),
+            Connection::Connected => Line::from(vec![
                 "✓ ".green(),
                 "Live ".green().bold(),
                 format!("(refreshed {}s ago)", seconds).into(),
-            ])
+            ]),
+            Connection::Connecting => Line::from(vec!["⋯ ".into(), "Connecting...".into()]),
         }
-    } else {
-        Line::from(vec!["⋯ ".into(), "Connecting...".into()])
     };
 
     let block = Block::bordered()
@@ -50,3 +48,26 @@ pub fn render_top_bar(
 
     frame.render_widget(paragraph, area);
 }
+
+/// Renders a one-line banner warning that the shown statistics are no longer updated
+pub fn render_disconnected_banner(
+    frame: &mut Frame,
+    area: Rect,
+    metrics_port: u16,
+    last_successful_fetch: Option<Instant>,
+) {
+    let seconds = last_successful_fetch
+        .map(|last_fetch| Instant::now().duration_since(last_fetch).as_secs())
+        .unwrap_or(0);
+
+    let banner = Line::from(format!(
+        " ⚠ Metrics server at http://127.0.0.1:{} is unreachable, showing data from {}s ago. Retrying… ",
+        metrics_port, seconds
+    ))
+    .bold()
+    .centered();
+
+    let paragraph = Paragraph::new(banner).style(Style::default().fg(Color::White).bg(Color::Red));
+
+    frame.render_widget(paragraph, area);
+}
@@ -14,22 +14,32 @@ pub(crate) fn truncate_left(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Queue depth against capacity, e.g. `[3/10]`, `N/A` for unbounded channels
+pub(crate) fn queue_text(queued: u64, channel_type: &ChannelType) -> String {
+    match queue_capacity(channel_type) {
+        Some(cap) if cap > 0 => format!("[{}/{}]", queued, cap),
+        _ => "N/A".to_string(),
+    }
+}
+
+fn queue_capacity(channel_type: &ChannelType) -> Option<usize> {
+    match channel_type {
+        ChannelType::Bounded(cap) => Some(*cap),
+        ChannelType::Oneshot => Some(1),
+        ChannelType::Unbounded => None,
+    }
+}
+
 pub(crate) fn queue_status(
     queued: u64,
     channel_type: &ChannelType,
     _width: usize,
 ) -> Cell<'static> {
-    let capacity = match channel_type {
-        ChannelType::Bounded(cap) => Some(*cap),
-        ChannelType::Oneshot => Some(1),
-        ChannelType::Unbounded => None,
-    };
-
-    match capacity {
+    match queue_capacity(channel_type) {
         Some(cap) if cap > 0 => {
             let percentage = (queued as f64 / cap as f64 * 100.0).min(100.0);
 
-            let text = format!("[{}/{}]", queued, cap);
+            let text = queue_text(queued, channel_type);
 
             let color = if percentage >= 100.0 {
                 Color::Red
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use channels_console::{format_bytes, CombinedJson, SerializableChannelStats};
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use prettytable::{Cell, Row, Table};
+
+#[derive(Debug, Parser)]
+pub struct DiffArgs {
+    /// Baseline snapshot written by `export`
+    pub before: PathBuf,
+
+    /// Snapshot compared against the baseline
+    pub after: PathBuf,
+}
+
+impl DiffArgs {
+    pub fn run(&self) -> Result<()> {
+        let before = load_snapshot(&self.before)?;
+        let after = load_snapshot(&self.after)?;
+        print!("{}", render_diff(&before.channels, &after.channels));
+        Ok(())
+    }
+}
+
+fn load_snapshot(path: &Path) -> Result<CombinedJson> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .wrap_err_with(|| format!("Failed to parse {} as a JSON snapshot", path.display()))
+}
+
+/// Channel ids differ between runs, so channels are matched by their label
+fn by_label(channels: &[SerializableChannelStats]) -> BTreeMap<&str, &SerializableChannelStats> {
+    channels
+        .iter()
+        .map(|channel| (channel.label.as_str(), channel))
+        .collect()
+}
+
+fn render_diff(before: &[SerializableChannelStats], after: &[SerializableChannelStats]) -> String {
+    let before = by_label(before);
+    let after = by_label(after);
+
+    let mut labels: Vec<&str> = before.keys().chain(after.keys()).copied().collect();
+    labels.sort_unstable();
+    labels.dedup();
+
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Channel"),
+        Cell::new("Status"),
+        Cell::new("Sent"),
+        Cell::new("Received"),
+        Cell::new("Queued"),
+        Cell::new("Mem"),
+    ]));
+
+    let (mut added, mut removed) = (0, 0);
+    for label in labels {
+        let before = before.get(label).copied();
+        let after = after.get(label).copied();
+        let status = match (before, after) {
+            (None, Some(_)) => {
+                added += 1;
+                "added"
+            }
+            (Some(_), None) => {
+                removed += 1;
+                "removed"
+            }
+            _ => "",
+        };
+
+        // A channel missing from one side counts as zero there
+        let value = |channel: Option<&SerializableChannelStats>,
+                     field: fn(&SerializableChannelStats) -> u64| {
+            channel.map(field).unwrap_or(0)
+        };
+        let count_cell = |field: fn(&SerializableChannelStats) -> u64| {
+            Cell::new(&format_change(
+                value(before, field),
+                value(after, field),
+                |n| n.to_string(),
+            ))
+        };
+
+        table.add_row(Row::new(vec![
+            Cell::new(label),
+            Cell::new(status),
+            count_cell(|c| c.sent_count),
+            count_cell(|c| c.received_count),
+            count_cell(|c| c.queued),
+            Cell::new(&format_change(
+                value(before, |c| c.queued_bytes),
+                value(after, |c| c.queued_bytes),
+                format_bytes,
+            )),
+        ]));
+    }
+
+    format!(
+        "{}\nChannels: {} added, {} removed\n",
+        table, added, removed
+    )
+}
+
+/// `after` followed by the signed difference to `before`, e.g. `120 (+20)`
+fn format_change(before: u64, after: u64, format: fn(u64) -> String) -> String {
+    let delta = match after.cmp(&before) {
+        std::cmp::Ordering::Equal => return format(after),
+        std::cmp::Ordering::Greater => format!("+{}", format(after - before)),
+        std::cmp::Ordering::Less => format!("-{}", format(before - after)),
+    };
+    format!("{} ({})", format(after), delta)
+}
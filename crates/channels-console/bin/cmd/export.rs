@@ -0,0 +1,115 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use channels_console::{CombinedJson, SerializableChannelStats};
+use clap::{Parser, ValueEnum};
+use eyre::{Result, WrapErr};
+
+use super::console::http::{fetch_channels, fetch_streams, metrics_url};
+
+/// Format of the exported snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Channels and streams as returned by the HTTP API
+    Json,
+    /// One row per channel
+    Csv,
+    /// The tables printed by `ChannelsGuard`
+    Table,
+}
+
+#[derive(Debug, Parser)]
+pub struct ExportArgs {
+    /// File to write the snapshot to
+    pub path: PathBuf,
+
+    /// Format of the written snapshot
+    #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+    pub format: ExportFormat,
+
+    /// Port for the metrics server
+    #[arg(long, default_value = "6770")]
+    pub metrics_port: u16,
+
+    /// Path prefix the metrics server serves its endpoints under
+    #[arg(long, default_value = "")]
+    pub metrics_path: String,
+}
+
+impl ExportArgs {
+    pub fn run(&self) -> Result<()> {
+        let config = ureq::Agent::config_builder()
+            .timeout_connect(Some(Duration::from_millis(2000)))
+            .timeout_recv_body(Some(Duration::from_millis(1500)))
+            .build();
+        let agent: ureq::Agent = config.into();
+
+        let base_url = metrics_url(self.metrics_port, &self.metrics_path);
+        let channels =
+            fetch_channels(&agent, &base_url).wrap_err("Failed to fetch channel metrics")?;
+        let streams =
+            fetch_streams(&agent, &base_url).wrap_err("Failed to fetch stream metrics")?;
+        let snapshot = CombinedJson {
+            current_elapsed_ns: channels.current_elapsed_ns,
+            generation: channels.generation,
+            runtime_seconds: channels.runtime_seconds,
+            channels: channels.channels,
+            streams: streams.streams,
+        };
+
+        let contents = match self.format {
+            ExportFormat::Json => serde_json::to_string_pretty(&snapshot)? + "\n",
+            ExportFormat::Csv => to_csv(&snapshot.channels),
+            ExportFormat::Table => snapshot.to_table(),
+        };
+
+        std::fs::write(&self.path, contents)
+            .wrap_err_with(|| format!("Failed to write {}", self.path.display()))?;
+        println!(
+            "Exported {} channels and {} streams to {}",
+            snapshot.channels.len(),
+            snapshot.streams.len(),
+            self.path.display()
+        );
+        Ok(())
+    }
+}
+
+const CSV_HEADER: &str = "id,label,source,type,state,sent,received,queued,queued_bytes,utilization,full_events,type_name";
+
+fn to_csv(channels: &[SerializableChannelStats]) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(csv, "{CSV_HEADER}");
+    for channel in channels {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            channel.id,
+            csv_field(&channel.label),
+            csv_field(&channel.source),
+            channel.channel_type,
+            channel.state,
+            channel.sent_count,
+            channel.received_count,
+            channel.queued,
+            channel.queued_bytes,
+            channel
+                .utilization
+                .map(|u| format!("{:.1}", u))
+                .unwrap_or_default(),
+            channel.full_events,
+            csv_field(&channel.type_name),
+        );
+    }
+    csv
+}
+
+/// Quote a field containing separators, quotes or line breaks.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
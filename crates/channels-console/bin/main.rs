@@ -1,12 +1,18 @@
 mod cmd;
 use clap::{Parser, Subcommand};
 use cmd::console::ConsoleArgs;
+use cmd::diff::DiffArgs;
+use cmd::export::ExportArgs;
 use eyre::Result;
 
 #[derive(Subcommand, Debug)]
 pub enum TCSubcommand {
     #[command(about = "Start the console TUI")]
     Console(ConsoleArgs),
+    #[command(about = "Write a snapshot of the current statistics to a file")]
+    Export(ExportArgs),
+    #[command(about = "Compare two snapshots written by export")]
+    Diff(DiffArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -22,6 +28,14 @@ pub struct TCArgs {
     /// Port for the metrics server (used when no subcommand is provided)
     #[arg(long, default_value = "6770", global = true)]
     pub metrics_port: u16,
+
+    /// Path prefix of the metrics server endpoints (used when no subcommand is provided)
+    #[arg(long, default_value = "", global = true)]
+    pub metrics_path: String,
+
+    /// Seconds of history kept by the throughput chart (used when no subcommand is provided)
+    #[arg(long, default_value_t = cmd::console::DEFAULT_WINDOW, value_parser = clap::value_parser!(u64).range(2..=cmd::console::MAX_WINDOW))]
+    pub window: u64,
 }
 
 fn main() -> Result<()> {
@@ -31,9 +45,17 @@ fn main() -> Result<()> {
         Some(TCSubcommand::Console(args)) => {
             args.run()?;
         }
+        Some(TCSubcommand::Export(args)) => {
+            args.run()?;
+        }
+        Some(TCSubcommand::Diff(args)) => {
+            args.run()?;
+        }
         None => {
             let args = ConsoleArgs {
                 metrics_port: root_args.metrics_port,
+                metrics_path: root_args.metrics_path,
+                window: root_args.window,
             };
             args.run()?;
         }
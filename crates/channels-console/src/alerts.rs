@@ -0,0 +1,107 @@
+//! Threshold alerts evaluated by the background collector.
+//!
+//! Register alerts with [`ChannelsGuardBuilder::alert_on`](crate::ChannelsGuardBuilder::alert_on).
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::{ChannelStats, SerializableChannelStats};
+
+type Predicate = Box<dyn Fn(&SerializableChannelStats) -> bool + Send + Sync>;
+type Callback = Arc<dyn Fn(&SerializableChannelStats) + Send + Sync>;
+
+/// A user-defined condition and the callback to run when a channel meets it.
+pub(crate) struct Alert {
+    predicate: Predicate,
+    callback: Callback,
+    /// Channels currently matching the predicate, so the callback fires only on the
+    /// transition instead of on every collector tick.
+    triggered: HashSet<u64>,
+}
+
+impl Alert {
+    pub(crate) fn new(predicate: Predicate, callback: Callback) -> Self {
+        Self {
+            predicate,
+            callback,
+            triggered: HashSet::new(),
+        }
+    }
+}
+
+static ALERTS: Mutex<Vec<Alert>> = Mutex::new(Vec::new());
+
+pub(crate) fn register(alert: Alert) {
+    ALERTS.lock().unwrap().push(alert);
+}
+
+/// Evaluate all registered alerts against the current channel statistics.
+///
+/// An alert fires once when a channel starts matching its predicate and is re-armed
+/// once the channel stops matching or is removed. Callbacks run on the collector thread
+/// after both the statistics and the alerts locks have been released.
+pub(crate) fn check_alerts(stats: &RwLock<HashMap<u64, ChannelStats>>) {
+    let mut alerts = ALERTS.lock().unwrap();
+    if alerts.is_empty() {
+        return;
+    }
+
+    let snapshot: Vec<SerializableChannelStats> = stats
+        .read()
+        .unwrap()
+        .values()
+        .map(SerializableChannelStats::from)
+        .collect();
+    let ids: HashSet<u64> = snapshot.iter().map(|channel| channel.id).collect();
+
+    let mut fired = Vec::new();
+    for alert in alerts.iter_mut() {
+        // Forget channels that are no longer registered
+        alert.triggered.retain(|id| ids.contains(id));
+        for (idx, channel) in snapshot.iter().enumerate() {
+            if (alert.predicate)(channel) {
+                if alert.triggered.insert(channel.id) {
+                    fired.push((Arc::clone(&alert.callback), idx));
+                }
+            } else {
+                alert.triggered.remove(&channel.id);
+            }
+        }
+    }
+    drop(alerts);
+
+    for (callback, idx) in fired {
+        callback(&snapshot[idx]);
+    }
+}
+
+/// Build an alert callback that POSTs the offending channel's stats as JSON to `url`.
+///
+/// Requests are sent from a short-lived background thread so a slow endpoint never
+/// stalls statistics collection. Failures are reported on stderr.
+///
+/// # Examples
+///
+/// ```no_run
+/// use channels_console::{alerts, ChannelsGuardBuilder};
+///
+/// let _guard = ChannelsGuardBuilder::new()
+///     .alert_on(
+///         |stats| stats.queued > 1000,
+///         alerts::webhook("http://localhost:9000/alerts"),
+///     )
+///     .build();
+/// ```
+#[cfg(feature = "webhook")]
+pub fn webhook(url: impl Into<String>) -> impl Fn(&SerializableChannelStats) + Send + Sync {
+    let url = url.into();
+    move |stats: &SerializableChannelStats| {
+        let url = url.clone();
+        let stats = stats.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = ureq::post(&url).send_json(&stats) {
+                eprintln!("Failed to deliver channel alert to {}: {}", url, e);
+            }
+        });
+    }
+}
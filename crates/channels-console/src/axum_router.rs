@@ -0,0 +1,52 @@
+//! The HTTP API as an axum [`Router`], for programs that already run an axum server.
+
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::header;
+use axum::response::Response;
+use axum::Router;
+
+use crate::{http_api, SERVER_START_TIME, START_TIME};
+
+/// Router serving the same endpoints as the embedded metrics server, to be nested into an
+/// existing axum application.
+///
+/// The metrics path set with [`ChannelsGuardBuilder::metrics_path`](crate::ChannelsGuardBuilder::metrics_path)
+/// doesn't apply, the router is mounted wherever it is nested. Turn off the embedded
+/// server with [`ChannelsGuardBuilder::metrics_server`](crate::ChannelsGuardBuilder::metrics_server)
+/// to avoid a second listening socket.
+///
+/// # Examples
+///
+/// ```
+/// let app: axum::Router = axum::Router::new()
+///     .nest("/admin/channels", channels_console::channels_metrics_router());
+/// ```
+pub fn channels_metrics_router() -> Router {
+    START_TIME.get_or_init(Instant::now);
+    SERVER_START_TIME.get_or_init(Instant::now);
+    Router::new().fallback(handle)
+}
+
+async fn handle(request: Request) -> Response {
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().unwrap_or_default().to_string();
+    let method = request.method().as_str().to_string();
+
+    // Statistics are read under blocking locks, kept off the async workers
+    let response = tokio::task::spawn_blocking(move || http_api::route(&path, &query, &method))
+        .await
+        .expect("Metrics request handler panicked");
+
+    let mut builder = Response::builder()
+        .status(response.status)
+        .header(header::CONTENT_TYPE, response.content_type);
+    if let Some(allow) = response.allow {
+        builder = builder.header(header::ALLOW, allow);
+    }
+    builder
+        .body(Body::from(response.body))
+        .expect("Invalid metrics response")
+}
@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{
+    ChannelState, ChannelStats, ChannelType, DwellTimeStats, LogEntry, OpDurationStats,
+    RuntimeInfo, SendWaitStats,
+};
+
+/// Read-only statistics of one channel, returned by [`channel_views`](crate::channel_views).
+///
+/// Unlike [`SerializableChannelStats`](crate::SerializableChannelStats) it keeps the
+/// collected values as they are, e.g. timestamps as [`Instant`]s, any percentile of the
+/// sampled queue depth and the message logs, and computes derived values on access.
+#[derive(Debug, Clone)]
+pub struct ChannelView {
+    stats: ChannelStats,
+    label: String,
+}
+
+impl ChannelView {
+    pub(crate) fn new(stats: ChannelStats) -> Self {
+        let label = stats.display_label();
+        Self { stats, label }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.stats.id
+    }
+
+    /// Module path, file and line where the channel was instrumented.
+    pub fn source(&self) -> &'static str {
+        self.stats.source
+    }
+
+    /// Custom label, or one generated from the source.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn has_custom_label(&self) -> bool {
+        self.stats.label.is_some()
+    }
+
+    pub fn channel_type(&self) -> ChannelType {
+        self.stats.channel_type
+    }
+
+    pub fn state(&self) -> ChannelState {
+        self.stats.state
+    }
+
+    pub fn sent_count(&self) -> u64 {
+        self.stats.sent_count
+    }
+
+    pub fn received_count(&self) -> u64 {
+        self.stats.received_count
+    }
+
+    pub fn queued(&self) -> u64 {
+        self.stats.queued()
+    }
+
+    /// Total size of all sent messages, see [`register_sizer`](crate::register_sizer).
+    pub fn sent_bytes(&self) -> u64 {
+        self.stats.sent_bytes
+    }
+
+    pub fn received_bytes(&self) -> u64 {
+        self.stats.received_bytes
+    }
+
+    pub fn queued_bytes(&self) -> u64 {
+        self.stats.queued_bytes()
+    }
+
+    /// Most memory the queued messages ever used.
+    pub fn peak_queued_bytes(&self) -> u64 {
+        self.stats.peak_queued_bytes
+    }
+
+    /// Rust type of the messages.
+    pub fn type_name(&self) -> &'static str {
+        self.stats.type_name
+    }
+
+    pub fn type_size(&self) -> usize {
+        self.stats.type_size
+    }
+
+    /// Instance number of channels sharing a source.
+    pub fn iter(&self) -> u32 {
+        self.stats.iter
+    }
+
+    /// Number of times a bounded channel became full.
+    pub fn full_events(&self) -> u64 {
+        self.stats.full_events
+    }
+
+    /// Messages dropped because the receiver was gone.
+    pub fn send_errors(&self) -> u64 {
+        self.stats.send_errors
+    }
+
+    /// Slots held by outstanding tokio permits.
+    pub fn reserved(&self) -> u64 {
+        self.stats.reserved
+    }
+
+    /// Capacity left after queued messages and permits, `None` unless bounded.
+    pub fn available(&self) -> Option<u64> {
+        self.stats.available()
+    }
+
+    /// Percentage (0-100) of capacity in use, `None` unless bounded.
+    pub fn utilization(&self) -> Option<f64> {
+        self.stats.utilization()
+    }
+
+    /// Received messages per sent message, `None` before the first send.
+    pub fn drain_ratio(&self) -> Option<f64> {
+        self.stats.drain_ratio()
+    }
+
+    /// Queue depth at `quantile` (0.0 to 1.0) of the depths sampled on every send and
+    /// receive.
+    pub fn queued_percentile(&self, quantile: f64) -> u64 {
+        self.stats.queue_depth.percentile(quantile)
+    }
+
+    /// Queue depth averaged over time since creation or the last reset.
+    pub fn avg_queued(&self) -> f64 {
+        self.stats.queue_average.average(self.stats.queued())
+    }
+
+    /// Enqueue-to-dequeue latency, `None` unless the `dwell-time` feature is enabled.
+    pub fn dwell_time(&self) -> Option<DwellTimeStats> {
+        #[cfg(feature = "dwell-time")]
+        return self.stats.dwell.stats();
+        #[cfg(not(feature = "dwell-time"))]
+        None
+    }
+
+    /// Time sends waited for capacity, `None` before the first wait was recorded.
+    pub fn send_wait(&self) -> Option<SendWaitStats> {
+        self.stats.send_wait.stats()
+    }
+
+    /// Time spent sending to the original channel, with the `op-durations` feature.
+    pub fn send_duration(&self) -> Option<OpDurationStats> {
+        self.stats.send_duration.stats()
+    }
+
+    /// Time spent handing messages to the receiver, with the `op-durations` feature.
+    pub fn recv_duration(&self) -> Option<OpDurationStats> {
+        self.stats.recv_duration.stats()
+    }
+
+    /// One in this many messages is observed.
+    pub fn sample_rate(&self) -> u64 {
+        self.stats.sample_rate
+    }
+
+    /// Live receivers, `None` when unknown.
+    pub fn active_receivers(&self) -> Option<u64> {
+        self.stats.active_receivers()
+    }
+
+    /// Live senders including clones, tokio mpsc channels only.
+    pub fn active_senders(&self) -> Option<u64> {
+        self.stats.active_senders()
+    }
+
+    /// Senders that ever existed, a lower bound, tokio mpsc channels only.
+    pub fn total_senders_created(&self) -> Option<u64> {
+        self.stats.total_senders_created()
+    }
+
+    pub fn created_at(&self) -> Instant {
+        self.stats.created_at
+    }
+
+    /// Time of the first send since the last reset.
+    pub fn first_sent_at(&self) -> Option<Instant> {
+        self.stats.first_sent_at
+    }
+
+    /// Time of the most recent send or receive.
+    pub fn last_message_at(&self) -> Option<Instant> {
+        self.stats.last_message_at
+    }
+
+    /// Time since the last send or receive, or since creation if there was none.
+    pub fn idle_for(&self) -> Duration {
+        self.stats
+            .last_message_at
+            .unwrap_or(self.stats.created_at)
+            .elapsed()
+    }
+
+    /// Messages sent per second, decaying with a one-minute time constant.
+    pub fn sent_rate_1m(&self) -> f64 {
+        self.stats.rates.sent_per_sec()
+    }
+
+    /// Messages received per second, decaying with a one-minute time constant.
+    pub fn received_rate_1m(&self) -> f64 {
+        self.stats.rates.received_per_sec()
+    }
+
+    /// Messages sent per second over the lifetime of the channel.
+    pub fn sent_rate_avg(&self) -> f64 {
+        self.stats.lifetime_rate(self.stats.sent_count)
+    }
+
+    /// Messages received per second over the lifetime of the channel.
+    pub fn received_rate_avg(&self) -> f64 {
+        self.stats.lifetime_rate(self.stats.received_count)
+    }
+
+    /// Channels aggregated into this entry.
+    pub fn instance_count(&self) -> u64 {
+        self.stats.instance_count
+    }
+
+    /// Id of the tokio task that created the channel.
+    pub fn task_id(&self) -> Option<u64> {
+        self.stats.task_id
+    }
+
+    /// Tokio runtime that created the channel.
+    pub fn runtime(&self) -> Option<&RuntimeInfo> {
+        self.stats.runtime.as_ref()
+    }
+
+    /// `Debug` output of the latest message, with `preview = true`.
+    pub fn preview(&self) -> Option<&str> {
+        self.stats.preview.as_deref()
+    }
+
+    /// Whether a oneshot receiver was dropped before the value arrived.
+    pub fn cancelled(&self) -> bool {
+        self.stats.cancelled
+    }
+
+    /// Whether counting was turned off with [`disable_channel`](crate::disable_channel).
+    pub fn disabled(&self) -> bool {
+        self.stats.disabled
+    }
+
+    /// Recent sent log entries, oldest first.
+    pub fn sent_logs(&self) -> &VecDeque<LogEntry> {
+        &self.stats.sent_logs
+    }
+
+    /// Recent received log entries, oldest first.
+    pub fn received_logs(&self) -> &VecDeque<LogEntry> {
+        &self.stats.received_logs
+    }
+}
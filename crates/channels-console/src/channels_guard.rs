@@ -1,10 +1,17 @@
-use std::time::Instant;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use prettytable::{Cell, Row, Table};
 
+use crate::alerts::{self, Alert};
+use crate::leaks::suspected_leaks;
 use crate::{
-    format_bytes, get_combined_json, get_sorted_channel_stats, get_sorted_stream_stats,
-    resolve_label, Format,
+    flush_channel_stats, format_bytes, get_combined_json, get_sorted_channel_stats,
+    get_sorted_stream_stats, sort_channels, ChannelType, CombinedJson, DuplicateChannels, Format,
+    SerializableChannelStats, SerializableStreamStats, SortKey,
 };
 
 /// Builder for creating a ChannelsGuard with custom configuration.
@@ -21,18 +28,82 @@ use crate::{
 /// ```
 pub struct ChannelsGuardBuilder {
     format: Format,
+    ascii: bool,
+    thousands_separator: char,
+    max_label_width: Option<usize>,
+    alerts: Vec<Alert>,
+    sort_key: SortKey,
+    filters: Vec<ChannelFilter>,
+    deregister_closed: bool,
+    duplicate_channels: DuplicateChannels,
+    strict_labels: bool,
+    send_wait_buckets: Option<Vec<f64>>,
+    print_interval: Option<Duration>,
+    #[cfg(feature = "statsd")]
+    statsd_addr: Option<String>,
+    #[cfg(feature = "statsd")]
+    statsd_interval: Duration,
+    #[cfg(feature = "mmap")]
+    mmap_path: Option<PathBuf>,
+    #[cfg(unix)]
+    metrics_unix_socket: Option<PathBuf>,
+    metrics_path: Option<String>,
+    metrics_server: bool,
 }
 
+pub(crate) const DEFAULT_THOUSANDS_SEPARATOR: char = ',';
+pub(crate) const DEFAULT_MAX_LABEL_WIDTH: usize = 40;
+
+/// Narrowest label width, an ellipsis followed by at least one character.
+const MIN_LABEL_WIDTH: usize = 4;
+
+/// Format used unless one is set explicitly, `CHANNELS_CONSOLE_FORMAT` or the table.
+fn default_format() -> Format {
+    let Ok(value) = std::env::var("CHANNELS_CONSOLE_FORMAT") else {
+        return Format::default();
+    };
+    value.parse().unwrap_or_else(|e| {
+        eprintln!("Ignoring CHANNELS_CONSOLE_FORMAT, {}", e);
+        Format::default()
+    })
+}
+
+#[cfg(feature = "statsd")]
+const DEFAULT_STATSD_INTERVAL: Duration = Duration::from_secs(10);
+
 impl ChannelsGuardBuilder {
     /// Create a new channels guard builder.
     pub fn new() -> Self {
         Self {
-            format: Format::default(),
+            format: default_format(),
+            ascii: false,
+            thousands_separator: DEFAULT_THOUSANDS_SEPARATOR,
+            max_label_width: Some(DEFAULT_MAX_LABEL_WIDTH),
+            alerts: Vec::new(),
+            sort_key: SortKey::default(),
+            filters: Vec::new(),
+            deregister_closed: false,
+            duplicate_channels: DuplicateChannels::default(),
+            strict_labels: false,
+            send_wait_buckets: None,
+            print_interval: None,
+            #[cfg(feature = "statsd")]
+            statsd_addr: None,
+            #[cfg(feature = "statsd")]
+            statsd_interval: DEFAULT_STATSD_INTERVAL,
+            #[cfg(feature = "mmap")]
+            mmap_path: None,
+            #[cfg(unix)]
+            metrics_unix_socket: None,
+            metrics_path: None,
+            metrics_server: true,
         }
     }
 
     /// Set the output format for statistics.
     ///
+    /// Takes precedence over the `CHANNELS_CONSOLE_FORMAT` environment variable.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -47,12 +118,423 @@ impl ChannelsGuardBuilder {
         self
     }
 
+    /// Draw the queue bars in the statistics table with ASCII characters only.
+    ///
+    /// ASCII is also used automatically when `NO_COLOR` is set or the locale is not UTF-8.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Set the character grouping thousands in the table's counts, `,` by default.
+    ///
+    /// JSON and YAML output always contain raw numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// // Prints 3.000.000 instead of 3,000,000
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .thousands_separator('.')
+    ///     .build();
+    /// ```
+    pub fn thousands_separator(mut self, separator: char) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+
+    /// Shorten labels longer than `width` characters in the table, keeping their end
+    /// after an ellipsis. Labels are cut at 40 characters by default. Widths below 4 are
+    /// raised to 4, so at least one character is kept after the ellipsis.
+    ///
+    /// Only the table is affected, JSON and YAML output and the TUI keep full labels.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .max_label_width(24)
+    ///     .build();
+    /// ```
+    pub fn max_label_width(mut self, width: usize) -> Self {
+        self.max_label_width = Some(width.max(MIN_LABEL_WIDTH));
+        self
+    }
+
+    /// Print full labels in the table, however wide they make the channel column.
+    pub fn full_labels(mut self) -> Self {
+        self.max_label_width = None;
+        self
+    }
+
+    /// Invoke `callback` whenever a channel starts matching `predicate`.
+    ///
+    /// Alerts are checked by the collector thread every 100ms. The callback fires once
+    /// when a channel crosses the threshold and again only after the channel has dropped
+    /// back below it. Callbacks run on the collector thread, so keep them short.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .alert_on(
+    ///         |stats| stats.utilization.is_some_and(|u| u >= 90.0),
+    ///         |stats| eprintln!("channel {} is almost full", stats.label),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn alert_on<P, C>(mut self, predicate: P, callback: C) -> Self
+    where
+        P: Fn(&SerializableChannelStats) -> bool + Send + Sync + 'static,
+        C: Fn(&SerializableChannelStats) + Send + Sync + 'static,
+    {
+        self.alerts
+            .push(Alert::new(Box::new(predicate), Arc::new(callback)));
+        self
+    }
+
+    /// Remove channels from the statistics once both of their halves have been dropped.
+    ///
+    /// By default closed channels are kept, so the summary printed on drop covers every
+    /// channel the program created. Programs creating many short-lived channels can enable
+    /// this to keep totals and memory usage bounded, at the cost of losing the history of
+    /// finished channels. A channel is removed after all its messages were handed to the
+    /// receiver, messages still buffered on the receiving side are not counted as queued.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .deregister_closed(true)
+    ///     .build();
+    /// ```
+    pub fn deregister_closed(mut self, enabled: bool) -> Self {
+        self.deregister_closed = enabled;
+        self
+    }
+
+    /// Order the channels in the printed statistics by `key`.
+    ///
+    /// `SortKey::Label` (the default) lists custom labels alphabetically, then generated
+    /// ones by source location. Other keys put the largest values first, so for example
+    /// `SortKey::Queued` shows the most backed-up channels at the top.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::{ChannelsGuardBuilder, SortKey};
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .sort_by(SortKey::QueuedBytes)
+    ///     .build();
+    /// ```
+    pub fn sort_by(mut self, key: SortKey) -> Self {
+        self.sort_key = key;
+        self
+    }
+
+    /// Only print channels matching `predicate`.
+    ///
+    /// Filters apply to the printed statistics in every format, the HTTP API keeps serving
+    /// all channels. When called several times, channels have to match all predicates.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .only_if(|stats| stats.full_events > 0 || stats.send_errors > 0)
+    ///     .build();
+    /// ```
+    pub fn only_if<P>(mut self, predicate: P) -> Self
+    where
+        P: Fn(&SerializableChannelStats) -> bool + Send + Sync + 'static,
+    {
+        self.filters.push(Arc::new(predicate));
+        self
+    }
+
+    /// Leave out channels that never sent or received a message.
+    pub fn hide_empty(self) -> Self {
+        self.only_if(|stats| stats.sent_count > 0 || stats.received_count > 0)
+    }
+
+    /// Leave out channels with fewer than `queued` messages waiting.
+    pub fn min_queued(self, queued: u64) -> Self {
+        self.only_if(move |stats| stats.queued >= queued)
+    }
+
+    /// Choose how channels created at the same source location with the same label are
+    /// reported, e.g. a channel created in a loop or per connection.
+    ///
+    /// [`DuplicateChannels::Disambiguate`] (the default) keeps one entry per channel, which
+    /// keeps exact per-channel numbers but can flood the output with short-lived entries.
+    /// [`DuplicateChannels::Aggregate`] folds them into a single entry with summed counts
+    /// and an `instance_count`. Capacity and type are taken from the first instance, queued
+    /// counts are a sum over instances and logs are interleaved. The mode applies to
+    /// channels created after the guard is built.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::{ChannelsGuardBuilder, DuplicateChannels};
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .duplicate_channels(DuplicateChannels::Aggregate)
+    ///     .build();
+    /// ```
+    pub fn duplicate_channels(mut self, mode: DuplicateChannels) -> Self {
+        self.duplicate_channels = mode;
+        self
+    }
+
+    /// Check that custom labels are unique, catching two channels given the same
+    /// `label = "..."` by mistake.
+    ///
+    /// A channel registering a label already used by a channel at another source panics in
+    /// debug builds and logs a warning in release builds. Channels created at the same
+    /// source, e.g. in a loop, can share a label. The check applies to channels created
+    /// after the guard is built and to labels changed with [`set_label`](crate::set_label).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new().strict_labels(true).build();
+    /// ```
+    pub fn strict_labels(mut self, enabled: bool) -> Self {
+        self.strict_labels = enabled;
+        self
+    }
+
+    /// Set the upper bounds, in seconds, of the send wait histogram buckets.
+    ///
+    /// The default buckets cover 1µs to 10s in powers of ten. Buckets are shared by all
+    /// channels and fixed once the first wait is recorded, so set them before sending.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .send_wait_buckets([0.001, 0.01, 0.1, 1.0])
+    ///     .build();
+    /// ```
+    pub fn send_wait_buckets(mut self, buckets: impl IntoIterator<Item = f64>) -> Self {
+        self.send_wait_buckets = Some(buckets.into_iter().collect());
+        self
+    }
+
+    /// Also print the statistics every `interval`, not only when the guard is dropped.
+    ///
+    /// Meant for long-running services that never drop the guard. Each snapshot uses the
+    /// configured format, so with `Format::JsonLines` every interval appends one line per
+    /// channel and stream to the log. Snapshots are skipped while nothing is instrumented.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use channels_console::{ChannelsGuardBuilder, Format};
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .format(Format::Json)
+    ///     .print_interval(Duration::from_secs(60))
+    ///     .build();
+    /// ```
+    pub fn print_interval(mut self, interval: Duration) -> Self {
+        self.print_interval = Some(interval);
+        self
+    }
+
+    /// Push channel statistics to a StatsD agent listening on `addr` (e.g. `"127.0.0.1:8125"`).
+    ///
+    /// Every push sends a `channels_console.queued` gauge and `channels_console.sent` and
+    /// `channels_console.received` counters per channel, tagged with the channel id and
+    /// label in the DogStatsD format. Requires the `statsd` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .statsd_addr("127.0.0.1:8125")
+    ///     .build();
+    /// ```
+    #[cfg(feature = "statsd")]
+    pub fn statsd_addr(mut self, addr: impl Into<String>) -> Self {
+        self.statsd_addr = Some(addr.into());
+        self
+    }
+
+    /// Set how often statistics are pushed to the StatsD agent, 10 seconds by default.
+    #[cfg(feature = "statsd")]
+    pub fn statsd_interval(mut self, interval: Duration) -> Self {
+        self.statsd_interval = interval;
+        self
+    }
+
+    /// Mirror channel statistics into the file at `path` on every collector tick.
+    ///
+    /// Local monitoring tools can `mmap` the file and read the counters without going
+    /// through the HTTP server. The binary layout is versioned and described in the
+    /// README. Requires the `mmap` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .mmap_path("/tmp/channels-console.stats")
+    ///     .build();
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub fn mmap_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.mmap_path = Some(path.into());
+        self
+    }
+
+    /// Serve the HTTP API on a Unix domain socket at `path` instead of a TCP port.
+    ///
+    /// The server starts together with the first instrumented channel or stream, so the
+    /// guard has to be built before that. A socket file left over from a previous run is
+    /// replaced, and the file is removed on [`shutdown`](crate::shutdown).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .metrics_unix_socket("/run/my-app/channels.sock")
+    ///     .build();
+    /// ```
+    #[cfg(unix)]
+    pub fn metrics_unix_socket(mut self, path: impl Into<PathBuf>) -> Self {
+        self.metrics_unix_socket = Some(path.into());
+        self
+    }
+
+    /// Serve the HTTP endpoints under `path`, e.g. `/internal` for `/internal/channels`
+    /// and `/internal/health`, when the application already serves paths like `/channels`
+    /// or sits behind a reverse proxy.
+    ///
+    /// Takes precedence over the `CHANNELS_CONSOLE_METRICS_PATH` environment variable. Like
+    /// [`metrics_unix_socket`](Self::metrics_unix_socket), it has to be set before the first
+    /// channel or stream is instrumented.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new()
+    ///     .metrics_path("/internal")
+    ///     .build();
+    /// ```
+    pub fn metrics_path(mut self, path: impl Into<String>) -> Self {
+        self.metrics_path = Some(path.into());
+        self
+    }
+
+    /// Whether to start the embedded metrics server, `true` by default.
+    ///
+    /// Turn it off when the statistics are served from an existing axum server with
+    /// [`channels_metrics_router`](crate::channels_metrics_router) or only read in-process,
+    /// so no port is bound. It has to be set before the first channel or stream is
+    /// instrumented, the TUI console can't connect without the server.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use channels_console::ChannelsGuardBuilder;
+    ///
+    /// let _guard = ChannelsGuardBuilder::new().metrics_server(false).build();
+    /// ```
+    pub fn metrics_server(mut self, enabled: bool) -> Self {
+        self.metrics_server = enabled;
+        self
+    }
+
     /// Build and return the ChannelsGuard.
     /// Statistics will be printed when the guard is dropped.
     pub fn build(self) -> ChannelsGuard {
-        ChannelsGuard {
-            start_time: Instant::now(),
+        for alert in self.alerts {
+            alerts::register(alert);
+        }
+        if self.deregister_closed {
+            crate::set_deregister_closed(true);
+        }
+        crate::set_duplicate_channels(self.duplicate_channels);
+        if self.strict_labels {
+            crate::set_strict_labels(true);
+        }
+        if let Some(buckets) = self.send_wait_buckets {
+            if !crate::send_wait::set_buckets(buckets) {
+                eprintln!("Send wait buckets already in use, ignoring the new buckets");
+            }
+        }
+        #[cfg(unix)]
+        if let Some(path) = self.metrics_unix_socket {
+            if !crate::set_metrics_unix_socket(path) {
+                eprintln!("Metrics server already started, ignoring the Unix socket setting");
+            }
+        }
+        if let Some(path) = self.metrics_path {
+            if !crate::set_metrics_path(&path) {
+                eprintln!("Metrics server already started, ignoring the metrics path");
+            }
+        }
+        if !self.metrics_server && !crate::disable_metrics_server() {
+            eprintln!("Metrics server already started, it keeps running");
+        }
+        #[cfg(feature = "statsd")]
+        if let Some(addr) = self.statsd_addr {
+            match crate::statsd::StatsdExporter::connect(&addr, self.statsd_interval) {
+                Ok(exporter) => crate::statsd::register(exporter),
+                Err(e) => eprintln!("Failed to set up StatsD exporter for {}: {}", addr, e),
+            }
+        }
+        #[cfg(feature = "mmap")]
+        if let Some(path) = self.mmap_path {
+            match crate::mmap_export::MmapExporter::create(&path) {
+                Ok(exporter) => crate::mmap_export::register(exporter),
+                Err(e) => eprintln!(
+                    "Failed to create channel statistics file {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        let start_time = Instant::now();
+        let printer = Printer {
             format: self.format,
+            ascii: self.ascii,
+            thousands_separator: self.thousands_separator,
+            max_label_width: self.max_label_width,
+            sort_key: self.sort_key,
+            filters: self.filters,
+        };
+        let periodic = self
+            .print_interval
+            .and_then(|interval| PeriodicPrinter::spawn(printer.clone(), start_time, interval));
+        ChannelsGuard {
+            start_time,
+            printer,
+            periodic,
         }
     }
 }
@@ -79,18 +561,79 @@ impl Default for ChannelsGuardBuilder {
 /// ```
 pub struct ChannelsGuard {
     start_time: Instant,
+    printer: Printer,
+    periodic: Option<PeriodicPrinter>,
+}
+
+/// Predicate selecting the channels included in the printed statistics.
+type ChannelFilter = Arc<dyn Fn(&SerializableChannelStats) -> bool + Send + Sync>;
+
+/// Output settings shared by the final and the periodic statistics.
+#[derive(Clone)]
+struct Printer {
     format: Format,
+    ascii: bool,
+    thousands_separator: char,
+    max_label_width: Option<usize>,
+    sort_key: SortKey,
+    filters: Vec<ChannelFilter>,
+}
+
+/// Background thread printing statistics every interval until the guard is dropped.
+struct PeriodicPrinter {
+    stop_tx: crossbeam_channel::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl PeriodicPrinter {
+    fn spawn(printer: Printer, start_time: Instant, interval: Duration) -> Option<Self> {
+        let (stop_tx, stop_rx) = crossbeam_channel::bounded::<()>(0);
+        let spawned = std::thread::Builder::new()
+            .name(crate::thread_name(
+                "CHANNELS_CONSOLE_PRINTER_THREAD_NAME",
+                "channel-stats-printer",
+            ))
+            .spawn(move || {
+                // Any message or a disconnect means the guard is being dropped
+                while let Err(crossbeam_channel::RecvTimeoutError::Timeout) =
+                    stop_rx.recv_timeout(interval)
+                {
+                    printer.print(start_time.elapsed(), false);
+                }
+            });
+        match spawned {
+            Ok(handle) => Some(Self { stop_tx, handle }),
+            Err(e) => {
+                eprintln!("Failed to spawn the periodic statistics printer: {}", e);
+                None
+            }
+        }
+    }
+
+    fn stop(self) {
+        drop(self.stop_tx);
+        let _ = self.handle.join();
+    }
 }
 
 impl ChannelsGuard {
-    /// Create a new channels guard with default settings (table format).
+    /// Create a new channels guard with default settings (table format, unless
+    /// `CHANNELS_CONSOLE_FORMAT` selects another one).
     /// Statistics will be printed when this guard is dropped.
     ///
     /// For custom configuration, use `ChannelsGuardBuilder::new()` instead.
     pub fn new() -> Self {
         Self {
             start_time: Instant::now(),
-            format: Format::default(),
+            printer: Printer {
+                format: default_format(),
+                ascii: false,
+                thousands_separator: DEFAULT_THOUSANDS_SEPARATOR,
+                max_label_width: Some(DEFAULT_MAX_LABEL_WIDTH),
+                sort_key: SortKey::default(),
+                filters: Vec::new(),
+            },
+            periodic: None,
         }
     }
 
@@ -105,7 +648,7 @@ impl ChannelsGuard {
     /// let _guard = ChannelsGuard::new().format(Format::Json);
     /// ```
     pub fn format(mut self, format: Format) -> Self {
-        self.format = format;
+        self.printer.format = format;
         self
     }
 }
@@ -118,15 +661,45 @@ impl Default for ChannelsGuard {
 
 impl Drop for ChannelsGuard {
     fn drop(&mut self) {
-        let elapsed = self.start_time.elapsed();
+        // Stopped first so a snapshot can't interleave with the final statistics
+        if let Some(periodic) = self.periodic.take() {
+            periodic.stop();
+        }
+        self.printer.print(self.start_time.elapsed(), true);
+    }
+}
+
+impl Printer {
+    /// Statistics of all streams and the matching channels, in the configured order.
+    fn combined_json(&self, elapsed: Duration) -> CombinedJson {
+        let mut combined = get_combined_json();
+        combined.runtime_seconds = elapsed.as_secs_f64();
+        self.select(&mut combined.channels);
+        combined
+    }
+
+    /// Drop channels not matching the filters and sort the rest.
+    fn select(&self, channels: &mut Vec<SerializableChannelStats>) {
+        channels.retain(|channel| self.filters.iter().all(|filter| filter(channel)));
+        sort_channels(channels, self.sort_key, self.sort_key != SortKey::Label);
+    }
+
+    /// Print the statistics in the configured format, `report_empty` prints a note when
+    /// nothing is instrumented instead of staying silent.
+    fn print(&self, elapsed: Duration, report_empty: bool) {
+        flush_channel_stats();
         let channels = get_sorted_channel_stats();
         let streams = get_sorted_stream_stats();
 
         if channels.is_empty() && streams.is_empty() {
-            println!("\nNo instrumented channels or streams found.");
+            if report_empty {
+                println!("\nNo instrumented channels or streams found.");
+            }
             return;
         }
 
+        let leaks = suspected_leaks(&channels);
+
         match self.format {
             Format::Table => {
                 println!(
@@ -134,82 +707,321 @@ impl Drop for ChannelsGuard {
                     elapsed.as_secs_f64()
                 );
 
-                // Display channels table if there are any
-                if !channels.is_empty() {
-                    let mut table = Table::new();
-
-                    table.add_row(Row::new(vec![
-                        Cell::new("Channel"),
-                        Cell::new("Type"),
-                        Cell::new("State"),
-                        Cell::new("Sent"),
-                        Cell::new("Received"),
-                        Cell::new("Queued"),
-                        Cell::new("Mem"),
-                    ]));
-
-                    for channel_stats in channels {
-                        let label = resolve_label(
-                            channel_stats.source,
-                            channel_stats.label.as_deref(),
-                            channel_stats.iter,
-                        );
-                        table.add_row(Row::new(vec![
-                            Cell::new(&label),
-                            Cell::new(&channel_stats.channel_type.to_string()),
-                            Cell::new(channel_stats.state.as_str()),
-                            Cell::new(&channel_stats.sent_count.to_string()),
-                            Cell::new(&channel_stats.received_count.to_string()),
-                            Cell::new(&channel_stats.queued().to_string()),
-                            Cell::new(&format_bytes(channel_stats.queued_bytes())),
-                        ]));
-                    }
-
-                    println!("\nChannels:");
-                    table.printstd();
-                }
-
-                // Display streams table if there are any
-                if !streams.is_empty() {
-                    let mut table = Table::new();
-
-                    table.add_row(Row::new(vec![
-                        Cell::new("Stream"),
-                        Cell::new("State"),
-                        Cell::new("Yielded"),
-                    ]));
-
-                    for stream_stats in streams {
-                        let label = resolve_label(
-                            stream_stats.source,
-                            stream_stats.label.as_deref(),
-                            stream_stats.iter,
-                        );
-                        table.add_row(Row::new(vec![
-                            Cell::new(&label),
-                            Cell::new(stream_stats.state.as_str()),
-                            Cell::new(&stream_stats.items_yielded.to_string()),
-                        ]));
-                    }
-
-                    println!("\nStreams:");
-                    table.printstd();
-                }
+                let mut channels: Vec<SerializableChannelStats> = channels
+                    .iter()
+                    .map(SerializableChannelStats::from)
+                    .collect();
+                self.select(&mut channels);
+                let streams: Vec<SerializableStreamStats> =
+                    streams.iter().map(SerializableStreamStats::from).collect();
+                print!(
+                    "{}",
+                    render_tables(
+                        &channels,
+                        &streams,
+                        self.ascii || !unicode_supported(),
+                        self.thousands_separator,
+                        self.max_label_width,
+                    )
+                );
             }
             Format::Json => {
-                let combined = get_combined_json();
+                let combined = self.combined_json(elapsed);
                 match serde_json::to_string(&combined) {
                     Ok(json) => println!("{}", json),
                     Err(e) => eprintln!("Failed to serialize statistics to JSON: {}", e),
                 }
             }
             Format::JsonPretty => {
-                let combined = get_combined_json();
+                let combined = self.combined_json(elapsed);
+                match serde_json::to_string_pretty(&combined) {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => eprintln!("Failed to serialize statistics to pretty JSON: {}", e),
+                }
+            }
+            Format::JsonLines => {
+                let combined = self.combined_json(elapsed);
+                for channel in &combined.channels {
+                    match serde_json::to_string(channel) {
+                        Ok(json) => println!("{}", json),
+                        Err(e) => {
+                            eprintln!("Failed to serialize channel statistics to JSON: {}", e)
+                        }
+                    }
+                }
+                for stream in &combined.streams {
+                    match serde_json::to_string(stream) {
+                        Ok(json) => println!("{}", json),
+                        Err(e) => eprintln!("Failed to serialize stream statistics to JSON: {}", e),
+                    }
+                }
+            }
+            Format::Summary => {
+                let mut channels: Vec<SerializableChannelStats> = channels
+                    .iter()
+                    .map(SerializableChannelStats::from)
+                    .collect();
+                self.select(&mut channels);
+                println!("{}", render_summary(&channels));
+            }
+            #[cfg(feature = "yaml")]
+            Format::Yaml => {
+                let combined = self.combined_json(elapsed);
+                match serde_norway::to_string(&combined) {
+                    Ok(yaml) => println!("{}", yaml),
+                    Err(e) => eprintln!("Failed to serialize statistics to YAML: {}", e),
+                }
+            }
+            #[cfg(not(feature = "yaml"))]
+            Format::Yaml => {
+                eprintln!("Format::Yaml requires the `yaml` feature, printing JSON instead");
+                let combined = self.combined_json(elapsed);
                 match serde_json::to_string_pretty(&combined) {
                     Ok(json) => println!("{}", json),
                     Err(e) => eprintln!("Failed to serialize statistics to pretty JSON: {}", e),
                 }
             }
         }
+
+        // Reported on stderr so machine-readable formats stay parseable
+        for leak in leaks {
+            eprintln!(
+                "Warning: channel '{}' may be leaking, its queue grew by {:.1} msg/s to {} messages",
+                leak.label, leak.growth_per_sec, leak.queued
+            );
+        }
+    }
+}
+
+/// Render the line printed by the guard in the `Summary` format.
+fn render_summary(channels: &[SerializableChannelStats]) -> String {
+    let sent: u64 = channels.iter().map(|c| c.sent_count).sum();
+    let received: u64 = channels.iter().map(|c| c.received_count).sum();
+    let queued: u64 = channels.iter().map(|c| c.queued).sum();
+    let queued_bytes: u64 = channels.iter().map(|c| c.queued_bytes).sum();
+    let mut summary = format!(
+        "channels={} sent={} recv={} queued={} mem={}",
+        channels.len(),
+        format_compact(sent),
+        format_compact(received),
+        format_compact(queued),
+        format_bytes(queued_bytes).replace(' ', "")
+    );
+    // Oneshots given up on by their receiver, only reported when there are oneshots
+    if channels
+        .iter()
+        .any(|c| c.channel_type == ChannelType::Oneshot)
+    {
+        let cancelled = channels.iter().filter(|c| c.cancelled).count() as u64;
+        summary.push_str(&format!(" cancelled={}", format_compact(cancelled)));
+    }
+    summary
+}
+
+/// Shorten large counts to one decimal with a K, M or G suffix, e.g. `3.4M`.
+fn format_compact(count: u64) -> String {
+    const UNITS: &[(f64, &str)] = &[(1e9, "G"), (1e6, "M"), (1e3, "K")];
+    UNITS
+        .iter()
+        .find(|(scale, _)| count as f64 >= *scale)
+        .map_or_else(
+            || count.to_string(),
+            |(scale, unit)| format!("{:.1}{}", count as f64 / scale, unit),
+        )
+}
+
+/// Render the channels and streams tables printed by the guard in the `Table` format.
+pub(crate) fn render_tables(
+    channels: &[SerializableChannelStats],
+    streams: &[SerializableStreamStats],
+    ascii: bool,
+    thousands_separator: char,
+    max_label_width: Option<usize>,
+) -> String {
+    let mut output = String::new();
+    let label = |label: &str| match max_label_width {
+        Some(width) => truncate_label(label, width, ascii),
+        None => label.to_string(),
+    };
+
+    if !channels.is_empty() {
+        // Unbounded queues have no capacity, their bars are relative to the largest one
+        let max_unbounded_queued = channels
+            .iter()
+            .filter(|c| c.channel_type == ChannelType::Unbounded)
+            .map(|c| c.queued)
+            .max()
+            .unwrap_or(0);
+
+        let mut table = Table::new();
+
+        table.add_row(Row::new(vec![
+            Cell::new("Channel"),
+            Cell::new("Type"),
+            Cell::new("State"),
+            Cell::new("Sent"),
+            Cell::new("Received"),
+            Cell::new("Queued"),
+            Cell::new("Util%"),
+            Cell::new("Drain"),
+            Cell::new("Load"),
+            Cell::new("Mem"),
+            Cell::new("Peak Mem"),
+            Cell::new("Received Mem"),
+        ]));
+
+        for channel_stats in channels {
+            let load = match channel_stats.channel_type {
+                ChannelType::Bounded(cap) if cap > 0 => channel_stats.queued as f64 / cap as f64,
+                ChannelType::Bounded(_) => 0.0,
+                ChannelType::Oneshot => channel_stats.queued.min(1) as f64,
+                ChannelType::Unbounded if max_unbounded_queued > 0 => {
+                    channel_stats.queued as f64 / max_unbounded_queued as f64
+                }
+                ChannelType::Unbounded => 0.0,
+            };
+            table.add_row(Row::new(vec![
+                Cell::new(&label(&channel_stats.label)),
+                Cell::new(&channel_stats.type_label()),
+                Cell::new(&if channel_stats.disabled {
+                    format!("{} (disabled)", channel_stats.state)
+                } else {
+                    channel_stats.state.to_string()
+                }),
+                Cell::new(&group_thousands(
+                    channel_stats.sent_count,
+                    thousands_separator,
+                )),
+                Cell::new(&group_thousands(
+                    channel_stats.received_count,
+                    thousands_separator,
+                )),
+                Cell::new(&group_thousands(channel_stats.queued, thousands_separator)),
+                Cell::new(
+                    &channel_stats
+                        .utilization
+                        .map(|u| format!("{:.1}%", u))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::new(
+                    &channel_stats
+                        .drain_ratio
+                        .map(|r| format!("{:.2}", r))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::new(&queue_bar(load, ascii)),
+                Cell::new(&format_bytes(channel_stats.queued_bytes)),
+                Cell::new(&format_bytes(channel_stats.peak_queued_bytes)),
+                Cell::new(&format_bytes(channel_stats.received_bytes)),
+            ]));
+        }
+
+        output.push_str("\nChannels:\n");
+        output.push_str(&table.to_string());
+        output.push_str(&format!(
+            "Total queued: {}\n",
+            format_bytes(crate::total_queued_bytes(channels))
+        ));
+        if channels.iter().any(|c| c.type_size == 0) {
+            output.push_str(
+                "(ZST) channels carry zero-sized messages, their memory is always 0 B.\n",
+            );
+        }
+    }
+
+    if !streams.is_empty() {
+        let mut table = Table::new();
+
+        table.add_row(Row::new(vec![
+            Cell::new("Stream"),
+            Cell::new("State"),
+            Cell::new("Yielded"),
+        ]));
+
+        for stream_stats in streams {
+            table.add_row(Row::new(vec![
+                Cell::new(&label(&stream_stats.label)),
+                Cell::new(stream_stats.state.as_str()),
+                Cell::new(&group_thousands(
+                    stream_stats.items_yielded,
+                    thousands_separator,
+                )),
+            ]));
+        }
+
+        output.push_str("\nStreams:\n");
+        output.push_str(&table.to_string());
+    }
+
+    output
+}
+
+/// Keep the last characters of a label longer than `width`, after an ellipsis.
+fn truncate_label(label: &str, width: usize, ascii: bool) -> String {
+    let len = label.chars().count();
+    if len <= width {
+        return label.to_string();
+    }
+    let ellipsis = if ascii { "..." } else { "…" };
+    let keep = width.saturating_sub(ellipsis.chars().count());
+    let tail: String = label.chars().skip(len - keep).collect();
+    format!("{}{}", ellipsis, tail)
+}
+
+/// Format `n` with `separator` between groups of three digits.
+fn group_thousands(n: u64, separator: char) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+const QUEUE_BAR_WIDTH: usize = 8;
+
+/// Partially filled cells, in eighths.
+const QUEUE_BAR_EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Render `fraction` (0.0-1.0) as a fixed-width horizontal bar.
+fn queue_bar(fraction: f64, ascii: bool) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    if ascii {
+        let filled = (fraction * QUEUE_BAR_WIDTH as f64).round() as usize;
+        return format!(
+            "{}{}",
+            "#".repeat(filled),
+            ".".repeat(QUEUE_BAR_WIDTH - filled)
+        );
+    }
+
+    let eighths = (fraction * (QUEUE_BAR_WIDTH * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(partial) = (eighths % 8).checked_sub(1) {
+        bar.push(QUEUE_BAR_EIGHTHS[partial]);
+    }
+    let drawn = bar.chars().count();
+    bar.push_str(&" ".repeat(QUEUE_BAR_WIDTH - drawn));
+    bar
+}
+
+/// Whether the terminal is expected to render block characters, judged from `NO_COLOR`
+/// and the locale environment variables.
+pub(crate) fn unicode_supported() -> bool {
+    if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
     }
+    // The first non-empty variable decides, like in setlocale
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(true)
 }
@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::histogram::Histogram;
+use crate::DwellTimeStats;
+
+/// Tracks how long messages wait inside the instrumented channel.
+///
+/// Channels are FIFO, so the n-th sent message is the n-th received one. Send and
+/// receive events come from different forwarders and may reach the collector out of
+/// order, so whichever side arrives first is queued until its counterpart shows up.
+/// Messages that are never counted on both sides, e.g. while the channel is disabled,
+/// would shift the pairing, so the queues are trimmed to the counts and cleared when
+/// they can't be matched anymore.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DwellTracker {
+    unmatched_sent: VecDeque<Instant>,
+    unmatched_received: VecDeque<Instant>,
+    histogram: Histogram,
+}
+
+impl DwellTracker {
+    pub(crate) fn on_sent(&mut self, timestamp: Instant) {
+        match self.unmatched_received.pop_front() {
+            Some(received_at) => self.record(timestamp, received_at),
+            None => self.unmatched_sent.push_back(timestamp),
+        }
+    }
+
+    pub(crate) fn on_received(&mut self, timestamp: Instant) {
+        match self.unmatched_sent.pop_front() {
+            Some(sent_at) => self.record(sent_at, timestamp),
+            None => self.unmatched_received.push_back(timestamp),
+        }
+    }
+
+    /// Drop the oldest unmatched sends beyond the `pending` messages the counts say are
+    /// still in the channel, their receives were not counted.
+    pub(crate) fn trim(&mut self, pending: u64) {
+        let excess = (self.unmatched_sent.len() as u64).saturating_sub(pending);
+        self.unmatched_sent.drain(..excess as usize);
+    }
+
+    /// Forget the unmatched timestamps after messages went by uncounted, pairing starts
+    /// over with the next send.
+    pub(crate) fn resync(&mut self) {
+        self.unmatched_sent.clear();
+        self.unmatched_received.clear();
+    }
+
+    /// Forget the recorded dwell times, messages still in the channel stay tracked.
+    pub(crate) fn reset(&mut self) {
+        self.histogram = Histogram::default();
+    }
+
+    fn record(&mut self, sent_at: Instant, received_at: Instant) {
+        let dwell = received_at.saturating_duration_since(sent_at);
+        self.histogram.record(dwell.as_nanos() as u64);
+    }
+
+    /// Aggregated dwell time, or `None` if no message has been received yet.
+    pub(crate) fn stats(&self) -> Option<DwellTimeStats> {
+        if self.histogram.count() == 0 {
+            return None;
+        }
+
+        Some(DwellTimeStats {
+            avg_ns: self.histogram.mean(),
+            max_ns: self.histogram.max(),
+            p50_ns: self.histogram.percentile(0.50),
+            p95_ns: self.histogram.percentile(0.95),
+            p99_ns: self.histogram.percentile(0.99),
+        })
+    }
+}
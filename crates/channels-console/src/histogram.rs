@@ -0,0 +1,95 @@
+/// Number of bits used for sub-buckets within each power-of-two range.
+/// Values below `2^SUB_BUCKET_BITS` are tracked exactly, larger ones with ~3% precision.
+const SUB_BUCKET_BITS: u32 = 5;
+const SUB_BUCKET_COUNT: u64 = 1 << SUB_BUCKET_BITS;
+
+/// A compact log-linear histogram of `u64` samples (HDR-style).
+///
+/// Buckets are allocated lazily, so a histogram that only ever sees small values
+/// stays small.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Histogram {
+    counts: Vec<u64>,
+    total: u64,
+    sum: u128,
+    max: u64,
+}
+
+impl Histogram {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a single sample.
+    pub(crate) fn record(&mut self, value: u64) {
+        let idx = bucket_index(value);
+        if idx >= self.counts.len() {
+            self.counts.resize(idx + 1, 0);
+        }
+        self.counts[idx] += 1;
+        self.total += 1;
+        self.sum += value as u128;
+        self.max = self.max.max(value);
+    }
+
+    /// Number of recorded samples.
+    #[cfg_attr(not(feature = "dwell-time"), allow(dead_code))]
+    pub(crate) fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Largest recorded sample.
+    #[cfg_attr(not(feature = "dwell-time"), allow(dead_code))]
+    pub(crate) fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Mean of all recorded samples, or 0 when empty.
+    #[cfg_attr(not(feature = "dwell-time"), allow(dead_code))]
+    pub(crate) fn mean(&self) -> u64 {
+        if self.total == 0 {
+            0
+        } else {
+            (self.sum / self.total as u128) as u64
+        }
+    }
+
+    /// Value at the given quantile (0.0..=1.0), or 0 when empty.
+    pub(crate) fn percentile(&self, quantile: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+
+        let rank = ((quantile * self.total as f64).ceil() as u64).clamp(1, self.total);
+        let mut seen = 0;
+        for (idx, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_upper_bound(idx).min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+fn bucket_index(value: u64) -> usize {
+    if value < SUB_BUCKET_COUNT {
+        return value as usize;
+    }
+    let msb = 63 - value.leading_zeros();
+    let shift = msb - SUB_BUCKET_BITS;
+    let sub = (value >> shift) - SUB_BUCKET_COUNT;
+    (SUB_BUCKET_COUNT + shift as u64 * SUB_BUCKET_COUNT + sub) as usize
+}
+
+fn bucket_upper_bound(idx: usize) -> u64 {
+    let idx = idx as u64;
+    if idx < SUB_BUCKET_COUNT {
+        return idx;
+    }
+    let rel = idx - SUB_BUCKET_COUNT;
+    let shift = rel / SUB_BUCKET_COUNT;
+    let sub = rel % SUB_BUCKET_COUNT;
+    let upper = ((SUB_BUCKET_COUNT + sub + 1) as u128) << shift;
+    (upper - 1).min(u64::MAX as u128) as u64
+}
@@ -1,8 +1,19 @@
-use crate::{get_channel_logs, get_channels_json, get_stream_logs, get_streams_json};
+use crate::openmetrics;
+use crate::{
+    get_channel_logs, get_channels_json, get_health_json, get_stream_logs, get_streams_json,
+    get_suspected_leaks, metrics_path, reset_all, reset_channel, sort_channels, ChannelDelta,
+    SerializableChannelStats, SortKey, SERVER_START_TIME,
+};
 use regex::Regex;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::fmt::Display;
-use std::sync::LazyLock;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tiny_http::{Header, Request, Response, Server};
 
 static RE_CHANNEL_LOGS: LazyLock<Regex> =
@@ -10,76 +21,477 @@ static RE_CHANNEL_LOGS: LazyLock<Regex> =
 static RE_STREAM_LOGS: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^/streams/(\d+)/logs$").unwrap());
 
-pub(crate) fn start_metrics_server(addr: &str) {
-    let server = match Server::http(addr) {
-        Ok(s) => s,
-        Err(e) => {
-            panic!("Failed to bind metrics server to {}: {}. Customize the port using the CHANNELS_CONSOLE_METRICS_PORT environment variable.", addr, e);
+/// Number of the last `GET /channels` response.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Most clients whose counts are kept for deltas, the one that scraped least recently
+/// is forgotten first.
+const MAX_DELTA_CLIENTS: usize = 64;
+
+/// Counts each client saw in its previous scrape with `?deltas=true`, by client token.
+static DELTA_CURSORS: LazyLock<Mutex<HashMap<String, DeltaCursor>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+struct DeltaCursor {
+    scraped_at: Instant,
+    /// Sent and received counts by channel id.
+    counts: HashMap<u64, (u64, u64)>,
+}
+
+/// The running server, kept so that [`stop_metrics_server`] can unblock it.
+struct ServerSlot {
+    server: Option<Arc<Server>>,
+    /// TCP port the running server is bound to.
+    port: Option<u16>,
+    stopped: bool,
+}
+
+static SERVER: Mutex<ServerSlot> = Mutex::new(ServerSlot {
+    server: None,
+    port: None,
+    stopped: false,
+});
+
+/// Where the metrics server listens.
+#[derive(Debug, Clone)]
+pub(crate) enum MetricsAddr {
+    /// Candidates tried in order until one can be bound.
+    Tcp(Vec<SocketAddr>),
+    /// tiny_http supports Unix domain sockets natively, so only the listener differs and
+    /// requests are handled exactly like on TCP.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl Display for MetricsAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricsAddr::Tcp(addrs) => {
+                let addrs: Vec<String> = addrs.iter().map(|a| format!("http://{}", a)).collect();
+                write!(f, "{}", addrs.join(" or "))
+            }
+            #[cfg(unix)]
+            MetricsAddr::Unix(path) => write!(f, "unix:{}", path.display()),
         }
+    }
+}
+
+fn bind(addr: &MetricsAddr) -> Server {
+    match addr {
+        MetricsAddr::Tcp(addrs) => match Server::http(&addrs[..]) {
+            Ok(s) => s,
+            Err(e) => {
+                panic!("Failed to bind metrics server to {}: {}. Customize the address using the CHANNELS_CONSOLE_METRICS_HOST and CHANNELS_CONSOLE_METRICS_PORT environment variables.", addr, e);
+            }
+        },
+        #[cfg(unix)]
+        MetricsAddr::Unix(path) => {
+            remove_stale_socket(path);
+            match Server::http_unix(path) {
+                Ok(s) => s,
+                Err(e) => {
+                    panic!(
+                        "Failed to bind metrics server to Unix socket {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Remove a socket file left behind by a previous run, never touching other file types.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) {
+    use std::os::unix::fs::FileTypeExt;
+
+    if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+pub(crate) fn start_metrics_server(addr: &MetricsAddr) {
+    let server = Arc::new(bind(addr));
+    SERVER_START_TIME.get_or_init(Instant::now);
+
+    let registered = {
+        let mut slot = SERVER.lock().unwrap();
+        if !slot.stopped {
+            slot.server = Some(Arc::clone(&server));
+            slot.port = server.server_addr().to_ip().map(|addr| addr.port());
+        }
+        !slot.stopped
     };
 
-    println!("Channel metrics server listening on http://{}", addr);
+    if registered {
+        match server.server_addr().to_ip() {
+            Some(bound) => println!(
+                "Channel metrics server listening on http://{}{}",
+                bound,
+                metrics_path()
+            ),
+            None => println!("Channel metrics server listening on {}", addr),
+        }
+
+        for request in server.incoming_requests() {
+            handle_request(request);
+        }
+    }
+
+    #[cfg(unix)]
+    if let MetricsAddr::Unix(path) = addr {
+        let _ = std::fs::remove_file(path);
+    }
+}
 
-    for request in server.incoming_requests() {
-        handle_request(request);
+/// Make the server loop return. A server that hasn't bound yet exits right after binding.
+pub(crate) fn stop_metrics_server() {
+    let server = {
+        let mut slot = SERVER.lock().unwrap();
+        slot.stopped = true;
+        slot.port = None;
+        slot.server.take()
+    };
+    if let Some(server) = server {
+        server.unblock();
     }
 }
 
+pub(crate) fn bound_port() -> Option<u16> {
+    SERVER.lock().unwrap().port
+}
+
+/// Response of `POST /reset`.
+#[derive(Serialize)]
+struct ResetJson {
+    /// Whether any channel was reset.
+    found: bool,
+}
+
 fn handle_request(request: Request) {
-    let path = request.url().split('?').next().unwrap_or("/");
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+    // Endpoints are only served under the configured prefix
+    let response = match path.strip_prefix(metrics_path()) {
+        Some(path) if path.starts_with('/') => route(path, query, request.method().as_str()),
+        _ => ApiResponse::error(404, "Not found"),
+    };
+
+    let mut http_response = Response::from_data(response.body).with_status_code(response.status);
+    http_response.add_header(
+        Header::from_bytes(b"Content-Type".as_slice(), response.content_type.as_bytes()).unwrap(),
+    );
+    if let Some(allow) = response.allow {
+        http_response
+            .add_header(Header::from_bytes(b"Allow".as_slice(), allow.as_bytes()).unwrap());
+    }
+    let _ = request.respond(http_response);
+}
 
+/// Methods accepted by the endpoint at `path`, as listed in an `Allow` header. `None` if
+/// there is no such endpoint.
+fn allowed_methods(path: &str) -> Option<&'static str> {
     match path {
+        "/reset" => Some("POST"),
+        "/channels"
+        | "/channels/leaks"
+        | "/channels/influx"
+        | "/channels/openmetrics"
+        | "/streams"
+        | "/health"
+        | "/metrics/schema" => Some("GET, HEAD"),
+        _ if RE_CHANNEL_LOGS.is_match(path) || RE_STREAM_LOGS.is_match(path) => Some("GET, HEAD"),
+        _ => None,
+    }
+}
+
+/// Answer a `method` request for `path`, relative to the metrics path, with the raw
+/// `query` string. Shared by the embedded server and the axum router.
+pub(crate) fn route(path: &str, query: &str, method: &str) -> ApiResponse {
+    let Some(allow) = allowed_methods(path) else {
+        return ApiResponse::error(404, "Not found");
+    };
+    if !allow.split(", ").any(|allowed| allowed == method) {
+        return ApiResponse::method_not_allowed(allow);
+    }
+
+    let params = parse_query(query);
+
+    match path {
+        "/reset" => match params.get("channel") {
+            Some(channel) if !reset_channel(channel) => {
+                ApiResponse::error(404, "Channel not found")
+            }
+            Some(_) => ApiResponse::json(&ResetJson { found: true }),
+            None => ApiResponse::json(&ResetJson { found: reset_all() }),
+        },
         "/channels" => {
+            let mut channels = get_channels_json();
+            channels.sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1;
+
+            // Deltas cover all channels, so the cursor survives a changing filter
+            channels.deltas = match params.get("deltas").map(String::as_str) {
+                Some("true") => match params.get("client") {
+                    Some(client) if !client.is_empty() => {
+                        Some(channel_deltas(client, &channels.channels))
+                    }
+                    _ => return ApiResponse::error(400, "deltas require a client token"),
+                },
+                Some("false") | None => None,
+                Some(other) => {
+                    return ApiResponse::error(400, &format!("invalid deltas: {}", other))
+                }
+            };
+
+            if let Some(filter) = params.get("filter") {
+                channels.channels.retain(|c| {
+                    c.label.contains(filter.as_str())
+                        || c.source.contains(filter.as_str())
+                        || c.id.to_string().contains(filter.as_str())
+                });
+            }
+
+            let sort = match params.get("sort").map(|s| s.parse::<SortKey>()) {
+                Some(Ok(key)) => key,
+                Some(Err(e)) => return ApiResponse::error(400, &e),
+                None => SortKey::default(),
+            };
+            let descending = match params.get("order").map(String::as_str) {
+                Some("desc") => true,
+                Some("asc") | None => false,
+                Some(other) => {
+                    return ApiResponse::error(400, &format!("invalid order: {}", other))
+                }
+            };
+            sort_channels(&mut channels.channels, sort, descending);
+
+            if let Some(deltas) = &mut channels.deltas {
+                deltas.retain(|delta| channels.channels.iter().any(|c| c.id == delta.id));
+            }
+
+            ApiResponse::json(&channels)
+        }
+        "/channels/leaks" => {
+            let leaks = get_suspected_leaks();
+            ApiResponse::json(&leaks)
+        }
+        "/channels/influx" => {
+            let channels = get_channels_json();
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default();
+            ApiResponse::text(
+                &influx_lines(&channels.channels, timestamp),
+                "text/plain; charset=utf-8",
+            )
+        }
+        "/channels/openmetrics" => {
             let channels = get_channels_json();
-            respond_json(request, &channels);
+            ApiResponse::text(
+                &openmetrics::render(
+                    &channels.channels,
+                    channels.generation,
+                    channels.runtime_seconds,
+                ),
+                openmetrics::CONTENT_TYPE,
+            )
         }
         "/streams" => {
             let streams = get_streams_json();
-            respond_json(request, &streams);
+            ApiResponse::json(&streams)
         }
+        "/health" => ApiResponse::json(&get_health_json()),
+        "/metrics/schema" => ApiResponse::json(&crate::channel_stats_schema()),
         _ => {
             // Handle /channels/<id>/logs
             if let Some(caps) = RE_CHANNEL_LOGS.captures(path) {
                 return match get_channel_logs(&caps[1]) {
-                    Some(logs) => respond_json(request, &logs),
-                    None => respond_error(request, 404, "Channel not found"),
+                    Some(logs) => ApiResponse::json(&logs),
+                    None => ApiResponse::error(404, "Channel not found"),
                 };
             }
 
             // Handle /streams/<id>/logs
             if let Some(caps) = RE_STREAM_LOGS.captures(path) {
                 return match get_stream_logs(&caps[1]) {
-                    Some(logs) => respond_json(request, &logs),
-                    None => respond_error(request, 404, "Stream not found"),
+                    Some(logs) => ApiResponse::json(&logs),
+                    None => ApiResponse::error(404, "Stream not found"),
                 };
             }
 
-            respond_error(request, 404, "Not found");
+            ApiResponse::error(404, "Not found")
         }
     }
 }
 
-fn respond_json<T: Serialize>(request: Request, value: &T) {
-    match serde_json::to_vec(value) {
-        Ok(body) => {
-            let mut response = Response::from_data(body);
-            response.add_header(
-                Header::from_bytes(b"Content-Type".as_slice(), b"application/json".as_slice())
-                    .unwrap(),
-            );
-            let _ = request.respond(response);
+/// Messages of every channel since the previous scrape of `client`, remembering the
+/// current counts for its next one.
+fn channel_deltas(client: &str, channels: &[SerializableChannelStats]) -> Vec<ChannelDelta> {
+    let mut cursors = DELTA_CURSORS.lock().unwrap();
+    if !cursors.contains_key(client) && cursors.len() >= MAX_DELTA_CLIENTS {
+        let oldest = cursors
+            .iter()
+            .min_by_key(|(_, cursor)| cursor.scraped_at)
+            .map(|(token, _)| token.clone());
+        if let Some(oldest) = oldest {
+            cursors.remove(&oldest);
         }
-        Err(e) => respond_internal_error(request, e),
     }
+
+    let counts: HashMap<u64, (u64, u64)> = channels
+        .iter()
+        .map(|c| (c.id, (c.sent_count, c.received_count)))
+        .collect();
+    let previous = cursors
+        .insert(
+            client.to_string(),
+            DeltaCursor {
+                scraped_at: Instant::now(),
+                counts,
+            },
+        )
+        .map(|cursor| cursor.counts)
+        .unwrap_or_default();
+
+    channels
+        .iter()
+        .map(|c| {
+            let (sent, received) = previous.get(&c.id).copied().unwrap_or_default();
+            ChannelDelta {
+                id: c.id,
+                sent_delta: counter_delta(sent, c.sent_count),
+                received_delta: counter_delta(received, c.received_count),
+            }
+        })
+        .collect()
 }
 
-fn respond_error(request: Request, code: u16, msg: &str) {
-    let _ = request.respond(Response::from_string(msg).with_status_code(code));
+/// Increase of a counter, or its whole value if it went down after a reset.
+fn counter_delta(previous: u64, current: u64) -> u64 {
+    current.checked_sub(previous).unwrap_or(current)
 }
 
-fn respond_internal_error(request: Request, e: impl Display) {
-    eprintln!("Internal server error: {}", e);
-    let _ = request.respond(
-        Response::from_string(format!("Internal server error: {}", e)).with_status_code(500),
-    );
+/// Parse a URL query string into key/value pairs, decoding `+` and percent escapes.
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Render channel statistics in InfluxDB line protocol, one line per channel.
+fn influx_lines(channels: &[SerializableChannelStats], timestamp: u128) -> String {
+    let mut body = String::new();
+    for c in channels {
+        body.push_str(&format!(
+            "channel,id={},label={},type={} sent={}i,received={}i,queued={}i,queued_bytes={}i {}\n",
+            c.id,
+            escape_influx_tag(&c.label),
+            c.kind,
+            c.sent_count,
+            c.received_count,
+            c.queued,
+            c.queued_bytes,
+            timestamp
+        ));
+    }
+    body
+}
+
+/// Escape commas, spaces and equals signs, which delimit tags in line protocol. Line
+/// breaks can't be escaped, they are replaced with spaces.
+fn escape_influx_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        let ch = if matches!(ch, '\n' | '\r') { ' ' } else { ch };
+        if matches!(ch, ',' | ' ' | '=') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Status, content type and body of an API response, independent of the server writing it.
+pub(crate) struct ApiResponse {
+    pub(crate) status: u16,
+    pub(crate) content_type: &'static str,
+    pub(crate) body: Vec<u8>,
+    /// Methods for the `Allow` header of a `405` response.
+    pub(crate) allow: Option<&'static str>,
+}
+
+impl ApiResponse {
+    fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self {
+                status: 200,
+                content_type: "application/json",
+                body,
+                allow: None,
+            },
+            Err(e) => Self::internal_error(e),
+        }
+    }
+
+    fn text(body: &str, content_type: &'static str) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            body: body.as_bytes().to_vec(),
+            allow: None,
+        }
+    }
+
+    fn error(status: u16, msg: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=UTF-8",
+            body: msg.as_bytes().to_vec(),
+            allow: None,
+        }
+    }
+
+    fn method_not_allowed(allow: &'static str) -> Self {
+        Self {
+            allow: Some(allow),
+            ..Self::error(405, &format!("Method not allowed, use {}", allow))
+        }
+    }
+
+    fn internal_error(e: impl Display) -> Self {
+        eprintln!("Internal server error: {}", e);
+        Self::error(500, &format!("Internal server error: {}", e))
+    }
 }
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::panic::Location;
+use std::sync::{LazyLock, Mutex};
+
+use crate::{set_source_group, Instrument, InstrumentLog};
+
+static INTERNED_SOURCES: LazyLock<Mutex<HashSet<&'static str>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Return a `'static` copy of `source`, allocating it only once per distinct value.
+fn intern_source(source: String) -> &'static str {
+    let mut sources = INTERNED_SOURCES.lock().unwrap();
+    if let Some(existing) = sources.get(source.as_str()) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(source.into_boxed_str());
+    sources.insert(leaked);
+    leaked
+}
+
+/// Builder for instrumenting a channel without the `channel!` macro.
+///
+/// Useful in generic code or when channels live in struct fields. Unless overridden
+/// with [`id`](Self::id), the channel is identified by the `file:line` that called
+/// [`InstrumentBuilder::new`]. Unlike `channel!`, the id can't include the module path:
+/// `#[track_caller]` only exposes the caller's file, line and column, and `module_path!()`
+/// would expand to this module. Set an explicit id if a channel may be instrumented with
+/// either API.
+///
+/// # Examples
+///
+/// ```
+/// use channels_console::InstrumentBuilder;
+///
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// let (tx, rx) = InstrumentBuilder::new((tx, rx))
+///     .id("ingest-queue")
+///     .label("ingest")
+///     .group("ingest-pipeline")
+///     .build();
+///
+/// tx.send(1).unwrap();
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// ```
+pub struct InstrumentBuilder<C> {
+    channel: C,
+    source: &'static str,
+    label: Option<String>,
+    group: Option<&'static str>,
+    capacity: Option<usize>,
+    sample: u64,
+}
+
+impl<C> InstrumentBuilder<C> {
+    /// Start instrumenting the given `(sender, receiver)` pair.
+    #[track_caller]
+    pub fn new(channel: C) -> Self {
+        let location = Location::caller();
+        Self {
+            channel,
+            source: intern_source(format!("{}:{}", location.file(), location.line())),
+            label: None,
+            group: None,
+            capacity: None,
+            sample: 1,
+        }
+    }
+
+    /// Set a stable identifier used instead of the caller's `file:line`.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.source = intern_source(id.into());
+        self
+    }
+
+    /// Set a custom display label.
+    pub fn label(mut self, label: impl ToString) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Merge the statistics of this channel into one entry with every other channel of
+    /// `group`, regardless of [`DuplicateChannels`](crate::DuplicateChannels).
+    ///
+    /// The entry keeps the id, type and capacity of the first channel created and reports
+    /// an `instance_count`, the same way as [`DuplicateChannels::Aggregate`](crate::DuplicateChannels::Aggregate).
+    /// Without a [`label`](Self::label), the group name is used as the label. The group
+    /// applies to every channel instrumented with the same id.
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(intern_source(group.into()));
+        self
+    }
+
+    /// Set the channel capacity (required for bounded `std` and `futures` channels).
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Report only one in `rate` messages to the collector, scaling the counts accordingly.
+    ///
+    /// Reduces overhead on very hot channels at the cost of approximate statistics.
+    /// A rate of `0` is treated as `1`.
+    pub fn sample(mut self, rate: u64) -> Self {
+        self.sample = rate;
+        self
+    }
+
+    /// Instrument the channel and return the wrapped pair.
+    pub fn build(self) -> <C as Instrument>::Output
+    where
+        C: Instrument,
+    {
+        let label = self.register_group();
+        self.channel
+            .instrument(self.source, label, self.capacity, self.sample)
+    }
+
+    /// Instrument the channel with message logging enabled (requires `T: Debug`).
+    pub fn build_log(self) -> <C as InstrumentLog>::Output
+    where
+        C: InstrumentLog,
+    {
+        let label = self.register_group();
+        self.channel
+            .instrument_log(self.source, label, self.capacity, self.sample)
+    }
+
+    /// Register the group of this channel's source and return the label to use.
+    fn register_group(&self) -> Option<String> {
+        match self.group {
+            Some(group) => {
+                set_source_group(self.source, group);
+                Some(self.label.clone().unwrap_or_else(|| group.to_string()))
+            }
+            None => self.label.clone(),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ChannelStats;
+
+const DEFAULT_LEAK_WINDOW_MS: u64 = 60_000;
+
+/// Number of intervals the leak window is split into.
+const LEAK_SAMPLES: usize = 10;
+
+/// How far back the queue trend is tracked before a channel can be reported as leaking.
+/// Configurable with the `CHANNELS_CONSOLE_LEAK_WINDOW_MS` environment variable.
+static LEAK_WINDOW: LazyLock<Duration> = LazyLock::new(|| {
+    let ms = std::env::var("CHANNELS_CONSOLE_LEAK_WINDOW_MS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_LEAK_WINDOW_MS);
+    Duration::from_millis(ms)
+});
+
+/// Sampled history of a channel's queue depth.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QueueTrend {
+    samples: VecDeque<u64>,
+}
+
+impl QueueTrend {
+    fn record(&mut self, queued: u64) {
+        if self.samples.len() > LEAK_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(queued);
+    }
+
+    /// Growth rate in messages per second if the queue grew at every sample
+    /// across the whole window.
+    fn growth_per_sec(&self) -> Option<f64> {
+        if self.samples.len() <= LEAK_SAMPLES {
+            return None;
+        }
+
+        let strictly_increasing = self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .all(|(prev, next)| next > prev);
+        if !strictly_increasing {
+            return None;
+        }
+
+        let first = *self.samples.front()?;
+        let last = *self.samples.back()?;
+        Some((last - first) as f64 / LEAK_WINDOW.as_secs_f64())
+    }
+}
+
+/// Records queue samples on collector ticks, spaced evenly across the leak window.
+pub(crate) struct LeakDetector {
+    last_sample: Option<Instant>,
+}
+
+impl LeakDetector {
+    pub(crate) fn new() -> Self {
+        Self { last_sample: None }
+    }
+
+    pub(crate) fn on_tick(&mut self, stats: &mut HashMap<u64, ChannelStats>) {
+        let interval = *LEAK_WINDOW / LEAK_SAMPLES as u32;
+        if self
+            .last_sample
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_sample = Some(Instant::now());
+
+        for channel_stats in stats.values_mut() {
+            let queued = channel_stats.queued();
+            channel_stats.queued_trend.record(queued);
+        }
+    }
+}
+
+/// A channel whose queue has been growing for the whole leak window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspectedLeak {
+    pub id: u64,
+    pub label: String,
+    pub queued: u64,
+    /// Average queue growth over the leak window, in messages per second.
+    pub growth_per_sec: f64,
+}
+
+/// Channels that are still open and whose queue grew at every sample in the window.
+pub(crate) fn suspected_leaks(channels: &[ChannelStats]) -> Vec<SuspectedLeak> {
+    channels
+        .iter()
+        .filter(|c| !c.state.is_closed())
+        .filter_map(|c| {
+            let growth_per_sec = c.queued_trend.growth_per_sec()?;
+            Some(SuspectedLeak {
+                id: c.id,
+                label: c.display_label(),
+                queued: c.queued(),
+                growth_per_sec,
+            })
+        })
+        .collect()
+}
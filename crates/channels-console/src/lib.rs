@@ -1,18 +1,62 @@
-use crossbeam_channel::{unbounded, Sender as CbSender};
+use crossbeam_channel::{unbounded, RecvTimeoutError, Sender as CbSender};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
-use std::sync::atomic::AtomicU64;
-use std::sync::{Arc, OnceLock, RwLock};
-use std::time::Instant;
-
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, Once, OnceLock, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+pub mod alerts;
 pub mod channels_guard;
 pub use channels_guard::{ChannelsGuard, ChannelsGuardBuilder};
-
-use crate::http_api::start_metrics_server;
+mod channel_view;
+pub use channel_view::ChannelView;
+mod instrument_builder;
+pub use instrument_builder::InstrumentBuilder;
+
+use crate::histogram::Histogram;
+use crate::http_api::{start_metrics_server, MetricsAddr};
+#[cfg(feature = "axum")]
+mod axum_router;
+#[cfg(feature = "axum")]
+pub use axum_router::channels_metrics_router;
+#[cfg(feature = "dwell-time")]
+mod dwell_time;
+mod histogram;
 mod http_api;
+mod leaks;
+#[cfg(feature = "mmap")]
+mod mmap_export;
+mod op_duration;
+mod openmetrics;
+mod queue_average;
+mod rates;
+mod schema;
+mod send_timeout;
+mod send_wait;
+mod sizers;
+#[cfg(feature = "statsd")]
+mod statsd;
+#[cfg(feature = "tokio")]
+mod subscribe;
+pub use leaks::SuspectedLeak;
+pub use schema::{channel_stats_schema, ChannelStatsSchema, FieldSchema};
+#[cfg(feature = "tokio")]
+pub use send_timeout::SendTimeoutExt;
+pub use sizers::register_sizer;
+#[cfg(feature = "tokio")]
+pub use subscribe::{subscribe, StatsSnapshot};
 mod stream_wrappers;
 mod wrappers;
 
+/// Nanoseconds between the start of statistics collection and `instant`.
+fn elapsed_ns(instant: Instant) -> u64 {
+    let start_time = START_TIME.get().copied().unwrap_or(instant);
+    instant.duration_since(start_time).as_nanos() as u64
+}
+
 /// A single log entry for a message sent or received.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
@@ -23,11 +67,9 @@ pub struct LogEntry {
 
 impl LogEntry {
     pub(crate) fn new(index: u64, timestamp: Instant, message: Option<String>) -> Self {
-        let start_time = START_TIME.get().copied().unwrap_or(timestamp);
-        let timestamp_nanos = timestamp.duration_since(start_time).as_nanos() as u64;
         Self {
             index,
-            timestamp: timestamp_nanos,
+            timestamp: elapsed_ns(timestamp),
             message,
         }
     }
@@ -41,6 +83,25 @@ pub enum ChannelType {
     Oneshot,
 }
 
+impl ChannelType {
+    /// Kind of the channel without its capacity, e.g. `bounded`.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            ChannelType::Bounded(_) => "bounded",
+            ChannelType::Unbounded => "unbounded",
+            ChannelType::Oneshot => "oneshot",
+        }
+    }
+
+    /// Capacity of a bounded channel.
+    pub(crate) fn capacity(&self) -> Option<usize> {
+        match self {
+            ChannelType::Bounded(capacity) => Some(*capacity),
+            ChannelType::Unbounded | ChannelType::Oneshot => None,
+        }
+    }
+}
+
 impl std::fmt::Display for ChannelType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -92,16 +153,122 @@ pub enum Format {
     Table,
     Json,
     JsonPretty,
+    /// One JSON object per line (NDJSON) for each channel and stream.
+    JsonLines,
+    /// A single line with totals over all channels, e.g.
+    /// `channels=12 sent=3.4M recv=3.4M queued=120 mem=4.2MB`.
+    Summary,
+    /// YAML output, requires the `yaml` feature. Without it a warning is printed and the
+    /// output falls back to [`Format::JsonPretty`].
+    Yaml,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "json-pretty" => Ok(Format::JsonPretty),
+            "json-lines" => Ok(Format::JsonLines),
+            "summary" => Ok(Format::Summary),
+            "yaml" => Ok(Format::Yaml),
+            _ => Err(format!("invalid format: {}", s)),
+        }
+    }
+}
+
+/// How channels created at the same source location with the same label are reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateChannels {
+    /// Every channel gets its own entry, labels of repeated creations are suffixed with
+    /// their iteration, e.g. `worker-2`.
+    #[default]
+    Disambiguate,
+    /// Channels share one entry with summed counts and an `instance_count`. Queue depth,
+    /// memory and state are estimated over all instances together.
+    Aggregate,
+}
+
+/// Key used to order channel statistics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortKey {
+    /// Custom labels first (alphabetically), then auto-generated labels by source location.
+    #[default]
+    Label,
+    Id,
+    Sent,
+    Received,
+    Queued,
+    QueuedBytes,
+    /// Time since the last send or receive.
+    Idle,
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "label" => Ok(SortKey::Label),
+            "id" => Ok(SortKey::Id),
+            "sent" => Ok(SortKey::Sent),
+            "received" => Ok(SortKey::Received),
+            "queued" => Ok(SortKey::Queued),
+            "queued_bytes" => Ok(SortKey::QueuedBytes),
+            "idle" => Ok(SortKey::Idle),
+            _ => Err(format!("invalid sort key: {}", s)),
+        }
+    }
+}
+
+/// Sort channels by the given key. The input is expected to be in the default
+/// (label) order, which `SortKey::Label` preserves.
+pub(crate) fn sort_channels(
+    channels: &mut [SerializableChannelStats],
+    key: SortKey,
+    descending: bool,
+) {
+    let value: Option<fn(&SerializableChannelStats) -> u64> = match key {
+        SortKey::Label => None,
+        SortKey::Id => Some(|c| c.id),
+        SortKey::Sent => Some(|c| c.sent_count),
+        SortKey::Received => Some(|c| c.received_count),
+        SortKey::Queued => Some(|c| c.queued),
+        SortKey::QueuedBytes => Some(|c| c.queued_bytes),
+        SortKey::Idle => Some(|c| c.idle_for_ms),
+    };
+
+    match value {
+        None if descending => channels.reverse(),
+        None => {}
+        Some(value) if descending => channels.sort_by_key(|c| std::cmp::Reverse(value(c))),
+        Some(value) => channels.sort_by_key(value),
+    }
 }
 
 /// State of a instrumented channel.
+///
+/// Bounded and unbounded channels are `Active`, `Full` or `Closed`. Oneshot channels go
+/// through their own lifecycle starting at `Created`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ChannelState {
     #[default]
     Active,
     Closed,
     Full,
-    Notified,
+    /// The oneshot value was sent but hasn't reached the receiver yet. Servers before the
+    /// oneshot lifecycle states reported it as `notified`.
+    Sent,
+    /// The oneshot channel hasn't carried a value yet.
+    Created,
+    /// The oneshot value was delivered to the receiver.
+    Received,
+    /// The oneshot sender was dropped without sending a value.
+    SenderDropped,
+    /// The oneshot receiver was dropped before a value was delivered.
+    ReceiverDropped,
 }
 
 impl std::fmt::Display for ChannelState {
@@ -116,9 +283,40 @@ impl ChannelState {
             ChannelState::Active => "active",
             ChannelState::Closed => "closed",
             ChannelState::Full => "full",
-            ChannelState::Notified => "notified",
+            ChannelState::Sent => "sent",
+            ChannelState::Created => "created",
+            ChannelState::Received => "received",
+            ChannelState::SenderDropped => "sender_dropped",
+            ChannelState::ReceiverDropped => "receiver_dropped",
+        }
+    }
+
+    /// Stable numeric code of the state, for consumers that shouldn't match strings:
+    /// 0 active, 1 closed, 2 full, 3 sent, 4 created, 5 received, 6 sender dropped,
+    /// 7 receiver dropped.
+    pub fn code(&self) -> u8 {
+        match self {
+            ChannelState::Active => 0,
+            ChannelState::Closed => 1,
+            ChannelState::Full => 2,
+            ChannelState::Sent => 3,
+            ChannelState::Created => 4,
+            ChannelState::Received => 5,
+            ChannelState::SenderDropped => 6,
+            ChannelState::ReceiverDropped => 7,
         }
     }
+
+    /// Whether the channel can't carry messages anymore.
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            ChannelState::Closed
+                | ChannelState::Received
+                | ChannelState::SenderDropped
+                | ChannelState::ReceiverDropped
+        )
+    }
 }
 
 impl Serialize for ChannelState {
@@ -140,7 +338,11 @@ impl<'de> Deserialize<'de> for ChannelState {
             "active" => Ok(ChannelState::Active),
             "closed" => Ok(ChannelState::Closed),
             "full" => Ok(ChannelState::Full),
-            "notified" => Ok(ChannelState::Notified),
+            "sent" | "notified" => Ok(ChannelState::Sent),
+            "created" => Ok(ChannelState::Created),
+            "received" => Ok(ChannelState::Received),
+            "sender_dropped" => Ok(ChannelState::SenderDropped),
+            "receiver_dropped" => Ok(ChannelState::ReceiverDropped),
             _ => Err(serde::de::Error::custom("invalid channel state")),
         }
     }
@@ -152,18 +354,79 @@ pub(crate) struct ChannelStats {
     pub(crate) id: u64,
     pub(crate) source: &'static str,
     pub(crate) label: Option<String>,
+    /// Label made from `source` when the channel was created, shown without a custom one.
+    pub(crate) generated_label: String,
     pub(crate) channel_type: ChannelType,
     pub(crate) state: ChannelState,
     pub(crate) sent_count: u64,
     pub(crate) received_count: u64,
+    /// Total size of all sent messages, see [`register_sizer`].
+    pub(crate) sent_bytes: u64,
+    pub(crate) received_bytes: u64,
+    /// Largest `queued_bytes()` seen after a send.
+    pub(crate) peak_queued_bytes: u64,
+    pub(crate) full_events: u64,
+    /// Messages dropped by the proxies because the receiving side was gone.
+    pub(crate) send_errors: u64,
+    /// Capacity held by outstanding permits, see [`SerializableChannelStats::reserved`].
+    pub(crate) reserved: u64,
+    /// Failed sends, with their bytes, taken back before their `MessageSent` was applied.
+    pending_unsent: (u64, u64),
     pub(crate) type_name: &'static str,
     pub(crate) type_size: usize,
     pub(crate) sent_logs: VecDeque<LogEntry>,
     pub(crate) received_logs: VecDeque<LogEntry>,
     pub(crate) iter: u32,
+    /// Every message event stands for this many messages, see the `sample` option.
+    pub(crate) sample_rate: u64,
+    pub(crate) multi_consumer: bool,
+    /// Tokio task the channel was created in.
+    pub(crate) task_id: Option<u64>,
+    pub(crate) runtime: Option<RuntimeInfo>,
+    /// Set with `preview = true`, the latest message is kept in `preview`.
+    pub(crate) preview_enabled: bool,
+    /// Whether sent logs keep the message text, off when only a preview was asked for.
+    pub(crate) log_messages: bool,
+    pub(crate) preview: Option<String>,
+    pub(crate) receivers_dropped: bool,
+    /// Oneshot whose receiver was dropped before the value arrived.
+    pub(crate) cancelled: bool,
+    /// Turned off with [`disable_channel`], messages are not counted.
+    pub(crate) disabled: bool,
+    /// Live and ever created sender handles per instance, only known for tokio mpsc channels.
+    pub(crate) senders: HashMap<u64, (u64, u64)>,
+    /// Channels merged into this entry, see [`DuplicateChannels::Aggregate`].
+    pub(crate) instance_count: u64,
+    /// Instances whose channel was closed, whose receivers were dropped and whose
+    /// forwarders finished. The entry follows once all instances did.
+    pub(crate) closed_instances: HashSet<u64>,
+    pub(crate) dropped_receiver_instances: HashSet<u64>,
+    pub(crate) finished_instances: u64,
+    pub(crate) created_at: Instant,
+    pub(crate) first_sent_at: Option<Instant>,
+    /// Time of the most recent send or receive.
+    pub(crate) last_message_at: Option<Instant>,
+    pub(crate) queue_depth: Histogram,
+    pub(crate) queue_average: queue_average::QueueAverage,
+    pub(crate) queued_trend: leaks::QueueTrend,
+    pub(crate) rates: rates::DecayingRates,
+    /// Received messages taken off both counts by resets, so pushed counters keep rising.
+    #[cfg(feature = "statsd")]
+    pub(crate) reset_received: u64,
+    #[cfg(feature = "dwell-time")]
+    pub(crate) dwell: dwell_time::DwellTracker,
+    pub(crate) send_wait: send_wait::SendWaitHistogram,
+    pub(crate) send_timeout: send_timeout::SendTimeoutTracker,
+    pub(crate) send_duration: op_duration::OpDurationTracker,
+    pub(crate) recv_duration: op_duration::OpDurationTracker,
 }
 
 impl ChannelStats {
+    /// Custom label, or the generated one, numbered for channels sharing a source.
+    pub(crate) fn display_label(&self) -> String {
+        resolve_label(&self.generated_label, self.label.as_deref(), self.iter)
+    }
+
     pub fn queued(&self) -> u64 {
         self.sent_count
             .saturating_sub(self.received_count)
@@ -171,7 +434,85 @@ impl ChannelStats {
     }
 
     pub fn queued_bytes(&self) -> u64 {
-        self.queued() * self.type_size as u64
+        // Bytes of all messages not received yet, scaled down to the queued ones since the
+        // message held by the receive forwarder is not counted as queued.
+        let unreceived = self.sent_count.saturating_sub(self.received_count);
+        if unreceived == 0 {
+            return 0;
+        }
+        let unreceived_bytes = self.sent_bytes.saturating_sub(self.received_bytes);
+        (unreceived_bytes as u128 * self.queued() as u128 / unreceived as u128) as u64
+    }
+
+    /// Average of `count` messages per second since the channel was created.
+    fn lifetime_rate(&self, count: u64) -> f64 {
+        let secs = self.created_at.elapsed().as_secs_f64();
+        if secs > 0.0 {
+            count as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Milliseconds since the last send or receive, or since creation if there was none.
+    pub fn idle_for_ms(&self) -> u64 {
+        self.last_message_at
+            .unwrap_or(self.created_at)
+            .elapsed()
+            .as_millis() as u64
+    }
+
+    /// Number of live receivers, `None` while receivers of a multi-consumer channel are
+    /// alive, since clones of the native receiver can't be observed.
+    pub fn active_receivers(&self) -> Option<u64> {
+        if self.receivers_dropped {
+            Some(0)
+        } else if self.multi_consumer {
+            None
+        } else {
+            Some(1)
+        }
+    }
+
+    /// Number of live senders, `None` unless sender handles can be counted.
+    pub fn active_senders(&self) -> Option<u64> {
+        (!self.senders.is_empty()).then(|| self.senders.values().map(|s| s.0).sum())
+    }
+
+    /// Number of sender handles that ever existed, including dropped clones.
+    pub fn total_senders_created(&self) -> Option<u64> {
+        (!self.senders.is_empty()).then(|| self.senders.values().map(|s| s.1).sum())
+    }
+
+    /// Percentage (0-100) of capacity in use, only defined for bounded channels.
+    pub fn utilization(&self) -> Option<f64> {
+        match self.channel_type {
+            ChannelType::Bounded(cap) if cap > 0 => {
+                Some((self.queued() as f64 / cap as f64 * 100.0).min(100.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Capacity left for new messages after queued ones and outstanding permits, `None`
+    /// unless bounded.
+    pub fn available(&self) -> Option<u64> {
+        match self.channel_type {
+            ChannelType::Bounded(cap) => Some(
+                (cap as u64)
+                    .saturating_sub(self.queued())
+                    .saturating_sub(self.reserved),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Share of sent messages that have been received, `None` before the first send.
+    pub fn drain_ratio(&self) -> Option<f64> {
+        if self.sent_count == 0 {
+            return None;
+        }
+        Some(self.received_count as f64 / self.sent_count as f64)
     }
 }
 
@@ -181,6 +522,8 @@ pub(crate) struct StreamStats {
     pub(crate) id: u64,
     pub(crate) source: &'static str,
     pub(crate) label: Option<String>,
+    /// Label made from `source` when the stream was created, shown without a custom one.
+    pub(crate) generated_label: String,
     pub(crate) state: ChannelState, // Only Active or Closed
     pub(crate) items_yielded: u64,
     pub(crate) type_name: &'static str,
@@ -194,8 +537,57 @@ pub(crate) struct StreamStats {
 pub struct ChannelsJson {
     /// Current elapsed time since program start in nanoseconds
     pub current_elapsed_ns: u64,
+    /// Number of the `GET /channels` response, increasing by one with every response and
+    /// starting over at 1 when the program restarts, see [`ChannelDelta`].
+    #[serde(default)]
+    pub sequence: u64,
+    /// Number of resets with [`reset_channel`], [`reset_all`] or `POST /reset` so far. When
+    /// it changes between two scrapes, counters may have gone down and rates computed from
+    /// their difference are meaningless.
+    #[serde(default)]
+    pub generation: u64,
+    /// Seconds since the metrics server started, the time the counters accumulated over.
+    #[serde(default)]
+    pub runtime_seconds: f64,
     /// Channel statistics
     pub channels: Vec<SerializableChannelStats>,
+    /// Messages since the client's previous scrape, only requested with `?deltas=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deltas: Option<Vec<ChannelDelta>>,
+}
+
+/// Messages sent and received by a channel since the previous `GET /channels?deltas=true`
+/// of the same `client` token.
+///
+/// Channels new to the client count from zero, as do channels whose counters went down
+/// after a [`reset_channel`]. Clients that can't keep a token across reconnects can
+/// compute deltas from the counters themselves, a `sequence` lower than the last one they
+/// saw means the program restarted and the counters started over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelDelta {
+    pub id: u64,
+    pub sent_delta: u64,
+    pub received_delta: u64,
+}
+
+/// Health of the statistics collection itself, served by `GET /health`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthJson {
+    /// Channel events waiting for the collector thread. Message events buffered by a
+    /// forwarder arrive as one batch and count once. Persistently large values mean the
+    /// collector can't keep up and the statistics lag behind, consider sampling hot
+    /// channels.
+    pub pending_channel_events: usize,
+    /// Stream events waiting for the collector thread.
+    pub pending_stream_events: usize,
+    /// Time from a message being sent or received until the collector applied its
+    /// event, for the most recent message. If it keeps climbing, the statistics are stale.
+    #[serde(default)]
+    pub collector_lag_ms: f64,
+    /// Memory used by the queued messages of all channels, the aggregate to alert on when
+    /// a service's channels together hold too much.
+    #[serde(default)]
+    pub total_queued_bytes: u64,
 }
 
 /// Wrapper for streams-only JSON response
@@ -203,6 +595,9 @@ pub struct ChannelsJson {
 pub struct StreamsJson {
     /// Current elapsed time since program start in nanoseconds
     pub current_elapsed_ns: u64,
+    /// Seconds since the metrics server started, see [`ChannelsJson::runtime_seconds`].
+    #[serde(default)]
+    pub runtime_seconds: f64,
     /// Stream statistics
     pub streams: Vec<SerializableStreamStats>,
 }
@@ -212,12 +607,124 @@ pub struct StreamsJson {
 pub struct CombinedJson {
     /// Current elapsed time since program start in nanoseconds
     pub current_elapsed_ns: u64,
+    /// Number of resets so far, see [`ChannelsJson::generation`].
+    #[serde(default)]
+    pub generation: u64,
+    /// Seconds since the [`ChannelsGuard`] was built when printed by it, otherwise since
+    /// the metrics server started, or the first channel or stream was instrumented
+    /// without one.
+    #[serde(default)]
+    pub runtime_seconds: f64,
     /// Channel statistics
     pub channels: Vec<SerializableChannelStats>,
     /// Stream statistics
     pub streams: Vec<SerializableStreamStats>,
 }
 
+impl CombinedJson {
+    /// Render the statistics as the tables printed by [`ChannelsGuard`] in the
+    /// [`Format::Table`] format.
+    pub fn to_table(&self) -> String {
+        channels_guard::render_tables(
+            &self.channels,
+            &self.streams,
+            !channels_guard::unicode_supported(),
+            channels_guard::DEFAULT_THOUSANDS_SEPARATOR,
+            Some(channels_guard::DEFAULT_MAX_LABEL_WIDTH),
+        )
+    }
+
+    /// Memory used by the queued messages of all channels, the sum of their
+    /// `queued_bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let total = channels_console::snapshot().total_queued_bytes();
+    /// if total > 64 * 1024 * 1024 {
+    ///     eprintln!("Channels hold {total} bytes");
+    /// }
+    /// ```
+    pub fn total_queued_bytes(&self) -> u64 {
+        total_queued_bytes(&self.channels)
+    }
+}
+
+pub(crate) fn total_queued_bytes(channels: &[SerializableChannelStats]) -> u64 {
+    channels.iter().map(|c| c.queued_bytes).sum()
+}
+
+/// Time messages spent queued between being sent and received, in nanoseconds.
+/// Only collected when the `dwell-time` feature is enabled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DwellTimeStats {
+    pub avg_ns: u64,
+    pub max_ns: u64,
+    pub p50_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+}
+
+/// Time the send path of a bounded channel waited for capacity, as a histogram.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendWaitStats {
+    /// Cumulative counts like Prometheus `le` buckets, from the smallest bound up.
+    pub buckets: Vec<SendWaitBucket>,
+    pub sum_seconds: f64,
+    pub count: u64,
+}
+
+/// Sends made with [`SendTimeoutExt::instrumented_send_timeout`], the timeout they were
+/// given and how long they waited, in nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SendTimeoutStats {
+    /// Number of timed sends, delivered or timed out.
+    pub count: u64,
+    /// Timeout of the most recent timed send.
+    pub timeout_ns: u64,
+    pub avg_wait_ns: u64,
+    pub max_wait_ns: u64,
+}
+
+/// Time spent in the forwarded operations of one direction, in nanoseconds. Only
+/// collected when the `op-durations` feature is enabled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OpDurationStats {
+    /// Number of timed operations, scaled by the sample rate.
+    pub count: u64,
+    pub total_ns: u64,
+    pub avg_ns: u64,
+    pub max_ns: u64,
+}
+
+/// Tokio runtime a channel was created on, to correlate its statistics with the runtime's
+/// metrics. Only captured with the `tokio` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeInfo {
+    /// Id of the runtime, the same as `tokio::runtime::Handle::id`.
+    pub id: Option<u64>,
+    /// `current_thread` or `multi_thread`.
+    pub flavor: String,
+    /// Number of worker threads of the runtime.
+    pub workers: usize,
+    /// Index of the worker thread that created the channel, matching the worker indices of
+    /// `tokio::runtime::RuntimeMetrics`. Only known when built with `--cfg tokio_unstable`
+    /// and created on a worker thread.
+    pub worker: Option<usize>,
+}
+
+/// Number of sends that waited at most `le` seconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SendWaitBucket {
+    pub le: f64,
+    pub count: u64,
+}
+
+/// Default of `sample_rate` and `instance_count` in snapshots written before they existed.
+fn default_one() -> u64 {
+    1
+}
+
 /// Serializable version of channel statistics for JSON responses.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializableChannelStats {
@@ -226,14 +733,157 @@ pub struct SerializableChannelStats {
     pub label: String,
     pub has_custom_label: bool,
     pub channel_type: ChannelType,
+    /// `bounded`, `unbounded` or `oneshot`, the channel type without its capacity.
+    #[serde(default)]
+    pub kind: String,
+    /// Capacity of a bounded channel, `None` for unbounded and oneshot channels.
+    #[serde(default)]
+    pub capacity: Option<usize>,
     pub state: ChannelState,
+    /// Numeric form of `state`, see [`ChannelState::code`].
+    #[serde(default)]
+    pub state_code: u8,
     pub sent_count: u64,
     pub received_count: u64,
     pub queued: u64,
     pub type_name: String,
     pub type_size: usize,
     pub queued_bytes: u64,
+    /// Most memory the queued messages ever used, the high-water mark of `queued_bytes`.
+    #[serde(default)]
+    pub peak_queued_bytes: u64,
+    /// Total bytes of all messages received so far, estimated like `queued_bytes`.
+    #[serde(default)]
+    pub received_bytes: u64,
+    /// `queued_bytes` formatted with [`format_bytes`], e.g. `1.5 KB`.
+    #[serde(default)]
+    pub queued_bytes_human: String,
+    /// `peak_queued_bytes` formatted with [`format_bytes`].
+    #[serde(default)]
+    pub peak_queued_bytes_human: String,
+    /// `received_bytes` formatted with [`format_bytes`].
+    #[serde(default)]
+    pub received_bytes_human: String,
     pub iter: u32,
+    /// Number of times a bounded channel transitioned into the full state.
+    #[serde(default)]
+    pub full_events: u64,
+    /// Messages that couldn't be delivered because the receiver was dropped, they are not
+    /// counted in `sent_count`.
+    #[serde(default)]
+    pub send_errors: u64,
+    /// Sends made with [`SendTimeoutExt::instrumented_send_timeout`] that timed out. Always
+    /// `0` for other channels.
+    #[serde(default)]
+    pub send_timeout_count: u64,
+    /// Percentage (0-100) of capacity in use, `None` for unbounded and oneshot channels.
+    #[serde(default)]
+    pub utilization: Option<f64>,
+    /// Slots reserved by outstanding tokio permits (`reserve()` / `try_reserve()`) that
+    /// haven't been used or dropped yet. Always `0` for other channels.
+    #[serde(default)]
+    pub reserved: u64,
+    /// `capacity - queued - reserved`, `None` unless bounded.
+    #[serde(default)]
+    pub available: Option<u64>,
+    /// `received_count / sent_count`, below 1.0 when consumers fall behind. `None` before
+    /// the first send.
+    #[serde(default)]
+    pub drain_ratio: Option<f64>,
+    /// Median queue depth sampled on every send and receive.
+    #[serde(default)]
+    pub queued_p50: u64,
+    /// 95th percentile of sampled queue depth.
+    #[serde(default)]
+    pub queued_p95: u64,
+    /// 99th percentile of sampled queue depth.
+    #[serde(default)]
+    pub queued_p99: u64,
+    /// Queue depth averaged over the time since creation or the last reset, weighting
+    /// each depth by how long it lasted. Unlike the percentiles, which sample on every
+    /// message, a short burst barely moves it.
+    #[serde(default)]
+    pub avg_queued: f64,
+    /// Enqueue-to-dequeue latency, `None` unless the `dwell-time` feature is enabled.
+    #[serde(default)]
+    pub dwell_time: Option<DwellTimeStats>,
+    /// Time sends waited for capacity, `None` for unbounded and oneshot channels or before
+    /// the first send.
+    #[serde(default)]
+    pub send_wait: Option<SendWaitStats>,
+    /// Timeout and actual waits of the sends made with
+    /// [`SendTimeoutExt::instrumented_send_timeout`], `None` before the first one.
+    #[serde(default)]
+    pub send_timeout: Option<SendTimeoutStats>,
+    /// Time the send forwarder spent passing messages to the original channel, `None`
+    /// unless the `op-durations` feature is enabled.
+    #[serde(default)]
+    pub send_duration: Option<OpDurationStats>,
+    /// Time the receive forwarder spent handing messages to the outer receiver, `None`
+    /// unless the `op-durations` feature is enabled.
+    #[serde(default)]
+    pub recv_duration: Option<OpDurationStats>,
+    /// Only one in `sample_rate` messages is observed, counts are approximate when above 1.
+    #[serde(default = "default_one")]
+    pub sample_rate: u64,
+    /// Number of live receivers, `None` when unknown (multi-consumer channels with at least
+    /// one receiver alive).
+    #[serde(default)]
+    pub active_receivers: Option<u64>,
+    /// Number of live senders including clones, `None` unless the channel is a tokio mpsc
+    /// channel.
+    #[serde(default)]
+    pub active_senders: Option<u64>,
+    /// Number of senders that ever existed, a lower bound since clones are counted by
+    /// polling and ones dropped within 100ms can be missed. `None` like `active_senders`.
+    #[serde(default)]
+    pub total_senders_created: Option<u64>,
+    /// Time of the first send, in nanoseconds since statistics collection started.
+    #[serde(default)]
+    pub first_sent_ns: Option<u64>,
+    /// Time of the most recent send or receive, in nanoseconds since statistics collection started.
+    #[serde(default)]
+    pub last_message_ns: Option<u64>,
+    /// Milliseconds since the last send or receive, or since creation if there was none.
+    #[serde(default)]
+    pub idle_for_ms: u64,
+    /// Messages sent per second, decaying with a one-minute time constant like the Unix
+    /// load average, so it reflects the recent traffic. Updated every second.
+    #[serde(default)]
+    pub sent_rate_1m: f64,
+    /// Messages received per second, decaying like `sent_rate_1m`.
+    #[serde(default)]
+    pub received_rate_1m: f64,
+    /// Messages sent per second over the lifetime of the channel.
+    #[serde(default)]
+    pub sent_rate_avg: f64,
+    /// Messages received per second over the lifetime of the channel.
+    #[serde(default)]
+    pub received_rate_avg: f64,
+    /// Number of channels reported by this entry, above 1 only with
+    /// [`DuplicateChannels::Aggregate`].
+    #[serde(default = "default_one")]
+    pub instance_count: u64,
+    /// Id of the tokio task that created the channel, as shown by tokio-console. `None`
+    /// outside of a task or without the `tokio` feature.
+    #[serde(default)]
+    pub task_id: Option<u64>,
+    /// Tokio runtime that created the channel, `None` outside of a runtime or without the
+    /// `tokio` feature.
+    #[serde(default)]
+    pub runtime: Option<RuntimeInfo>,
+    /// `Debug` output of the most recent message, truncated, for channels instrumented with
+    /// `preview = true`.
+    #[serde(default)]
+    pub preview: Option<String>,
+    /// Whether the receiver of a oneshot was dropped before the value arrived, e.g. because
+    /// the caller timed out or was cancelled. Always `false` for other channels.
+    #[serde(default)]
+    pub cancelled: bool,
+    /// Whether monitoring was turned off with [`disable_channel`], the counts are frozen
+    /// until [`enable_channel`].
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 /// Serializable version of stream statistics for JSON responses.
@@ -244,19 +894,35 @@ pub struct SerializableStreamStats {
     pub label: String,
     pub has_custom_label: bool,
     pub state: ChannelState,
+    /// Numeric form of `state`, see [`ChannelState::code`].
+    #[serde(default)]
+    pub state_code: u8,
     pub items_yielded: u64,
     pub type_name: String,
     pub type_size: usize,
     pub iter: u32,
 }
 
+impl SerializableChannelStats {
+    /// Channel type for display, e.g. `bounded[10]`. Channels of zero-sized messages like
+    /// `()` are marked `(ZST)`, their memory figures are always 0 B.
+    pub fn type_label(&self) -> String {
+        if self.type_size == 0 {
+            format!("{} (ZST)", self.channel_type)
+        } else {
+            self.channel_type.to_string()
+        }
+    }
+}
+
 impl From<&ChannelStats> for SerializableChannelStats {
     fn from(channel_stats: &ChannelStats) -> Self {
-        let label = resolve_label(
-            channel_stats.source,
-            channel_stats.label.as_deref(),
-            channel_stats.iter,
-        );
+        let label = channel_stats.display_label();
+
+        #[cfg(feature = "dwell-time")]
+        let dwell_time = channel_stats.dwell.stats();
+        #[cfg(not(feature = "dwell-time"))]
+        let dwell_time = None;
 
         Self {
             id: channel_stats.id,
@@ -264,25 +930,62 @@ impl From<&ChannelStats> for SerializableChannelStats {
             label,
             has_custom_label: channel_stats.label.is_some(),
             channel_type: channel_stats.channel_type,
+            kind: channel_stats.channel_type.kind().to_string(),
+            capacity: channel_stats.channel_type.capacity(),
             state: channel_stats.state,
+            state_code: channel_stats.state.code(),
             sent_count: channel_stats.sent_count,
             received_count: channel_stats.received_count,
             queued: channel_stats.queued(),
             type_name: channel_stats.type_name.to_string(),
             type_size: channel_stats.type_size,
             queued_bytes: channel_stats.queued_bytes(),
+            peak_queued_bytes: channel_stats.peak_queued_bytes,
+            received_bytes: channel_stats.received_bytes,
+            queued_bytes_human: format_bytes(channel_stats.queued_bytes()),
+            peak_queued_bytes_human: format_bytes(channel_stats.peak_queued_bytes),
+            received_bytes_human: format_bytes(channel_stats.received_bytes),
             iter: channel_stats.iter,
+            full_events: channel_stats.full_events,
+            send_errors: channel_stats.send_errors,
+            send_timeout_count: channel_stats.send_timeout.timeouts(),
+            utilization: channel_stats.utilization(),
+            reserved: channel_stats.reserved,
+            available: channel_stats.available(),
+            drain_ratio: channel_stats.drain_ratio(),
+            queued_p50: channel_stats.queue_depth.percentile(0.50),
+            queued_p95: channel_stats.queue_depth.percentile(0.95),
+            queued_p99: channel_stats.queue_depth.percentile(0.99),
+            avg_queued: channel_stats.queue_average.average(channel_stats.queued()),
+            dwell_time,
+            send_wait: channel_stats.send_wait.stats(),
+            send_timeout: channel_stats.send_timeout.stats(),
+            send_duration: channel_stats.send_duration.stats(),
+            recv_duration: channel_stats.recv_duration.stats(),
+            sample_rate: channel_stats.sample_rate,
+            active_receivers: channel_stats.active_receivers(),
+            active_senders: channel_stats.active_senders(),
+            total_senders_created: channel_stats.total_senders_created(),
+            first_sent_ns: channel_stats.first_sent_at.map(elapsed_ns),
+            last_message_ns: channel_stats.last_message_at.map(elapsed_ns),
+            idle_for_ms: channel_stats.idle_for_ms(),
+            sent_rate_1m: channel_stats.rates.sent_per_sec(),
+            received_rate_1m: channel_stats.rates.received_per_sec(),
+            sent_rate_avg: channel_stats.lifetime_rate(channel_stats.sent_count),
+            received_rate_avg: channel_stats.lifetime_rate(channel_stats.received_count),
+            instance_count: channel_stats.instance_count,
+            task_id: channel_stats.task_id,
+            runtime: channel_stats.runtime.clone(),
+            preview: channel_stats.preview.clone(),
+            cancelled: channel_stats.cancelled,
+            disabled: channel_stats.disabled,
         }
     }
 }
 
 impl From<&StreamStats> for SerializableStreamStats {
     fn from(stream_stats: &StreamStats) -> Self {
-        let label = resolve_label(
-            stream_stats.source,
-            stream_stats.label.as_deref(),
-            stream_stats.iter,
-        );
+        let label = stream_stats.display_label();
 
         Self {
             id: stream_stats.id,
@@ -290,6 +993,7 @@ impl From<&StreamStats> for SerializableStreamStats {
             label,
             has_custom_label: stream_stats.label.is_some(),
             state: stream_stats.state,
+            state_code: stream_stats.state.code(),
             items_yielded: stream_stats.items_yielded,
             type_name: stream_stats.type_name.to_string(),
             type_size: stream_stats.type_size,
@@ -312,20 +1016,91 @@ impl ChannelStats {
             id,
             source,
             label,
+            generated_label: generated_label(source),
             channel_type,
-            state: ChannelState::default(),
+            state: match channel_type {
+                ChannelType::Oneshot => ChannelState::Created,
+                _ => ChannelState::Active,
+            },
             sent_count: 0,
             received_count: 0,
+            sent_bytes: 0,
+            received_bytes: 0,
+            peak_queued_bytes: 0,
+            full_events: 0,
+            send_errors: 0,
+            reserved: 0,
+            pending_unsent: (0, 0),
             type_name,
             type_size,
             sent_logs: VecDeque::new(),
             received_logs: VecDeque::new(),
             iter,
+            sample_rate: 1,
+            multi_consumer: false,
+            task_id: None,
+            runtime: None,
+            preview_enabled: false,
+            log_messages: true,
+            preview: None,
+            receivers_dropped: false,
+            cancelled: false,
+            disabled: false,
+            instance_count: 1,
+            senders: HashMap::new(),
+            closed_instances: HashSet::new(),
+            dropped_receiver_instances: HashSet::new(),
+            finished_instances: 0,
+            created_at: Instant::now(),
+            first_sent_at: None,
+            last_message_at: None,
+            queue_depth: Histogram::new(),
+            queue_average: queue_average::QueueAverage::new(),
+            queued_trend: leaks::QueueTrend::default(),
+            rates: rates::DecayingRates::default(),
+            #[cfg(feature = "statsd")]
+            reset_received: 0,
+            #[cfg(feature = "dwell-time")]
+            dwell: dwell_time::DwellTracker::default(),
+            send_wait: send_wait::SendWaitHistogram::default(),
+            send_timeout: send_timeout::SendTimeoutTracker::default(),
+            send_duration: op_duration::OpDurationTracker::default(),
+            recv_duration: op_duration::OpDurationTracker::default(),
+        }
+    }
+
+    /// Zero the counters and peaks. Messages still in the channel stay counted as sent,
+    /// so the queue depth keeps matching the channel.
+    fn reset(&mut self) {
+        #[cfg(feature = "statsd")]
+        {
+            self.reset_received += self.received_count;
         }
+        self.sent_count = self.sent_count.saturating_sub(self.received_count);
+        self.sent_bytes = self.sent_bytes.saturating_sub(self.received_bytes);
+        self.received_count = 0;
+        self.received_bytes = 0;
+        self.peak_queued_bytes = self.queued_bytes();
+        self.full_events = 0;
+        self.send_errors = 0;
+        self.sent_logs.clear();
+        self.received_logs.clear();
+        self.first_sent_at = None;
+        self.queue_depth = Histogram::new();
+        self.queue_average = queue_average::QueueAverage::new();
+        self.queued_trend = leaks::QueueTrend::default();
+        self.rates = rates::DecayingRates::starting_at(self.sent_count, self.received_count);
+        #[cfg(feature = "dwell-time")]
+        self.dwell.reset();
+        self.send_wait = send_wait::SendWaitHistogram::default();
+        self.send_timeout = send_timeout::SendTimeoutTracker::default();
+        self.send_duration = op_duration::OpDurationTracker::default();
+        self.recv_duration = op_duration::OpDurationTracker::default();
     }
 
     fn update_state(&mut self) {
-        if self.state == ChannelState::Closed || self.state == ChannelState::Notified {
+        // Oneshot states follow the lifecycle events instead of the queue
+        if self.state == ChannelState::Closed || self.channel_type == ChannelType::Oneshot {
             return;
         }
 
@@ -337,6 +1112,10 @@ impl ChannelStats {
         };
 
         if is_full {
+            let is_bounded = matches!(self.channel_type, ChannelType::Bounded(_));
+            if is_bounded && self.state != ChannelState::Full {
+                self.full_events += 1;
+            }
             self.state = ChannelState::Full;
         } else {
             self.state = ChannelState::Active;
@@ -345,6 +1124,11 @@ impl ChannelStats {
 }
 
 impl StreamStats {
+    /// Custom label, or the generated one, numbered for streams sharing a source.
+    pub(crate) fn display_label(&self) -> String {
+        resolve_label(&self.generated_label, self.label.as_deref(), self.iter)
+    }
+
     fn new(
         id: u64,
         source: &'static str,
@@ -357,6 +1141,7 @@ impl StreamStats {
             id,
             source,
             label,
+            generated_label: generated_label(source),
             state: ChannelState::Active,
             items_yielded: 0,
             type_name,
@@ -367,184 +1152,1109 @@ impl StreamStats {
     }
 }
 
-/// Events sent to the background channel statistics collection thread.
-#[derive(Debug)]
-pub(crate) enum ChannelEvent {
-    Created {
-        id: u64,
-        source: &'static str,
-        display_label: Option<String>,
-        channel_type: ChannelType,
-        type_name: &'static str,
-        type_size: usize,
-    },
-    MessageSent {
-        id: u64,
-        log: Option<String>,
-        timestamp: Instant,
-    },
-    MessageReceived {
-        id: u64,
-        timestamp: Instant,
-    },
-    Closed {
-        id: u64,
-    },
-    #[allow(dead_code)]
-    Notified {
-        id: u64,
-    },
+/// Tokio task and runtime a channel was created in.
+#[derive(Debug)]
+pub(crate) struct ChannelOrigin {
+    pub(crate) task_id: Option<u64>,
+    pub(crate) runtime: Option<RuntimeInfo>,
+}
+
+/// Events sent to the background channel statistics collection thread.
+#[derive(Debug)]
+pub(crate) enum ChannelEvent {
+    Created {
+        id: u64,
+        source: &'static str,
+        display_label: Option<String>,
+        channel_type: ChannelType,
+        type_name: &'static str,
+        type_size: usize,
+        sample_rate: u64,
+        /// Whether the receiver can be cloned, so the number of receivers is unknown.
+        multi_consumer: bool,
+        /// Boxed to keep the events that are sent for every message small.
+        origin: Box<ChannelOrigin>,
+    },
+    MessageSent {
+        id: u64,
+        log: Option<String>,
+        /// Size from a registered sizer, `None` means `type_size`.
+        size: Option<u64>,
+        /// How long the forwarder waited to pass the message on, bounded channels only.
+        send_wait: Option<Duration>,
+        /// How long the send to the original channel took, with the `op-durations` feature.
+        op_duration: Option<Duration>,
+        timestamp: Instant,
+    },
+    MessageReceived {
+        id: u64,
+        size: Option<u64>,
+        /// How long handing the message to the outer receiver took, with the
+        /// `op-durations` feature.
+        op_duration: Option<Duration>,
+        timestamp: Instant,
+    },
+    Closed {
+        id: u64,
+    },
+    /// The number of slots held by outstanding permits changed.
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    Reserved {
+        id: u64,
+        reserved: u64,
+    },
+    /// A send made with [`SendTimeoutExt::instrumented_send_timeout`] completed.
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    TimedSend {
+        id: u64,
+        timeout: Duration,
+        waited: Duration,
+        timed_out: bool,
+    },
+    /// A message was dropped by the proxies because the receiving side is gone.
+    SendFailed {
+        id: u64,
+        /// Whether the message was already reported as sent, which is then taken back.
+        reported: bool,
+        size: Option<u64>,
+    },
+    /// All receiving ends have been dropped.
+    ReceiverDropped {
+        id: u64,
+    },
+    /// Both forwarders have finished, so the channel can't carry messages anymore.
+    Finished {
+        id: u64,
+    },
+    Relabeled {
+        id: u64,
+        label: String,
+    },
+    /// Keep a preview of the latest message, requested with `preview = true`.
+    PreviewEnabled {
+        id: u64,
+        /// Whether `log = true` was given too, so logs keep the message text.
+        log_messages: bool,
+    },
+    /// Number of live senders of an instance and how many ever existed.
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    Senders {
+        id: u64,
+        active: u64,
+        created: u64,
+    },
+    /// A oneshot channel moved through its lifecycle, see [`ChannelState`].
+    #[cfg_attr(not(any(feature = "tokio", feature = "futures")), allow(dead_code))]
+    OneshotState {
+        id: u64,
+        state: ChannelState,
+    },
+    /// Several events buffered by a forwarder, applied under a single lock acquisition.
+    Batch(Vec<ChannelEvent>),
+    /// Acknowledged by the collector once all previously sent events have been applied.
+    Flush(CbSender<()>),
+    /// Stops the collector thread after all previously sent events have been applied.
+    Shutdown,
+}
+
+/// Events sent to the background stream statistics collection thread.
+#[derive(Debug)]
+pub(crate) enum StreamEvent {
+    Created {
+        id: u64,
+        source: &'static str,
+        display_label: Option<String>,
+        type_name: &'static str,
+        type_size: usize,
+    },
+    Yielded {
+        id: u64,
+        log: Option<String>,
+        timestamp: Instant,
+    },
+    Completed {
+        id: u64,
+    },
+    /// Stops the collector thread after all previously sent events have been applied.
+    Shutdown,
+}
+
+type ChannelStatsState = (
+    CbSender<ChannelEvent>,
+    Arc<RwLock<HashMap<u64, ChannelStats>>>,
+);
+type StreamStatsState = (
+    CbSender<StreamEvent>,
+    Arc<RwLock<HashMap<u64, StreamStats>>>,
+);
+
+static CHANNELS_STATE: OnceLock<ChannelStatsState> = OnceLock::new();
+
+static STREAMS_STATE: OnceLock<StreamStatsState> = OnceLock::new();
+
+static START_TIME: OnceLock<Instant> = OnceLock::new();
+
+/// Start of the HTTP server, or when the axum router was built.
+static SERVER_START_TIME: OnceLock<Instant> = OnceLock::new();
+
+/// Seconds since the metrics server started, or since the first instrumented channel or
+/// stream while it didn't.
+fn runtime_seconds() -> f64 {
+    SERVER_START_TIME
+        .get()
+        .or(START_TIME.get())
+        .map_or(0.0, |start| start.elapsed().as_secs_f64())
+}
+
+/// Lag of the most recently applied message event, see [`HealthJson::collector_lag_ms`].
+static COLLECTOR_LAG_NS: AtomicU64 = AtomicU64::new(0);
+
+fn record_collector_lag(timestamp: Instant) {
+    COLLECTOR_LAG_NS.store(timestamp.elapsed().as_nanos() as u64, Ordering::Relaxed);
+}
+
+/// Set with [`ChannelsGuardBuilder::duplicate_channels`].
+static AGGREGATE_DUPLICATES: AtomicBool = AtomicBool::new(false);
+
+/// Ids of aggregated channels mapped to the id of the entry they were merged into.
+static CHANNEL_ALIASES: LazyLock<Mutex<HashMap<u64, u64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn set_duplicate_channels(mode: DuplicateChannels) {
+    AGGREGATE_DUPLICATES.store(mode == DuplicateChannels::Aggregate, Ordering::Relaxed);
+}
+
+/// Groups set with [`InstrumentBuilder::group`], keyed by the source of their channels.
+static SOURCE_GROUPS: LazyLock<Mutex<HashMap<&'static str, &'static str>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Set once a channel joins a group, so aliases are resolved outside of aggregate mode too.
+static GROUPS_USED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_source_group(source: &'static str, group: &'static str) {
+    SOURCE_GROUPS.lock().unwrap().insert(source, group);
+    GROUPS_USED.store(true, Ordering::Relaxed);
+}
+
+fn source_group(source: &str) -> Option<&'static str> {
+    if !GROUPS_USED.load(Ordering::Relaxed) {
+        return None;
+    }
+    SOURCE_GROUPS.lock().unwrap().get(source).copied()
+}
+
+/// Id of the entry collecting the statistics of channel `id`.
+fn resolve_channel_id(id: u64) -> u64 {
+    if !AGGREGATE_DUPLICATES.load(Ordering::Relaxed) && !GROUPS_USED.load(Ordering::Relaxed) {
+        return id;
+    }
+    CHANNEL_ALIASES
+        .lock()
+        .unwrap()
+        .get(&id)
+        .copied()
+        .unwrap_or(id)
+}
+
+/// Switches of the live channel instances, off while a channel is disabled with
+/// [`disable_channel`].
+static CHANNEL_SWITCHES: LazyLock<Mutex<HashMap<u64, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Switch checked by the forwarders of channel `id` before reporting a message.
+pub(crate) fn channel_switch(id: u64) -> Arc<AtomicBool> {
+    let switch = Arc::new(AtomicBool::new(true));
+    CHANNEL_SWITCHES
+        .lock()
+        .unwrap()
+        .insert(id, Arc::clone(&switch));
+    switch
+}
+
+/// Set with [`ChannelsGuardBuilder::deregister_closed`].
+static DEREGISTER_CLOSED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_deregister_closed(enabled: bool) {
+    DEREGISTER_CLOSED.store(enabled, Ordering::Relaxed);
+}
+
+/// Set with [`ChannelsGuardBuilder::strict_labels`].
+static STRICT_LABELS: AtomicBool = AtomicBool::new(false);
+
+/// Custom labels mapped to the source of the channel that registered them first.
+static CUSTOM_LABELS: LazyLock<Mutex<HashMap<String, &'static str>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn set_strict_labels(enabled: bool) {
+    STRICT_LABELS.store(enabled, Ordering::Relaxed);
+}
+
+/// In strict mode, report a custom label already used by a channel from another source.
+/// Panics in debug builds and logs a warning otherwise.
+pub(crate) fn check_label(source: &'static str, label: Option<&str>) {
+    if !STRICT_LABELS.load(Ordering::Relaxed) {
+        return;
+    }
+    let Some(label) = label else {
+        return;
+    };
+    let first_source = *CUSTOM_LABELS
+        .lock()
+        .unwrap()
+        .entry(label.to_string())
+        .or_insert(source);
+    // Channels created in a loop share both the source and the label
+    if first_source == source {
+        return;
+    }
+    let message = format!(
+        "Channel label \"{label}\" at {source} is already used by the channel at {first_source}"
+    );
+    if cfg!(debug_assertions) {
+        panic!("{message}");
+    }
+    eprintln!("Warning: {message}");
+}
+
+static CHANNEL_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Id of the channel most recently instrumented on this thread.
+    static LAST_CHANNEL_ID: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Allocate the id of a new channel.
+pub(crate) fn next_channel_id() -> u64 {
+    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    LAST_CHANNEL_ID.with(|last| last.set(Some(id)));
+    id
+}
+
+/// Id of the channel most recently instrumented on the current thread, the `id` reported
+/// by the HTTP API and the JSON output. Read it right after `channel!` to refer to the
+/// channel later, e.g. in [`set_label`].
+///
+/// # Examples
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// let (tx, rx) = channels_console::channel!((tx, rx));
+/// let id = channels_console::last_channel_id();
+/// assert!(id.is_some());
+/// # drop((tx, rx));
+/// ```
+pub fn last_channel_id() -> Option<u64> {
+    LAST_CHANNEL_ID.with(Cell::get)
+}
+
+/// Change the label of channel `id`, for channels whose identity is only known after
+/// they were created, e.g. a connection's peer address.
+///
+/// The new label shows up in the next HTTP response and TUI refresh. Unknown ids are
+/// ignored. With [`ChannelsGuardBuilder::strict_labels`], the new label is checked and
+/// registered like the one given to `channel!`.
+///
+/// # Examples
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "connection");
+/// let id = channels_console::last_channel_id().unwrap();
+///
+/// channels_console::set_label(id, "connection-10.0.0.7:5432");
+/// # drop((tx, rx));
+/// ```
+pub fn set_label(id: u64, label: impl Into<String>) {
+    let label = label.into();
+    if let Some((stats_tx, stats_map)) = CHANNELS_STATE.get() {
+        // Checked here rather than on the collector thread, so a panic hits the caller
+        if STRICT_LABELS.load(Ordering::Relaxed) {
+            flush_channel_stats();
+            let source = stats_map
+                .read()
+                .unwrap()
+                .get(&resolve_channel_id(id))
+                .map(|stats| stats.source);
+            if let Some(source) = source {
+                check_label(source, Some(&label));
+            }
+        }
+        let _ = stats_tx.send(ChannelEvent::Relabeled { id, label });
+    }
+}
+
+/// Maximum number of characters of a message preview.
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// Turn on the preview of the channel the `channel!` macro just instrumented.
+#[doc(hidden)]
+pub fn __enable_preview(log_messages: bool) {
+    let (Some(id), Some((stats_tx, _))) = (last_channel_id(), CHANNELS_STATE.get()) else {
+        return;
+    };
+    let _ = stats_tx.send(ChannelEvent::PreviewEnabled { id, log_messages });
+}
+
+/// Cut a message preview to [`PREVIEW_MAX_CHARS`], marking the cut with an ellipsis.
+fn truncate_preview(message: &str) -> String {
+    match message.char_indices().nth(PREVIEW_MAX_CHARS) {
+        Some((end, _)) => format!("{}…", &message[..end]),
+        None => message.to_string(),
+    }
+}
+
+pub(crate) static STREAM_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+const DEFAULT_LOG_LIMIT: usize = 50;
+
+/// Maximum number of log entries kept per channel/stream direction.
+/// Configurable with the `CHANNELS_CONSOLE_LOG_LIMIT` environment variable.
+static LOG_LIMIT: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("CHANNELS_CONSOLE_LOG_LIMIT")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_LOG_LIMIT)
+});
+
+fn get_log_limit() -> usize {
+    *LOG_LIMIT
+}
+
+/// How often the collector runs periodic work such as flushing partially filled event
+/// buffers, alert checks, leak sampling and StatsD pushes.
+const COLLECTOR_TICK: Duration = Duration::from_millis(100);
+
+/// Apply a single event to the channel statistics map.
+fn apply_channel_event(stats: &mut HashMap<u64, ChannelStats>, event: ChannelEvent) {
+    match event {
+        ChannelEvent::Created {
+            id,
+            source,
+            display_label,
+            channel_type,
+            type_name,
+            type_size,
+            sample_rate,
+            multi_consumer,
+            origin,
+        } => {
+            // Grouped channels always share an entry, others only in aggregate mode
+            let group = source_group(source);
+            if group.is_some() || AGGREGATE_DUPLICATES.load(Ordering::Relaxed) {
+                let existing = stats.values_mut().find(|s| match group {
+                    Some(group) => source_group(s.source) == Some(group),
+                    None => s.source == source && s.label == display_label,
+                });
+                if let Some(channel_stats) = existing {
+                    channel_stats.instance_count += 1;
+                    if channel_stats.state.is_closed() {
+                        channel_stats.state = match channel_stats.channel_type {
+                            ChannelType::Oneshot => ChannelState::Created,
+                            _ => ChannelState::Active,
+                        };
+                    }
+                    channel_stats.receivers_dropped = false;
+                    if channel_stats.disabled {
+                        if let Some(switch) = CHANNEL_SWITCHES.lock().unwrap().get(&id) {
+                            switch.store(false, Ordering::Relaxed);
+                        }
+                    }
+                    CHANNEL_ALIASES.lock().unwrap().insert(id, channel_stats.id);
+                    return;
+                }
+            }
+
+            register_source_path(source);
+
+            // Number items with the same source location, continuing after removed ones
+            let iter = stats
+                .values()
+                .filter(|s| s.source == source)
+                .map(|s| s.iter + 1)
+                .max()
+                .unwrap_or(0);
+
+            let mut channel_stats = ChannelStats::new(
+                id,
+                source,
+                display_label,
+                channel_type,
+                type_name,
+                type_size,
+                iter,
+            );
+            channel_stats.sample_rate = sample_rate;
+            channel_stats.multi_consumer = multi_consumer;
+            channel_stats.task_id = origin.task_id;
+            channel_stats.runtime = origin.runtime;
+            stats.insert(id, channel_stats);
+        }
+        ChannelEvent::MessageSent {
+            id,
+            log,
+            size,
+            send_wait,
+            op_duration,
+            timestamp,
+        } => {
+            record_collector_lag(timestamp);
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                let queued = channel_stats.queued();
+                channel_stats.queue_average.advance(queued, timestamp);
+                channel_stats.sent_count += channel_stats.sample_rate;
+                channel_stats.first_sent_at.get_or_insert(timestamp);
+                channel_stats.last_message_at = Some(timestamp);
+                channel_stats.sent_bytes +=
+                    size.unwrap_or(channel_stats.type_size as u64) * channel_stats.sample_rate;
+                if channel_stats.pending_unsent.0 > 0 {
+                    let (count, bytes) = std::mem::take(&mut channel_stats.pending_unsent);
+                    channel_stats.sent_count = channel_stats.sent_count.saturating_sub(count);
+                    channel_stats.sent_bytes = channel_stats.sent_bytes.saturating_sub(bytes);
+                }
+                if let Some(wait) = send_wait {
+                    channel_stats
+                        .send_wait
+                        .record(wait, channel_stats.sample_rate);
+                }
+                if let Some(duration) = op_duration {
+                    channel_stats
+                        .send_duration
+                        .record(duration, channel_stats.sample_rate);
+                }
+                channel_stats.update_state();
+                channel_stats.queue_depth.record(channel_stats.queued());
+                channel_stats.peak_queued_bytes = channel_stats
+                    .peak_queued_bytes
+                    .max(channel_stats.queued_bytes());
+                #[cfg(feature = "dwell-time")]
+                {
+                    channel_stats.dwell.on_sent(timestamp);
+                    let pending = channel_stats
+                        .sent_count
+                        .saturating_sub(channel_stats.received_count);
+                    channel_stats
+                        .dwell
+                        .trim(pending / channel_stats.sample_rate.max(1));
+                }
+
+                if channel_stats.preview_enabled {
+                    if let Some(message) = &log {
+                        channel_stats.preview = Some(truncate_preview(message));
+                    }
+                }
+                let log = log.filter(|_| channel_stats.log_messages);
+
+                let limit = get_log_limit();
+                if channel_stats.sent_logs.len() >= limit {
+                    channel_stats.sent_logs.pop_front();
+                }
+                channel_stats.sent_logs.push_back(LogEntry::new(
+                    channel_stats.sent_count,
+                    timestamp,
+                    log,
+                ));
+            }
+        }
+        ChannelEvent::MessageReceived {
+            id,
+            size,
+            op_duration,
+            timestamp,
+        } => {
+            record_collector_lag(timestamp);
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                let queued = channel_stats.queued();
+                channel_stats.queue_average.advance(queued, timestamp);
+                channel_stats.received_count += channel_stats.sample_rate;
+                if let Some(duration) = op_duration {
+                    channel_stats
+                        .recv_duration
+                        .record(duration, channel_stats.sample_rate);
+                }
+                channel_stats.last_message_at = Some(timestamp);
+                channel_stats.received_bytes +=
+                    size.unwrap_or(channel_stats.type_size as u64) * channel_stats.sample_rate;
+                channel_stats.update_state();
+                channel_stats.queue_depth.record(channel_stats.queued());
+                #[cfg(feature = "dwell-time")]
+                channel_stats.dwell.on_received(timestamp);
+
+                let limit = get_log_limit();
+                if channel_stats.received_logs.len() >= limit {
+                    channel_stats.received_logs.pop_front();
+                }
+                channel_stats.received_logs.push_back(LogEntry::new(
+                    channel_stats.received_count,
+                    timestamp,
+                    None,
+                ));
+            }
+        }
+        ChannelEvent::Closed { id } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.closed_instances.insert(id);
+                if channel_stats.closed_instances.len() as u64 >= channel_stats.instance_count {
+                    channel_stats.state = ChannelState::Closed;
+                }
+            }
+        }
+        ChannelEvent::Reserved { id, reserved } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.reserved = reserved;
+            }
+        }
+        ChannelEvent::TimedSend {
+            id,
+            timeout,
+            waited,
+            timed_out,
+        } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                if !channel_stats.disabled {
+                    channel_stats
+                        .send_timeout
+                        .record(timeout, waited, timed_out);
+                }
+            }
+        }
+        ChannelEvent::Senders {
+            id,
+            active,
+            created,
+        } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.senders.insert(id, (active, created));
+            }
+        }
+        ChannelEvent::SendFailed { id, reported, size } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.send_errors += 1;
+                #[cfg(feature = "dwell-time")]
+                channel_stats.dwell.resync();
+                if reported {
+                    let rate = channel_stats.sample_rate;
+                    let bytes = size.unwrap_or(channel_stats.type_size as u64) * rate;
+                    // The send may still sit in the send forwarder's buffer
+                    if channel_stats.sent_count >= rate {
+                        let queued = channel_stats.queued();
+                        channel_stats.queue_average.advance(queued, Instant::now());
+                        channel_stats.sent_count -= rate;
+                        channel_stats.sent_bytes = channel_stats.sent_bytes.saturating_sub(bytes);
+                    } else {
+                        channel_stats.pending_unsent.0 += rate;
+                        channel_stats.pending_unsent.1 += bytes;
+                    }
+                }
+            }
+        }
+        ChannelEvent::ReceiverDropped { id } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.dropped_receiver_instances.insert(id);
+                channel_stats.receivers_dropped = channel_stats.dropped_receiver_instances.len()
+                    as u64
+                    >= channel_stats.instance_count;
+            }
+        }
+        ChannelEvent::Finished { id } => {
+            let entry_id = resolve_channel_id(id);
+            // No more events follow for this instance
+            CHANNEL_ALIASES.lock().unwrap().remove(&id);
+            CHANNEL_SWITCHES.lock().unwrap().remove(&id);
+            if let Some(channel_stats) = stats.get_mut(&entry_id) {
+                channel_stats.finished_instances += 1;
+                if channel_stats.finished_instances >= channel_stats.instance_count
+                    && DEREGISTER_CLOSED.load(Ordering::Relaxed)
+                {
+                    stats.remove(&entry_id);
+                }
+            }
+        }
+        ChannelEvent::Relabeled { id, label } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.label = Some(label);
+            }
+        }
+        ChannelEvent::PreviewEnabled { id, log_messages } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                channel_stats.preview_enabled = true;
+                channel_stats.log_messages = log_messages;
+            }
+        }
+        ChannelEvent::OneshotState { id, state } => {
+            if let Some(channel_stats) = stats.get_mut(&resolve_channel_id(id)) {
+                // The forwarders race, so `Sent` may arrive after the value was received
+                if !channel_stats.state.is_closed() {
+                    channel_stats.cancelled |= state == ChannelState::ReceiverDropped;
+                    channel_stats.state = state;
+                }
+            }
+        }
+        ChannelEvent::Batch(events) => {
+            for event in events {
+                apply_channel_event(stats, event);
+            }
+        }
+        ChannelEvent::Flush(ack) => {
+            let _ = ack.send(());
+        }
+        // Handled by the collector loop
+        ChannelEvent::Shutdown => {}
+    }
+}
+
+/// Name of a background thread, overridable with the given environment variable.
+fn thread_name(env_var: &str, default: &str) -> String {
+    std::env::var(env_var)
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Initialize the channel statistics collection system (called on first instrumented channel).
+/// Returns a reference to the global state.
+pub(crate) fn init_channels_state() -> &'static ChannelStatsState {
+    CHANNELS_STATE.get_or_init(|| {
+        START_TIME.get_or_init(Instant::now);
+
+        let (tx, rx) = unbounded::<ChannelEvent>();
+        let stats_map = Arc::new(RwLock::new(HashMap::<u64, ChannelStats>::new()));
+        let stats_map_clone = Arc::clone(&stats_map);
+
+        let handle = std::thread::Builder::new()
+            .name(thread_name(
+                "CHANNELS_CONSOLE_COLLECTOR_THREAD_NAME",
+                "channel-stats-collector",
+            ))
+            .spawn(move || {
+                let mut last_tick = Instant::now();
+                let mut leak_detector = leaks::LeakDetector::new();
+                let mut rate_meter = rates::RateMeter::new();
+                loop {
+                    match rx.recv_timeout(COLLECTOR_TICK) {
+                        Ok(ChannelEvent::Shutdown) => break,
+                        Ok(event) => {
+                            let mut stats = stats_map_clone.write().unwrap();
+                            apply_channel_event(&mut stats, event);
+                            #[cfg(feature = "tokio")]
+                            subscribe::mark_changed();
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+
+                    if last_tick.elapsed() >= COLLECTOR_TICK {
+                        last_tick = Instant::now();
+                        wrappers::flush_event_buffers();
+                        {
+                            let mut stats = stats_map_clone.write().unwrap();
+                            leak_detector.on_tick(&mut stats);
+                            rate_meter.on_tick(&mut stats);
+                        }
+                        alerts::check_alerts(&stats_map_clone);
+                        #[cfg(feature = "statsd")]
+                        statsd::on_tick(&stats_map_clone);
+                        #[cfg(feature = "mmap")]
+                        mmap_export::on_tick(&stats_map_clone);
+                        #[cfg(feature = "tokio")]
+                        subscribe::on_tick(&stats_map_clone);
+                    }
+                }
+            })
+            .expect("Failed to spawn channel stats collector thread");
+        COLLECTOR_THREADS.lock().unwrap().push(handle);
+
+        init_metrics_server();
+
+        (tx, stats_map)
+    })
+}
+
+static METRICS_SERVER: Once = Once::new();
+
+static METRICS_SERVER_THREAD: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+/// Join handles of the running collector threads, taken by [`shutdown`].
+static COLLECTOR_THREADS: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Stop the metrics HTTP server and the background collector threads once they have
+/// applied all pending events.
+///
+/// Instrumented channels and streams keep working afterwards, but their statistics are
+/// no longer updated and the HTTP endpoints are gone. The last collected statistics remain available, so a
+/// [`ChannelsGuard`] dropped after `shutdown` still prints them. Calling `shutdown`
+/// more than once is a no-op.
+///
+/// # Examples
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// #[cfg(feature = "channels-console")]
+/// let (tx, rx) = channels_console::channel!((tx, rx));
+/// tx.send(1).unwrap();
+/// rx.recv().unwrap();
+///
+/// channels_console::shutdown();
+/// ```
+pub fn shutdown() {
+    flush_channel_stats();
+    if let Some((stats_tx, _)) = CHANNELS_STATE.get() {
+        let _ = stats_tx.send(ChannelEvent::Shutdown);
+    }
+    if let Some((stats_tx, _)) = STREAMS_STATE.get() {
+        let _ = stats_tx.send(StreamEvent::Shutdown);
+    }
+
+    let handles = std::mem::take(&mut *COLLECTOR_THREADS.lock().unwrap());
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    http_api::stop_metrics_server();
+    if let Some(handle) = METRICS_SERVER_THREAD.lock().unwrap().take() {
+        let _ = handle.join();
+    }
+}
+
+/// TCP port the metrics server is listening on.
+///
+/// Returns `None` until the server has bound, which happens in the background after the
+/// first channel or stream is instrumented, and after [`shutdown`]. A server listening on a
+/// Unix socket has no port.
+///
+/// # Examples
+///
+/// ```no_run
+/// if let Some(port) = channels_console::metrics_port() {
+///     println!("Channel statistics at http://127.0.0.1:{}/channels", port);
+/// }
+/// ```
+pub fn metrics_port() -> Option<u16> {
+    http_api::bound_port()
+}
+
+/// Current statistics of all instrumented channels and streams.
+///
+/// Events still buffered by the forwarders are applied first, like for the statistics
+/// printed by [`ChannelsGuard`]. Channels are ordered by label. To work with the
+/// collected values directly instead of their serializable form, use [`channel_views`].
+///
+/// # Examples
+///
+/// ```no_run
+/// let snapshot = channels_console::snapshot();
+/// for channel in &snapshot.channels {
+///     println!("{}: {} queued", channel.label, channel.queued);
+/// }
+/// ```
+pub fn snapshot() -> CombinedJson {
+    flush_channel_stats();
+    get_combined_json()
+}
+
+/// Serialize a [`snapshot`] of the statistics with any serde `serializer`.
+///
+/// Use it to export the statistics in formats like bincode, MessagePack or CBOR without
+/// a dedicated feature for each of them.
+///
+/// # Examples
+///
+/// ```
+/// let mut buffer = Vec::new();
+/// channels_console::write_stats(&mut serde_json::Serializer::new(&mut buffer)).unwrap();
+/// let stats: channels_console::CombinedJson = serde_json::from_slice(&buffer).unwrap();
+/// # let _ = stats;
+/// ```
+pub fn write_stats<S: serde::Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+    snapshot().serialize(serializer)
+}
+
+/// Current statistics of the channels matching `predicate`, ordered by label.
+///
+/// Like [`snapshot`], pending events are applied first. Channels are matched under the
+/// statistics lock instead of copying the statistics of all channels first.
+///
+/// # Examples
+///
+/// ```
+/// let backed_up = channels_console::channels_matching(|c| c.queued > 100);
+/// assert!(backed_up.is_empty(), "Backed up channels: {:?}", backed_up);
+/// ```
+pub fn channels_matching(
+    predicate: impl Fn(&SerializableChannelStats) -> bool,
+) -> Vec<SerializableChannelStats> {
+    flush_channel_stats();
+    let Some((_, stats_map)) = CHANNELS_STATE.get() else {
+        return Vec::new();
+    };
+
+    let stats = stats_map.read().unwrap();
+    let mut channels: Vec<&ChannelStats> = stats.values().collect();
+    channels.sort_by(|a, b| compare_channel_stats(a, b));
+    channels
+        .into_iter()
+        .map(SerializableChannelStats::from)
+        .filter(|channel| predicate(channel))
+        .collect()
+}
+
+/// Current statistics of all channels as [`ChannelView`]s, ordered by label.
+///
+/// Like [`snapshot`], pending events are applied first. The views give direct access to
+/// the collected values without going through the serialized form.
+///
+/// # Examples
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "jobs");
+/// tx.send(1).unwrap();
+///
+/// let busiest = channels_console::channel_views()
+///     .into_iter()
+///     .max_by(|a, b| a.sent_rate_1m().total_cmp(&b.sent_rate_1m()));
+/// assert!(busiest.is_some());
+/// # drop(rx);
+/// ```
+pub fn channel_views() -> Vec<ChannelView> {
+    flush_channel_stats();
+    get_sorted_channel_stats()
+        .into_iter()
+        .map(ChannelView::new)
+        .collect()
+}
+
+/// Zero the counters, byte totals and peaks of the channel with the given id or label,
+/// e.g. between the phases of a test. Returns whether a channel was found.
+///
+/// The channel stays registered with its type and label. Messages still queued are kept
+/// as sent, so `queued` is unchanged.
+///
+/// # Examples
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "phase");
+///
+/// assert!(channels_console::reset_channel("phase"));
+/// assert!(!channels_console::reset_channel("missing"));
+/// # drop((tx, rx));
+/// ```
+pub fn reset_channel(id_or_label: &str) -> bool {
+    reset_channels(|stats| matches_channel(stats, id_or_label))
+}
+
+/// Whether `id_or_label` is the id or the resolved label of the channel.
+fn matches_channel(stats: &ChannelStats, id_or_label: &str) -> bool {
+    id_or_label.parse::<u64>().ok() == Some(stats.id) || stats.display_label() == id_or_label
+}
+
+/// Zero the counters, byte totals and peaks of every channel, like [`reset_channel`].
+/// Returns whether any channel was found.
+pub fn reset_all() -> bool {
+    reset_channels(|_| true)
+}
+
+/// Stop counting the messages of the channel with the given id or label, e.g. to quiet a
+/// noisy channel during a debugging session without removing its instrumentation.
+/// Returns whether a channel was found.
+///
+/// The forwarders keep passing messages on but no longer report them, so the counts stay
+/// where they were and the channel is marked `disabled` in the output. Messages that
+/// were sent before and received while disabled are never counted as received, so the
+/// queue looks longer afterwards. Oneshot channels carry a single message and are always
+/// counted.
+///
+/// # Examples
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<i32>();
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "noisy");
+///
+/// assert!(channels_console::disable_channel("noisy"));
+/// tx.send(1).unwrap();
+/// rx.recv().unwrap();
+/// assert!(channels_console::enable_channel("noisy"));
+/// ```
+pub fn disable_channel(id_or_label: &str) -> bool {
+    set_channel_enabled(id_or_label, false)
+}
+
+/// Count the messages of a channel turned off with [`disable_channel`] again. Returns
+/// whether a channel was found. The queue-depth percentiles and dwell times start over,
+/// so the depths sampled before disabling don't mix with the skewed ones after.
+pub fn enable_channel(id_or_label: &str) -> bool {
+    set_channel_enabled(id_or_label, true)
+}
+
+fn set_channel_enabled(id_or_label: &str, enabled: bool) -> bool {
+    flush_channel_stats();
+    let Some((_, stats_map)) = CHANNELS_STATE.get() else {
+        return false;
+    };
+
+    let mut stats = stats_map.write().unwrap();
+    let entries: HashSet<u64> = stats
+        .values_mut()
+        .filter(|stats| matches_channel(stats, id_or_label))
+        .map(|stats| {
+            stats.disabled = !enabled;
+            #[cfg(feature = "dwell-time")]
+            stats.dwell.resync();
+            if enabled {
+                stats.queue_depth = Histogram::new();
+                #[cfg(feature = "dwell-time")]
+                stats.dwell.reset();
+            }
+            stats.id
+        })
+        .collect();
+    // Aggregated entries switch all of their instances
+    for (id, switch) in CHANNEL_SWITCHES.lock().unwrap().iter() {
+        if entries.contains(&resolve_channel_id(*id)) {
+            switch.store(enabled, Ordering::Relaxed);
+        }
+    }
+    !entries.is_empty()
+}
+
+/// Number of resets so far, see [`ChannelsJson::generation`].
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn reset_channels(matches: impl Fn(&ChannelStats) -> bool) -> bool {
+    flush_channel_stats();
+    let Some((_, stats_map)) = CHANNELS_STATE.get() else {
+        return false;
+    };
+
+    let mut channels = stats_map.write().unwrap();
+    let mut found = false;
+    for stats in channels.values_mut() {
+        if matches(stats) {
+            stats.reset();
+            found = true;
+        }
+    }
+    // Bumped under the lock, so no scrape sees the lower counts with the old generation
+    if found {
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+    found
 }
 
-/// Events sent to the background stream statistics collection thread.
-#[derive(Debug)]
-pub(crate) enum StreamEvent {
-    Created {
-        id: u64,
-        source: &'static str,
-        display_label: Option<String>,
-        type_name: &'static str,
-        type_size: usize,
-    },
-    Yielded {
-        id: u64,
-        log: Option<String>,
-        timestamp: Instant,
-    },
-    Completed {
-        id: u64,
-    },
+/// Unix socket path set with [`ChannelsGuardBuilder::metrics_unix_socket`].
+#[cfg(unix)]
+static METRICS_UNIX_SOCKET: OnceLock<std::path::PathBuf> = OnceLock::new();
+
+/// Make the metrics server listen on a Unix socket. Returns `false` if the server has
+/// already been started on TCP.
+#[cfg(unix)]
+pub(crate) fn set_metrics_unix_socket(path: std::path::PathBuf) -> bool {
+    !METRICS_SERVER.is_completed() && METRICS_UNIX_SOCKET.set(path).is_ok()
 }
 
-type ChannelStatsState = (
-    CbSender<ChannelEvent>,
-    Arc<RwLock<HashMap<u64, ChannelStats>>>,
-);
-type StreamStatsState = (
-    CbSender<StreamEvent>,
-    Arc<RwLock<HashMap<u64, StreamStats>>>,
-);
+/// Set with [`ChannelsGuardBuilder::metrics_server`], the embedded server isn't started.
+static METRICS_SERVER_DISABLED: AtomicBool = AtomicBool::new(false);
 
-static CHANNELS_STATE: OnceLock<ChannelStatsState> = OnceLock::new();
+/// Don't start the embedded metrics server. Returns `false` if it has already been started.
+pub(crate) fn disable_metrics_server() -> bool {
+    METRICS_SERVER_DISABLED.store(true, Ordering::Relaxed);
+    !METRICS_SERVER.is_completed()
+}
 
-static STREAMS_STATE: OnceLock<StreamStatsState> = OnceLock::new();
+static METRICS_PATH: OnceLock<String> = OnceLock::new();
 
-static START_TIME: OnceLock<Instant> = OnceLock::new();
+/// Serve the HTTP endpoints under `path`, e.g. `/internal` for `/internal/channels`.
+/// Returns `false` if the server has already been started.
+pub(crate) fn set_metrics_path(path: &str) -> bool {
+    !METRICS_SERVER.is_completed() && METRICS_PATH.set(normalize_metrics_path(path)).is_ok()
+}
 
-pub(crate) static CHANNEL_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+/// Prefix of all HTTP endpoints, empty unless set with
+/// [`ChannelsGuardBuilder::metrics_path`] or `CHANNELS_CONSOLE_METRICS_PATH`.
+pub(crate) fn metrics_path() -> &'static str {
+    METRICS_PATH.get_or_init(|| {
+        std::env::var("CHANNELS_CONSOLE_METRICS_PATH")
+            .map(|path| normalize_metrics_path(&path))
+            .unwrap_or_default()
+    })
+}
 
-pub(crate) static STREAM_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+/// A leading slash and no trailing one, so `internal/` becomes `/internal` and `/` none.
+fn normalize_metrics_path(path: &str) -> String {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("/{path}")
+    }
+}
 
-const DEFAULT_LOG_LIMIT: usize = 50;
+fn metrics_addr() -> MetricsAddr {
+    #[cfg(unix)]
+    if let Some(path) = METRICS_UNIX_SOCKET.get() {
+        return MetricsAddr::Unix(path.clone());
+    }
 
-fn get_log_limit() -> usize {
-    std::env::var("CHANNELS_CONSOLE_LOG_LIMIT")
+    // Check environment variable for custom port, default to 6770
+    let port = std::env::var("CHANNELS_CONSOLE_METRICS_PORT")
         .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(DEFAULT_LOG_LIMIT)
-}
+        .and_then(|p| p.parse::<u16>().ok())
+        .unwrap_or(6770);
 
-/// Initialize the channel statistics collection system (called on first instrumented channel).
-/// Returns a reference to the global state.
-pub(crate) fn init_channels_state() -> &'static ChannelStatsState {
-    CHANNELS_STATE.get_or_init(|| {
-        START_TIME.get_or_init(Instant::now);
+    let host = std::env::var("CHANNELS_CONSOLE_METRICS_HOST").ok();
+    if let Some(addrs) = host.and_then(|host| metrics_host_addrs(&host, port)) {
+        return MetricsAddr::Tcp(addrs);
+    }
 
-        let (tx, rx) = unbounded::<ChannelEvent>();
-        let stats_map = Arc::new(RwLock::new(HashMap::<u64, ChannelStats>::new()));
-        let stats_map_clone = Arc::clone(&stats_map);
+    // The IPv6 loopback is only tried when the IPv4 one can't be bound, e.g. on IPv6-only hosts
+    MetricsAddr::Tcp(vec![
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+    ])
+}
 
-        std::thread::Builder::new()
-            .name("channel-stats-collector".into())
-            .spawn(move || {
-                while let Ok(event) = rx.recv() {
-                    let mut stats = stats_map_clone.write().unwrap();
-                    match event {
-                        ChannelEvent::Created {
-                            id,
-                            source,
-                            display_label,
-                            channel_type,
-                            type_name,
-                            type_size,
-                        } => {
-                            // Count existing items with the same source location
-                            let iter = stats.values().filter(|s| s.source == source).count() as u32;
+/// Addresses to bind for `host`, an IP address, optionally in brackets like `[::1]`, or a
+/// host name. `None` with a warning if it can't be resolved.
+fn metrics_host_addrs(host: &str, port: u16) -> Option<Vec<SocketAddr>> {
+    let unbracketed = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+    if let Ok(ip) = unbracketed.parse::<IpAddr>() {
+        return Some(vec![SocketAddr::new(ip, port)]);
+    }
 
-                            stats.insert(
-                                id,
-                                ChannelStats::new(
-                                    id,
-                                    source,
-                                    display_label,
-                                    channel_type,
-                                    type_name,
-                                    type_size,
-                                    iter,
-                                ),
-                            );
-                        }
-                        ChannelEvent::MessageSent { id, log, timestamp } => {
-                            if let Some(channel_stats) = stats.get_mut(&id) {
-                                channel_stats.sent_count += 1;
-                                channel_stats.update_state();
+    match (unbracketed, port).to_socket_addrs() {
+        Ok(addrs) => Some(addrs.collect()),
+        Err(e) => {
+            eprintln!(
+                "Ignoring CHANNELS_CONSOLE_METRICS_HOST, cannot resolve {}: {}",
+                host, e
+            );
+            None
+        }
+    }
+}
 
-                                let limit = get_log_limit();
-                                if channel_stats.sent_logs.len() >= limit {
-                                    channel_stats.sent_logs.pop_front();
-                                }
-                                channel_stats.sent_logs.push_back(LogEntry::new(
-                                    channel_stats.sent_count,
-                                    timestamp,
-                                    log,
-                                ));
-                            }
-                        }
-                        ChannelEvent::MessageReceived { id, timestamp } => {
-                            if let Some(channel_stats) = stats.get_mut(&id) {
-                                channel_stats.received_count += 1;
-                                channel_stats.update_state();
+/// Spawn the metrics HTTP server in the background (only once, shared by channels and streams).
+fn init_metrics_server() {
+    METRICS_SERVER.call_once(|| {
+        if METRICS_SERVER_DISABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let addr = metrics_addr();
 
-                                let limit = get_log_limit();
-                                if channel_stats.received_logs.len() >= limit {
-                                    channel_stats.received_logs.pop_front();
-                                }
-                                channel_stats.received_logs.push_back(LogEntry::new(
-                                    channel_stats.received_count,
-                                    timestamp,
-                                    None,
-                                ));
-                            }
-                        }
-                        ChannelEvent::Closed { id } => {
-                            if let Some(channel_stats) = stats.get_mut(&id) {
-                                channel_stats.state = ChannelState::Closed;
-                            }
-                        }
-                        ChannelEvent::Notified { id } => {
-                            if let Some(channel_stats) = stats.get_mut(&id) {
-                                channel_stats.state = ChannelState::Notified;
-                            }
-                        }
-                    }
-                }
+        let handle = std::thread::Builder::new()
+            .name(thread_name(
+                "CHANNELS_CONSOLE_METRICS_THREAD_NAME",
+                "channel-metrics-server",
+            ))
+            .spawn(move || {
+                start_metrics_server(&addr);
             })
-            .expect("Failed to spawn channel-stats-collector thread");
-
-        // Spawn the metrics HTTP server in the background
-        // Check environment variable for custom port, default to 6770
-        let port = std::env::var("CHANNELS_CONSOLE_METRICS_PORT")
-            .ok()
-            .and_then(|p| p.parse::<u16>().ok())
-            .unwrap_or(6770);
-        let addr = format!("127.0.0.1:{}", port);
-
-        std::thread::spawn(move || {
-            start_metrics_server(&addr);
-        });
-
-        (tx, stats_map)
-    })
+            .expect("Failed to spawn metrics server thread");
+        *METRICS_SERVER_THREAD.lock().unwrap() = Some(handle);
+    });
 }
 
 /// Initialize the stream statistics collection system (called on first instrumented stream).
@@ -557,8 +2267,11 @@ pub(crate) fn init_streams_state() -> &'static StreamStatsState {
         let stats_map = Arc::new(RwLock::new(HashMap::<u64, StreamStats>::new()));
         let stats_map_clone = Arc::clone(&stats_map);
 
-        std::thread::Builder::new()
-            .name("stream-stats-collector".into())
+        let handle = std::thread::Builder::new()
+            .name(thread_name(
+                "CHANNELS_CONSOLE_STREAM_COLLECTOR_THREAD_NAME",
+                "stream-stats-collector",
+            ))
             .spawn(move || {
                 while let Ok(event) = rx.recv() {
                     let mut stats = stats_map_clone.write().unwrap();
@@ -570,6 +2283,8 @@ pub(crate) fn init_streams_state() -> &'static StreamStatsState {
                             type_name,
                             type_size,
                         } => {
+                            register_source_path(source);
+
                             // Count existing items with the same source location
                             let iter = stats.values().filter(|s| s.source == source).count() as u32;
 
@@ -605,44 +2320,107 @@ pub(crate) fn init_streams_state() -> &'static StreamStatsState {
                                 stream_stats.state = ChannelState::Closed;
                             }
                         }
+                        StreamEvent::Shutdown => break,
                     }
                 }
             })
-            .expect("Failed to spawn stream-stats-collector thread");
+            .expect("Failed to spawn stream stats collector thread");
+        COLLECTOR_THREADS.lock().unwrap().push(handle);
+
+        init_metrics_server();
 
         (tx, stats_map)
     })
 }
 
-fn resolve_label(id: &'static str, provided: Option<&str>, iter: u32) -> String {
-    let base_label = if let Some(l) = provided {
-        l.to_string()
-    } else if let Some(pos) = id.rfind(':') {
-        let (path, line_part) = id.split_at(pos);
+fn resolve_label(generated: &str, provided: Option<&str>, iter: u32) -> String {
+    let base_label = provided.unwrap_or(generated);
+
+    if iter > 0 {
+        format!("{}-{}", base_label, iter + 1)
+    } else {
+        base_label.to_string()
+    }
+}
+
+/// Label of a channel or stream without a custom one, made from its id. Computed once on
+/// creation, from the files instrumented so far.
+fn generated_label(id: &'static str) -> String {
+    let (module, location) = split_source_id(id);
+    let location = if let Some(pos) = location.rfind(':') {
+        let (path, line_part) = location.split_at(pos);
         let line = &line_part[1..];
         format!("{}:{}", extract_filename(path), line)
     } else {
-        extract_filename(id)
+        extract_filename(location)
     };
+    match module {
+        Some(module) => format!("{module}@{location}"),
+        None => location,
+    }
+}
 
-    if iter > 0 {
-        format!("{}-{}", base_label, iter + 1)
-    } else {
-        base_label
+/// Split an id made by `channel!` or `stream!` into its module path and `file:line`.
+/// Ids set by hand, or made without the module path, have no module.
+fn split_source_id(id: &str) -> (Option<&str>, &str) {
+    match id.split_once('@') {
+        Some((module, location))
+            if !module.is_empty()
+                && module
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == ':') =>
+        {
+            (Some(module), location)
+        }
+        _ => (None, id),
+    }
+}
+
+const DEFAULT_LABEL_PATH_COMPONENTS: usize = 2;
+
+/// Number of trailing path components kept in generated labels, at least one.
+/// Configurable with the `CHANNELS_CONSOLE_LABEL_PATH_COMPONENTS` environment variable.
+static LABEL_PATH_COMPONENTS: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("CHANNELS_CONSOLE_LABEL_PATH_COMPONENTS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|&components| components > 0)
+        .unwrap_or(DEFAULT_LABEL_PATH_COMPONENTS)
+});
+
+/// Files channels and streams were instrumented in, so generated labels of different
+/// files never look alike.
+static SOURCE_PATHS: LazyLock<RwLock<HashSet<&'static str>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+fn register_source_path(id: &'static str) {
+    let (_, location) = split_source_id(id);
+    let path = location.rfind(':').map_or(location, |pos| &location[..pos]);
+    if !SOURCE_PATHS.read().unwrap().contains(path) {
+        SOURCE_PATHS.write().unwrap().insert(path);
     }
 }
 
+/// Trailing components of `path`, as many as configured, or more if another
+/// instrumented file ends with the same ones.
 fn extract_filename(path: &str) -> String {
     let components: Vec<&str> = path.split('/').collect();
-    if components.len() >= 2 {
-        format!(
-            "{}/{}",
-            components[components.len() - 2],
-            components[components.len() - 1]
-        )
-    } else {
-        path.to_string()
+    let paths = SOURCE_PATHS.read().unwrap();
+    let mut keep = (*LABEL_PATH_COMPONENTS).min(components.len());
+    while keep < components.len() {
+        let tail = &components[components.len() - keep..];
+        let collides = paths.iter().any(|other| {
+            *other != path && {
+                let other: Vec<&str> = other.split('/').collect();
+                other.len() >= keep && other[other.len() - keep..] == *tail
+            }
+        });
+        if !collides {
+            break;
+        }
+        keep += 1;
     }
+    components[components.len() - keep..].join("/")
 }
 
 /// Format bytes into human-readable units (B, KB, MB, GB, TB).
@@ -678,6 +2456,7 @@ pub trait Instrument {
         source: &'static str,
         label: Option<String>,
         capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output;
 }
 
@@ -692,6 +2471,7 @@ pub trait InstrumentLog {
         source: &'static str,
         label: Option<String>,
         capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output;
 }
 
@@ -740,7 +2520,6 @@ where
 
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "tokio", feature = "futures"))] {
-        use std::sync::LazyLock;
         pub static RT: LazyLock<tokio::runtime::Runtime> = LazyLock::new(|| {
             tokio::runtime::Builder::new_multi_thread()
                 .enable_time()
@@ -750,9 +2529,33 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Id of a channel or stream instrumented at the call site: its module path, file and
+/// line, e.g. `app::worker@src/worker.rs:25`. Only the file and line with the
+/// `file-line-ids` feature.
+#[doc(hidden)]
+#[cfg(not(feature = "file-line-ids"))]
+#[macro_export]
+macro_rules! __source_id {
+    () => {
+        concat!(module_path!(), "@", file!(), ":", line!())
+    };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "file-line-ids")]
+#[macro_export]
+macro_rules! __source_id {
+    () => {
+        concat!(file!(), ":", line!())
+    };
+}
+
 /// Instrument a channel creation to wrap it with debugging proxies.
 /// Currently only supports bounded, unbounded and oneshot channels.
 ///
+/// The returned sender and receiver are the native types. Clones of the sender feed the
+/// same proxy, so messages from every clone are counted in the channel's one entry.
+///
 /// # Examples
 ///
 /// ```
@@ -773,130 +2576,257 @@ cfg_if::cfg_if! {
 /// }
 /// ```
 ///
+/// Labels accept any expression implementing `ToString`, including values computed at runtime:
+///
+/// ```
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     for shard in 0..2 {
+///         let (tx, rx) = mpsc::channel::<u32>(10);
+///         #[cfg(feature = "channels-console")]
+///         let (tx, rx) = channels_console::channel!((tx, rx), label = format!("shard-{shard}"));
+///         tx.send(shard).await.unwrap();
+///         drop(rx);
+///     }
+/// }
+/// ```
+///
+/// For very hot channels, `sample = N` reports only one in `N` messages to the collector
+/// and scales the counts accordingly. Counts, queue sizes and logs become approximate:
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<u64>();
+/// #[cfg(feature = "channels-console")]
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "hot", sample = 100);
+/// tx.send(1).unwrap();
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// ```
+///
+/// With `preview = true`, the `Debug` representation of the most recent message is kept,
+/// truncated, as the channel's `preview`. Like `log = true` it requires `T: Debug` and
+/// formats every observed message, so it's best left off for hot channels:
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<(u32, &str)>();
+/// #[cfg(feature = "channels-console")]
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "jobs", preview = true);
+/// tx.send((1, "resize")).unwrap();
+/// assert_eq!(rx.recv().unwrap(), (1, "resize"));
+/// ```
+///
+/// A channel is identified by its module path, file and line, so editing the code above it
+/// changes its identity. `id = "..."` pins a constant string instead, which also becomes
+/// the default label, to keep exported series stable across code edits:
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::channel::<u32>();
+/// #[cfg(feature = "channels-console")]
+/// let (tx, rx) = channels_console::channel!((tx, rx), id = "ingest-queue");
+/// tx.send(1).unwrap();
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// ```
+///
 /// See the `channel!` macro documentation for full usage details.
+#[cfg(not(feature = "disabled"))]
 #[macro_export]
 macro_rules! channel {
-    ($expr:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
-        $crate::Instrument::instrument($expr, CHANNEL_ID, None, None)
+    // Internal rules, `$sample` and `$source` are the already extracted `sample` and `id`
+    // options
+    (@sample $sample:expr; $source:expr; $expr:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
+        $crate::Instrument::instrument($expr, CHANNEL_ID, None, None, $sample)
     }};
 
-    ($expr:expr, label = $label:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
-        $crate::Instrument::instrument($expr, CHANNEL_ID, Some($label.to_string()), None)
+    (@sample $sample:expr; $source:expr; $expr:expr, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
+        $crate::Instrument::instrument($expr, CHANNEL_ID, Some($label.to_string()), None, $sample)
     }};
 
-    ($expr:expr, capacity = $capacity:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
-        $crate::Instrument::instrument($expr, CHANNEL_ID, None, Some($capacity))
+        $crate::Instrument::instrument($expr, CHANNEL_ID, None, Some($capacity), $sample)
     }};
 
-    ($expr:expr, label = $label:expr, capacity = $capacity:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, label = $label:expr, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
-        $crate::Instrument::instrument($expr, CHANNEL_ID, Some($label.to_string()), Some($capacity))
+        $crate::Instrument::instrument($expr, CHANNEL_ID, Some($label.to_string()), Some($capacity), $sample)
     }};
 
-    ($expr:expr, capacity = $capacity:expr, label = $label:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, capacity = $capacity:expr, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
-        $crate::Instrument::instrument($expr, CHANNEL_ID, Some($label.to_string()), Some($capacity))
+        $crate::Instrument::instrument($expr, CHANNEL_ID, Some($label.to_string()), Some($capacity), $sample)
     }};
 
     // Variants with log = true
-    ($expr:expr, log = true) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
-        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, None, None)
+    (@sample $sample:expr; $source:expr; $expr:expr, log = true) => {{
+        const CHANNEL_ID: &'static str = $source;
+        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, None, None, $sample)
     }};
 
-    ($expr:expr, label = $label:expr, log = true) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
-        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, Some($label.to_string()), None)
+    (@sample $sample:expr; $source:expr; $expr:expr, label = $label:expr, log = true) => {{
+        const CHANNEL_ID: &'static str = $source;
+        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, Some($label.to_string()), None, $sample)
     }};
 
-    ($expr:expr, log = true, label = $label:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
-        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, Some($label.to_string()), None)
+    (@sample $sample:expr; $source:expr; $expr:expr, log = true, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
+        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, Some($label.to_string()), None, $sample)
     }};
 
-    ($expr:expr, capacity = $capacity:expr, log = true) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, capacity = $capacity:expr, log = true) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
-        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, None, Some($capacity))
+        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, None, Some($capacity), $sample)
     }};
 
-    ($expr:expr, log = true, capacity = $capacity:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, log = true, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
-        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, None, Some($capacity))
+        $crate::InstrumentLog::instrument_log($expr, CHANNEL_ID, None, Some($capacity), $sample)
     }};
 
-    ($expr:expr, label = $label:expr, capacity = $capacity:expr, log = true) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, label = $label:expr, capacity = $capacity:expr, log = true) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
         $crate::InstrumentLog::instrument_log(
             $expr,
             CHANNEL_ID,
             Some($label.to_string()),
             Some($capacity),
+            $sample,
         )
     }};
 
-    ($expr:expr, label = $label:expr, log = true, capacity = $capacity:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, label = $label:expr, log = true, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
         $crate::InstrumentLog::instrument_log(
             $expr,
             CHANNEL_ID,
             Some($label.to_string()),
             Some($capacity),
+            $sample,
         )
     }};
 
-    ($expr:expr, capacity = $capacity:expr, label = $label:expr, log = true) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, capacity = $capacity:expr, label = $label:expr, log = true) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
         $crate::InstrumentLog::instrument_log(
             $expr,
             CHANNEL_ID,
             Some($label.to_string()),
             Some($capacity),
+            $sample,
         )
     }};
 
-    ($expr:expr, capacity = $capacity:expr, log = true, label = $label:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, capacity = $capacity:expr, log = true, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
         $crate::InstrumentLog::instrument_log(
             $expr,
             CHANNEL_ID,
             Some($label.to_string()),
             Some($capacity),
+            $sample,
         )
     }};
 
-    ($expr:expr, log = true, label = $label:expr, capacity = $capacity:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, log = true, label = $label:expr, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
         $crate::InstrumentLog::instrument_log(
             $expr,
             CHANNEL_ID,
             Some($label.to_string()),
             Some($capacity),
+            $sample,
         )
     }};
 
-    ($expr:expr, log = true, capacity = $capacity:expr, label = $label:expr) => {{
-        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+    (@sample $sample:expr; $source:expr; $expr:expr, log = true, capacity = $capacity:expr, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = $source;
         const _: usize = $capacity;
         $crate::InstrumentLog::instrument_log(
             $expr,
             CHANNEL_ID,
             Some($label.to_string()),
             Some($capacity),
+            $sample,
         )
     }};
+    ($expr:expr) => {
+        $crate::channel!(@sample 1; $crate::__source_id!(); $expr)
+    };
+
+    ($expr:expr, $($opts:tt)+) => {
+        $crate::channel!(@split $expr; 1; $crate::__source_id!(); false; false; []; $($opts)+)
+    };
+
+    // Pull `sample = N`, `id = ...`, `log = true` and `preview = true` out of the options
+    // wherever they appear, keeping the rest in order
+    (@split $expr:expr; $sample:expr; $source:expr; $log:tt; $preview:tt; [$($acc:tt)*]; sample = $new:expr $(, $($rest:tt)*)?) => {
+        $crate::channel!(@split $expr; $new; $source; $log; $preview; [$($acc)*]; $($($rest)*)?)
+    };
+
+    (@split $expr:expr; $sample:expr; $source:expr; $log:tt; $preview:tt; [$($acc:tt)*]; id = $id:expr $(, $($rest:tt)*)?) => {
+        $crate::channel!(@split $expr; $sample; $id; $log; $preview; [$($acc)*]; $($($rest)*)?)
+    };
+
+    (@split $expr:expr; $sample:expr; $source:expr; $log:tt; $preview:tt; [$($acc:tt)*]; log = true $(, $($rest:tt)*)?) => {
+        $crate::channel!(@split $expr; $sample; $source; true; $preview; [$($acc)*]; $($($rest)*)?)
+    };
+
+    (@split $expr:expr; $sample:expr; $source:expr; $log:tt; $preview:tt; [$($acc:tt)*]; preview = true $(, $($rest:tt)*)?) => {
+        $crate::channel!(@split $expr; $sample; $source; $log; true; [$($acc)*]; $($($rest)*)?)
+    };
+
+    (@split $expr:expr; $sample:expr; $source:expr; $log:tt; $preview:tt; [$($acc:tt)*]; $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::channel!(@split $expr; $sample; $source; $log; $preview; [$($acc)* , $key = $value]; $($($rest)*)?)
+    };
+
+    (@split $expr:expr; $sample:expr; $source:expr; false; false; [$($acc:tt)*];) => {
+        $crate::channel!(@sample $sample; $source; $expr $($acc)*)
+    };
+
+    (@split $expr:expr; $sample:expr; $source:expr; true; false; [$($acc:tt)*];) => {
+        $crate::channel!(@sample $sample; $source; $expr $($acc)* , log = true)
+    };
+
+    // A preview formats messages like `log = true`, the logs keep the text only if asked to
+    (@split $expr:expr; $sample:expr; $source:expr; $log:tt; true; [$($acc:tt)*];) => {{
+        let channel = $crate::channel!(@sample $sample; $source; $expr $($acc)* , log = true);
+        $crate::__enable_preview($log);
+        channel
+    }};
+}
+
+/// Instrument a channel creation, disabled by the `disabled` feature.
+///
+/// Expands to the original channel, so no proxies or collector threads are started. Options
+/// are still type checked but never evaluated.
+///
+/// ```
+/// let (tx, rx) = std::sync::mpsc::sync_channel::<u32>(1);
+/// let (tx, rx) = channels_console::channel!((tx, rx), label = "jobs", capacity = 1);
+/// tx.send(1).unwrap();
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// ```
+#[cfg(feature = "disabled")]
+#[macro_export]
+macro_rules! channel {
+    ($expr:expr $(, $key:ident = $value:expr)* $(,)?) => {{
+        $(let _ = || {
+            let _ = &$value;
+        };)*
+        $expr
+    }};
 }
 
 /// Instrument a stream to track its item yields.
@@ -921,25 +2851,26 @@ macro_rules! channel {
 /// ```
 ///
 /// See the `stream!` macro documentation for full usage details.
+#[cfg(not(feature = "disabled"))]
 #[macro_export]
 macro_rules! stream {
     ($expr:expr) => {{
-        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        const STREAM_ID: &'static str = $crate::__source_id!();
         $crate::InstrumentStream::instrument_stream($expr, STREAM_ID, None)
     }};
 
     ($expr:expr, label = $label:expr) => {{
-        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        const STREAM_ID: &'static str = $crate::__source_id!();
         $crate::InstrumentStream::instrument_stream($expr, STREAM_ID, Some($label.to_string()))
     }};
 
     ($expr:expr, log = true) => {{
-        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        const STREAM_ID: &'static str = $crate::__source_id!();
         $crate::InstrumentStreamLog::instrument_stream_log($expr, STREAM_ID, None)
     }};
 
     ($expr:expr, label = $label:expr, log = true) => {{
-        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        const STREAM_ID: &'static str = $crate::__source_id!();
         $crate::InstrumentStreamLog::instrument_stream_log(
             $expr,
             STREAM_ID,
@@ -948,7 +2879,7 @@ macro_rules! stream {
     }};
 
     ($expr:expr, log = true, label = $label:expr) => {{
-        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        const STREAM_ID: &'static str = $crate::__source_id!();
         $crate::InstrumentStreamLog::instrument_stream_log(
             $expr,
             STREAM_ID,
@@ -957,6 +2888,18 @@ macro_rules! stream {
     }};
 }
 
+/// Instrument a stream, disabled by the `disabled` feature: expands to the original stream.
+#[cfg(feature = "disabled")]
+#[macro_export]
+macro_rules! stream {
+    ($expr:expr $(, $key:ident = $value:expr)* $(,)?) => {{
+        $(let _ = || {
+            let _ = &$value;
+        };)*
+        $expr
+    }};
+}
+
 fn get_all_channel_stats() -> HashMap<u64, ChannelStats> {
     if let Some((_, stats_map)) = CHANNELS_STATE.get() {
         stats_map.read().unwrap().clone()
@@ -1023,6 +2966,23 @@ pub(crate) fn get_sorted_stream_stats() -> Vec<StreamStats> {
     stats
 }
 
+/// Wait until the collector has applied all events buffered by the forwarders so far.
+pub(crate) fn flush_channel_stats() {
+    let Some((stats_tx, _)) = CHANNELS_STATE.get() else {
+        return;
+    };
+
+    wrappers::flush_event_buffers();
+    let (ack_tx, ack_rx) = crossbeam_channel::bounded(1);
+    if stats_tx.send(ChannelEvent::Flush(ack_tx)).is_ok() {
+        let _ = ack_rx.recv_timeout(Duration::from_secs(1));
+    }
+}
+
+pub(crate) fn get_suspected_leaks() -> Vec<SuspectedLeak> {
+    leaks::suspected_leaks(&get_sorted_channel_stats())
+}
+
 pub(crate) fn get_channels_json() -> ChannelsJson {
     let channels = get_sorted_channel_stats()
         .iter()
@@ -1037,7 +2997,23 @@ pub(crate) fn get_channels_json() -> ChannelsJson {
 
     ChannelsJson {
         current_elapsed_ns,
+        sequence: 0,
+        generation: GENERATION.load(Ordering::Relaxed),
+        runtime_seconds: runtime_seconds(),
         channels,
+        deltas: None,
+    }
+}
+
+pub(crate) fn get_health_json() -> HealthJson {
+    HealthJson {
+        pending_channel_events: CHANNELS_STATE.get().map_or(0, |(tx, _)| tx.len()),
+        pending_stream_events: STREAMS_STATE.get().map_or(0, |(tx, _)| tx.len()),
+        collector_lag_ms: COLLECTOR_LAG_NS.load(Ordering::Relaxed) as f64 / 1e6,
+        total_queued_bytes: CHANNELS_STATE.get().map_or(0, |(_, stats_map)| {
+            let stats = stats_map.read().unwrap();
+            stats.values().map(ChannelStats::queued_bytes).sum()
+        }),
     }
 }
 
@@ -1055,6 +3031,7 @@ pub(crate) fn get_streams_json() -> StreamsJson {
 
     StreamsJson {
         current_elapsed_ns,
+        runtime_seconds: runtime_seconds(),
         streams,
     }
 }
@@ -1070,14 +3047,15 @@ pub(crate) fn get_combined_json() -> CombinedJson {
         .map(SerializableStreamStats::from)
         .collect();
 
+    // Nothing may be instrumented yet when called through `snapshot`
     let current_elapsed_ns = START_TIME
         .get()
-        .expect("START_TIME must be initialized")
-        .elapsed()
-        .as_nanos() as u64;
+        .map_or(0, |start| start.elapsed().as_nanos() as u64);
 
     CombinedJson {
         current_elapsed_ns,
+        generation: GENERATION.load(Ordering::Relaxed),
+        runtime_seconds: runtime_seconds(),
         channels,
         streams,
     }
@@ -1107,8 +3085,8 @@ pub(crate) fn get_channel_logs(channel_id: &str) -> Option<ChannelLogs> {
             channel_stats.received_logs.iter().cloned().collect();
 
         // Sort by index descending (most recent first)
-        sent_logs.sort_by(|a, b| b.index.cmp(&a.index));
-        received_logs.sort_by(|a, b| b.index.cmp(&a.index));
+        sent_logs.sort_by_key(|entry| std::cmp::Reverse(entry.index));
+        received_logs.sort_by_key(|entry| std::cmp::Reverse(entry.index));
 
         ChannelLogs {
             id: channel_id.to_string(),
@@ -1125,7 +3103,7 @@ pub(crate) fn get_stream_logs(stream_id: &str) -> Option<StreamLogs> {
         let mut yielded_logs: Vec<LogEntry> = stream_stats.logs.iter().cloned().collect();
 
         // Sort by index descending (most recent first)
-        yielded_logs.sort_by(|a, b| b.index.cmp(&a.index));
+        yielded_logs.sort_by_key(|entry| std::cmp::Reverse(entry.index));
 
         StreamLogs {
             id: stream_id.to_string(),
@@ -0,0 +1,149 @@
+//! Exporter mirroring channel statistics into a file for local readers to `mmap`.
+//!
+//! The file holds a 64 byte header followed by one 128 byte record per channel, all
+//! integers little endian, see the README for the offsets. It's rewritten in place on
+//! every collector tick and never shrinks, so mappings stay valid. The sequence in the
+//! header is odd while a snapshot is being written. Fields are only added in reserved
+//! bytes or at the end, a new version number marks incompatible changes.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::{Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{ChannelStats, ChannelType, GENERATION};
+
+const MAGIC: &[u8; 8] = b"CHCONMAP";
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = 64;
+const RECORD_SIZE: usize = 128;
+const LABEL_OFFSET: usize = 72;
+const LABEL_SIZE: usize = RECORD_SIZE - LABEL_OFFSET;
+const SEQUENCE_OFFSET: u64 = 16;
+
+/// Writes a snapshot of all channels to the file on every collector tick.
+pub(crate) struct MmapExporter {
+    file: File,
+    /// Number of snapshots started, odd while one is being written.
+    sequence: u64,
+}
+
+impl MmapExporter {
+    pub(crate) fn create(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self { file, sequence: 0 })
+    }
+
+    fn write(&mut self, stats: &HashMap<u64, ChannelStats>) -> std::io::Result<()> {
+        self.sequence += 1;
+        self.write_sequence()?;
+        let snapshot = encode(stats, self.sequence);
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&snapshot)?;
+        self.sequence += 1;
+        self.write_sequence()
+    }
+
+    fn write_sequence(&mut self) -> std::io::Result<()> {
+        self.file.seek(SeekFrom::Start(SEQUENCE_OFFSET))?;
+        self.file.write_all(&self.sequence.to_le_bytes())
+    }
+}
+
+/// Header and records, with the odd `sequence` of the write in progress.
+fn encode(stats: &HashMap<u64, ChannelStats>, sequence: u64) -> Vec<u8> {
+    let mut channels: Vec<&ChannelStats> = stats.values().collect();
+    channels.sort_by_key(|channel_stats| channel_stats.id);
+
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    let mut buf = Vec::with_capacity(HEADER_SIZE + channels.len() * RECORD_SIZE);
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&VERSION.to_le_bytes());
+    buf.extend_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+    buf.extend_from_slice(&sequence.to_le_bytes());
+    buf.extend_from_slice(&GENERATION.load(Ordering::Relaxed).to_le_bytes());
+    buf.extend_from_slice(&timestamp_ms.to_le_bytes());
+    buf.extend_from_slice(&(channels.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&(RECORD_SIZE as u32).to_le_bytes());
+    buf.resize(HEADER_SIZE, 0);
+
+    for channel_stats in channels {
+        encode_record(&mut buf, channel_stats);
+    }
+    buf
+}
+
+fn encode_record(buf: &mut Vec<u8>, channel_stats: &ChannelStats) {
+    let start = buf.len();
+    let capacity = channel_stats
+        .channel_type
+        .capacity()
+        .map_or(u64::MAX, |capacity| capacity as u64);
+    for value in [
+        channel_stats.id,
+        channel_stats.sent_count,
+        channel_stats.received_count,
+        channel_stats.queued(),
+        channel_stats.queued_bytes(),
+        capacity,
+        channel_stats.full_events,
+        channel_stats.send_errors,
+    ] {
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+    let kind = match channel_stats.channel_type {
+        ChannelType::Bounded(_) => 0u8,
+        ChannelType::Unbounded => 1,
+        ChannelType::Oneshot => 2,
+    };
+    buf.push(kind);
+    buf.push(channel_stats.state.code());
+
+    let label = channel_stats.display_label();
+    let label = truncate_utf8(&label, LABEL_SIZE);
+    buf.extend_from_slice(&(label.len() as u16).to_le_bytes());
+    buf.resize(start + LABEL_OFFSET, 0);
+    buf.extend_from_slice(label.as_bytes());
+    buf.resize(start + RECORD_SIZE, 0);
+}
+
+/// Longest prefix of `value` fitting in `max` bytes without splitting a character.
+fn truncate_utf8(value: &str, max: usize) -> &str {
+    let mut end = value.len().min(max);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+static EXPORTER: Mutex<Option<MmapExporter>> = Mutex::new(None);
+
+pub(crate) fn register(exporter: MmapExporter) {
+    *EXPORTER.lock().unwrap() = Some(exporter);
+}
+
+/// Rewrite the file with the current statistics, stopping the export when it fails.
+pub(crate) fn on_tick(stats: &RwLock<HashMap<u64, ChannelStats>>) {
+    let mut slot = EXPORTER.lock().unwrap();
+    let Some(exporter) = slot.as_mut() else {
+        return;
+    };
+    if let Err(e) = exporter.write(&stats.read().unwrap()) {
+        eprintln!(
+            "Failed to write the channel statistics file, stopping: {}",
+            e
+        );
+        *slot = None;
+    }
+}
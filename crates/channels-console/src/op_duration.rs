@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use crate::OpDurationStats;
+
+/// Total and longest duration of the operations forwarded in one direction of a channel.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OpDurationTracker {
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl OpDurationTracker {
+    /// Record an operation observed for `weight` messages, the sample rate of the channel.
+    pub(crate) fn record(&mut self, duration: Duration, weight: u64) {
+        self.count += weight;
+        self.total += duration.saturating_mul(u32::try_from(weight).unwrap_or(u32::MAX));
+        self.max = self.max.max(duration);
+    }
+
+    /// Aggregated durations, or `None` if no operation has been timed.
+    pub(crate) fn stats(&self) -> Option<OpDurationStats> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let total_ns = self.total.as_nanos() as u64;
+        Some(OpDurationStats {
+            count: self.count,
+            total_ns,
+            avg_ns: total_ns / self.count,
+            max_ns: self.max.as_nanos() as u64,
+        })
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::Write;
+
+use crate::{total_queued_bytes, SerializableChannelStats};
+
+pub(crate) const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// A metric family: its name, type, optional unit and how to read a sample from a channel.
+struct Family {
+    name: &'static str,
+    kind: &'static str,
+    unit: Option<&'static str>,
+    help: &'static str,
+    value: fn(&SerializableChannelStats) -> Option<u64>,
+}
+
+const FAMILIES: &[Family] = &[
+    Family {
+        name: "channels_console_sent",
+        kind: "counter",
+        unit: None,
+        help: "Messages sent through the channel.",
+        value: |c| Some(c.sent_count),
+    },
+    Family {
+        name: "channels_console_received",
+        kind: "counter",
+        unit: None,
+        help: "Messages received from the channel.",
+        value: |c| Some(c.received_count),
+    },
+    Family {
+        name: "channels_console_full_events",
+        kind: "counter",
+        unit: None,
+        help: "Times a bounded channel became full.",
+        value: |c| Some(c.full_events),
+    },
+    Family {
+        name: "channels_console_send_errors",
+        kind: "counter",
+        unit: None,
+        help: "Messages dropped because the receiver was gone.",
+        value: |c| Some(c.send_errors),
+    },
+    Family {
+        name: "channels_console_send_timeouts",
+        kind: "counter",
+        unit: None,
+        help: "Instrumented send_timeout calls that timed out.",
+        value: |c| Some(c.send_timeout_count),
+    },
+    Family {
+        name: "channels_console_queued",
+        kind: "gauge",
+        unit: None,
+        help: "Messages waiting in the channel.",
+        value: |c| Some(c.queued),
+    },
+    Family {
+        name: "channels_console_queued_bytes",
+        kind: "gauge",
+        unit: Some("bytes"),
+        help: "Memory used by queued messages.",
+        value: |c| Some(c.queued_bytes),
+    },
+    Family {
+        name: "channels_console_state",
+        kind: "gauge",
+        unit: None,
+        help: "Channel state: 0 active, 1 closed, 2 full, 3 sent, 4 created, 5 received, 6 sender dropped, 7 receiver dropped.",
+        value: |c| Some(c.state_code as u64),
+    },
+    Family {
+        name: "channels_console_capacity",
+        kind: "gauge",
+        unit: None,
+        help: "Capacity of a bounded channel.",
+        value: |c| c.capacity.map(|capacity| capacity as u64),
+    },
+    Family {
+        name: "channels_console_active_senders",
+        kind: "gauge",
+        unit: None,
+        help: "Live senders including clones, tokio mpsc channels only.",
+        value: |c| c.active_senders,
+    },
+    Family {
+        name: "channels_console_active_receivers",
+        kind: "gauge",
+        unit: None,
+        help: "Live receivers, omitted while clones of a multi-consumer receiver are alive.",
+        value: |c| c.active_receivers,
+    },
+];
+
+/// Render channel statistics in the OpenMetrics text format.
+pub(crate) fn render(
+    channels: &[SerializableChannelStats],
+    generation: u64,
+    runtime_seconds: f64,
+) -> String {
+    let mut body = String::new();
+    for family in FAMILIES {
+        let _ = writeln!(body, "# TYPE {} {}", family.name, family.kind);
+        if let Some(unit) = family.unit {
+            let _ = writeln!(body, "# UNIT {} {}", family.name, unit);
+        }
+        let _ = writeln!(body, "# HELP {} {}", family.name, family.help);
+
+        // Counter samples carry the _total suffix, the family name doesn't
+        let suffix = if family.kind == "counter" {
+            "_total"
+        } else {
+            ""
+        };
+        for channel in channels {
+            if let Some(value) = (family.value)(channel) {
+                let _ = writeln!(
+                    body,
+                    "{}{}{} {}",
+                    family.name,
+                    suffix,
+                    labels(channel),
+                    value
+                );
+            }
+        }
+    }
+    render_total_queued_bytes(&mut body, channels);
+    render_send_wait(&mut body, channels);
+    render_generation(&mut body, generation);
+    render_runtime(&mut body, runtime_seconds);
+    body.push_str("# EOF\n");
+    body
+}
+
+const TOTAL_QUEUED_BYTES: &str = "channels_console_total_queued_bytes";
+
+/// A single unlabeled sample summing the queued memory of all channels, to alert on.
+fn render_total_queued_bytes(body: &mut String, channels: &[SerializableChannelStats]) {
+    let _ = writeln!(body, "# TYPE {TOTAL_QUEUED_BYTES} gauge");
+    let _ = writeln!(body, "# UNIT {TOTAL_QUEUED_BYTES} bytes");
+    let _ = writeln!(
+        body,
+        "# HELP {TOTAL_QUEUED_BYTES} Memory used by queued messages of all channels."
+    );
+    let _ = writeln!(
+        body,
+        "{TOTAL_QUEUED_BYTES} {}",
+        total_queued_bytes(channels)
+    );
+}
+
+const GENERATION: &str = "channels_console_generation";
+
+/// Number of resets, so scrapers can tell a reset from a counter going backwards.
+fn render_generation(body: &mut String, generation: u64) {
+    let _ = writeln!(body, "# TYPE {GENERATION} gauge");
+    let _ = writeln!(
+        body,
+        "# HELP {GENERATION} Times the channel statistics were reset."
+    );
+    let _ = writeln!(body, "{GENERATION} {generation}");
+}
+
+const RUNTIME: &str = "channels_console_runtime_seconds";
+
+/// Time the counters accumulated over, the denominator for rates matching the table.
+fn render_runtime(body: &mut String, runtime_seconds: f64) {
+    let _ = writeln!(body, "# TYPE {RUNTIME} gauge");
+    let _ = writeln!(body, "# UNIT {RUNTIME} seconds");
+    let _ = writeln!(
+        body,
+        "# HELP {RUNTIME} Time since the metrics server started."
+    );
+    let _ = writeln!(body, "{RUNTIME} {runtime_seconds}");
+}
+
+const SEND_WAIT: &str = "channels_console_send_wait_seconds";
+
+/// Send wait histograms, with a cumulative sample per bucket, `+Inf`, `_sum` and `_count`.
+fn render_send_wait(body: &mut String, channels: &[SerializableChannelStats]) {
+    let _ = writeln!(body, "# TYPE {SEND_WAIT} histogram");
+    let _ = writeln!(body, "# UNIT {SEND_WAIT} seconds");
+    let _ = writeln!(
+        body,
+        "# HELP {SEND_WAIT} Time sends waited for capacity of a bounded channel."
+    );
+    for channel in channels {
+        let Some(send_wait) = &channel.send_wait else {
+            continue;
+        };
+        let labels = label_pairs(channel);
+        for bucket in &send_wait.buckets {
+            let _ = writeln!(
+                body,
+                "{SEND_WAIT}_bucket{{{labels},le=\"{:?}\"}} {}",
+                bucket.le, bucket.count
+            );
+        }
+        let _ = writeln!(
+            body,
+            "{SEND_WAIT}_bucket{{{labels},le=\"+Inf\"}} {}",
+            send_wait.count
+        );
+        let _ = writeln!(
+            body,
+            "{SEND_WAIT}_sum{{{labels}}} {}",
+            send_wait.sum_seconds
+        );
+        let _ = writeln!(body, "{SEND_WAIT}_count{{{labels}}} {}", send_wait.count);
+    }
+}
+
+fn labels(channel: &SerializableChannelStats) -> String {
+    format!("{{{}}}", label_pairs(channel))
+}
+
+fn label_pairs(channel: &SerializableChannelStats) -> String {
+    format!(
+        "id=\"{}\",label=\"{}\",type=\"{}\"",
+        channel.id,
+        escape_label_value(&channel.label),
+        channel.kind
+    )
+}
+
+/// Escape backslashes, double quotes and newlines as required for label values.
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
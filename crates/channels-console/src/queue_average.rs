@@ -0,0 +1,46 @@
+use std::time::Instant;
+
+/// Time-weighted average queue depth: the queue depth integrated over time since
+/// creation or the last reset, divided by that time.
+#[derive(Debug, Clone)]
+pub(crate) struct QueueAverage {
+    since: Instant,
+    /// Time of the last queue depth change, the depth held constant after it.
+    last_change: Instant,
+    /// Sum of queue depth times the seconds it lasted.
+    message_seconds: f64,
+}
+
+impl QueueAverage {
+    pub(crate) fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            since: now,
+            last_change: now,
+            message_seconds: 0.0,
+        }
+    }
+
+    /// Account for the time `queued` messages sat in the queue until `at`, call it before
+    /// the depth changes. Events arriving out of order add nothing.
+    pub(crate) fn advance(&mut self, queued: u64, at: Instant) {
+        if at <= self.last_change {
+            return;
+        }
+        self.message_seconds += queued as f64 * (at - self.last_change).as_secs_f64();
+        self.last_change = at;
+    }
+
+    /// Average depth up to now, with `queued` messages in the queue currently.
+    pub(crate) fn average(&self, queued: u64) -> f64 {
+        let now = Instant::now();
+        let total = now.saturating_duration_since(self.since).as_secs_f64();
+        if total <= 0.0 {
+            return queued as f64;
+        }
+        let current = now
+            .saturating_duration_since(self.last_change)
+            .as_secs_f64();
+        (self.message_seconds + queued as f64 * current) / total
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::ChannelStats;
+
+/// Time constant of the decaying rates, like the one-minute Unix load average.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How often the decaying rates are updated by the collector.
+const RATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Exponentially decaying sent and received rates of a channel, in messages per second.
+///
+/// Every update moves the rates towards the one measured since the previous update, by
+/// how much depends on the time passed relative to [`RATE_WINDOW`]. Recent traffic
+/// dominates and a channel that went idle drops to 0 within a few minutes.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DecayingRates {
+    last_sent: u64,
+    last_received: u64,
+    sent: f64,
+    received: f64,
+}
+
+impl DecayingRates {
+    /// Rates starting from zero at the given counts, after a reset.
+    pub(crate) fn starting_at(sent: u64, received: u64) -> Self {
+        Self {
+            last_sent: sent,
+            last_received: received,
+            ..Self::default()
+        }
+    }
+
+    fn update(&mut self, sent: u64, received: u64, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let decay = (-secs / RATE_WINDOW.as_secs_f64()).exp();
+        let decayed = |rate: f64, delta: u64| rate * decay + delta as f64 / secs * (1.0 - decay);
+
+        self.sent = decayed(self.sent, sent.saturating_sub(self.last_sent));
+        self.received = decayed(self.received, received.saturating_sub(self.last_received));
+        self.last_sent = sent;
+        self.last_received = received;
+    }
+
+    pub(crate) fn sent_per_sec(&self) -> f64 {
+        self.sent
+    }
+
+    pub(crate) fn received_per_sec(&self) -> f64 {
+        self.received
+    }
+}
+
+/// Updates the decaying rates of all channels on collector ticks, once per [`RATE_INTERVAL`].
+pub(crate) struct RateMeter {
+    last_update: Instant,
+}
+
+impl RateMeter {
+    pub(crate) fn new() -> Self {
+        Self {
+            last_update: Instant::now(),
+        }
+    }
+
+    pub(crate) fn on_tick(&mut self, stats: &mut HashMap<u64, ChannelStats>) {
+        let elapsed = self.last_update.elapsed();
+        if elapsed < RATE_INTERVAL {
+            return;
+        }
+        self.last_update = Instant::now();
+
+        for channel_stats in stats.values_mut() {
+            let (sent, received) = (channel_stats.sent_count, channel_stats.received_count);
+            channel_stats.rates.update(sent, received, elapsed);
+        }
+    }
+}
@@ -0,0 +1,439 @@
+//! Description of the channel statistics fields, served by `GET /metrics/schema`.
+
+use serde::{Deserialize, Serialize};
+
+/// Field of [`SerializableChannelStats`](crate::SerializableChannelStats) described for
+/// dashboard tooling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSchema {
+    /// Key of the field in the JSON output.
+    pub name: String,
+    /// JSON type: `integer`, `number`, `string`, `boolean` or `object`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Whether the field can be `null`.
+    pub nullable: bool,
+    /// Unit of numeric fields, e.g. `messages` or `bytes`.
+    pub unit: Option<String>,
+    pub description: String,
+}
+
+/// Response of `GET /metrics/schema`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelStatsSchema {
+    pub fields: Vec<FieldSchema>,
+}
+
+/// A documented field: its name, JSON type, unit and what it measures.
+struct Field {
+    name: &'static str,
+    kind: &'static str,
+    nullable: bool,
+    unit: Option<&'static str>,
+    description: &'static str,
+}
+
+/// Every field of the serialized channel statistics, in output order.
+const FIELDS: &[Field] = &[
+    Field {
+        name: "id",
+        kind: "integer",
+        nullable: false,
+        unit: None,
+        description: "Unique id of the channel.",
+    },
+    Field {
+        name: "source",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "Module path, file and line where the channel was instrumented.",
+    },
+    Field {
+        name: "label",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "Custom label, or one generated from the source.",
+    },
+    Field {
+        name: "has_custom_label",
+        kind: "boolean",
+        nullable: false,
+        unit: None,
+        description: "Whether the label was set by the user.",
+    },
+    Field {
+        name: "channel_type",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "Type with capacity, e.g. `bounded[10]`.",
+    },
+    Field {
+        name: "kind",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "`bounded`, `unbounded` or `oneshot`.",
+    },
+    Field {
+        name: "capacity",
+        kind: "integer",
+        nullable: true,
+        unit: Some("messages"),
+        description: "Capacity of a bounded channel.",
+    },
+    Field {
+        name: "state",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "`active`, `closed` or `full`, oneshots go from `created` to `sent` and `received`, `sender_dropped` or `receiver_dropped`.",
+    },
+    Field {
+        name: "state_code",
+        kind: "integer",
+        nullable: false,
+        unit: None,
+        description: "State as 0 active, 1 closed, 2 full, 3 sent, 4 created, 5 received, 6 sender dropped, 7 receiver dropped.",
+    },
+    Field {
+        name: "sent_count",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Messages sent into the channel.",
+    },
+    Field {
+        name: "received_count",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Messages received from the channel.",
+    },
+    Field {
+        name: "queued",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Messages waiting in the channel.",
+    },
+    Field {
+        name: "type_name",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "Rust type of the messages.",
+    },
+    Field {
+        name: "type_size",
+        kind: "integer",
+        nullable: false,
+        unit: Some("bytes"),
+        description: "Size of one message type.",
+    },
+    Field {
+        name: "queued_bytes",
+        kind: "integer",
+        nullable: false,
+        unit: Some("bytes"),
+        description: "Memory used by queued messages.",
+    },
+    Field {
+        name: "peak_queued_bytes",
+        kind: "integer",
+        nullable: false,
+        unit: Some("bytes"),
+        description: "Most memory queued messages ever used.",
+    },
+    Field {
+        name: "received_bytes",
+        kind: "integer",
+        nullable: false,
+        unit: Some("bytes"),
+        description: "Total size of received messages.",
+    },
+    Field {
+        name: "queued_bytes_human",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "`queued_bytes` formatted for display, e.g. `1.5 KB`.",
+    },
+    Field {
+        name: "peak_queued_bytes_human",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "`peak_queued_bytes` formatted for display.",
+    },
+    Field {
+        name: "received_bytes_human",
+        kind: "string",
+        nullable: false,
+        unit: None,
+        description: "`received_bytes` formatted for display.",
+    },
+    Field {
+        name: "iter",
+        kind: "integer",
+        nullable: false,
+        unit: None,
+        description: "Instance number of channels sharing a source.",
+    },
+    Field {
+        name: "full_events",
+        kind: "integer",
+        nullable: false,
+        unit: None,
+        description: "Times a bounded channel became full.",
+    },
+    Field {
+        name: "send_errors",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Messages dropped because the receiver was gone.",
+    },
+    Field {
+        name: "send_timeout_count",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Instrumented `send_timeout` calls that timed out.",
+    },
+    Field {
+        name: "utilization",
+        kind: "number",
+        nullable: true,
+        unit: Some("percent"),
+        description: "Share of capacity in use, 0 to 100.",
+    },
+    Field {
+        name: "reserved",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Slots held by outstanding permits.",
+    },
+    Field {
+        name: "available",
+        kind: "integer",
+        nullable: true,
+        unit: Some("messages"),
+        description: "Capacity left after queued messages and permits.",
+    },
+    Field {
+        name: "drain_ratio",
+        kind: "number",
+        nullable: true,
+        unit: Some("ratio"),
+        description: "Received messages per sent message.",
+    },
+    Field {
+        name: "queued_p50",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Median sampled queue depth.",
+    },
+    Field {
+        name: "queued_p95",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "95th percentile of sampled queue depth.",
+    },
+    Field {
+        name: "queued_p99",
+        kind: "integer",
+        nullable: false,
+        unit: Some("messages"),
+        description: "99th percentile of sampled queue depth.",
+    },
+    Field {
+        name: "avg_queued",
+        kind: "number",
+        nullable: false,
+        unit: Some("messages"),
+        description: "Queue depth averaged over time since creation or the last reset.",
+    },
+    Field {
+        name: "dwell_time",
+        kind: "object",
+        nullable: true,
+        unit: None,
+        description: "Enqueue-to-dequeue latency, with the `dwell-time` feature.",
+    },
+    Field {
+        name: "send_wait",
+        kind: "object",
+        nullable: true,
+        unit: None,
+        description: "Histogram of time sends waited for capacity.",
+    },
+    Field {
+        name: "send_timeout",
+        kind: "object",
+        nullable: true,
+        unit: None,
+        description: "Timeout and actual waits of instrumented `send_timeout` calls.",
+    },
+    Field {
+        name: "send_duration",
+        kind: "object",
+        nullable: true,
+        unit: None,
+        description: "Time spent sending to the original channel, with the `op-durations` feature.",
+    },
+    Field {
+        name: "recv_duration",
+        kind: "object",
+        nullable: true,
+        unit: None,
+        description: "Time spent handing messages to the receiver, with the `op-durations` feature.",
+    },
+    Field {
+        name: "sample_rate",
+        kind: "integer",
+        nullable: false,
+        unit: None,
+        description: "One in this many messages is observed.",
+    },
+    Field {
+        name: "active_receivers",
+        kind: "integer",
+        nullable: true,
+        unit: None,
+        description: "Live receivers, `null` when unknown.",
+    },
+    Field {
+        name: "active_senders",
+        kind: "integer",
+        nullable: true,
+        unit: None,
+        description: "Live senders including clones, tokio mpsc channels only.",
+    },
+    Field {
+        name: "total_senders_created",
+        kind: "integer",
+        nullable: true,
+        unit: None,
+        description: "Senders that ever existed, a lower bound, tokio mpsc channels only.",
+    },
+    Field {
+        name: "first_sent_ns",
+        kind: "integer",
+        nullable: true,
+        unit: Some("nanoseconds"),
+        description: "Time of the first send since start.",
+    },
+    Field {
+        name: "last_message_ns",
+        kind: "integer",
+        nullable: true,
+        unit: Some("nanoseconds"),
+        description: "Time of the last send or receive since start.",
+    },
+    Field {
+        name: "idle_for_ms",
+        kind: "integer",
+        nullable: false,
+        unit: Some("milliseconds"),
+        description: "Time since the last send or receive.",
+    },
+    Field {
+        name: "sent_rate_1m",
+        kind: "number",
+        nullable: false,
+        unit: Some("messages per second"),
+        description: "Send rate decaying over one minute, like the Unix load average.",
+    },
+    Field {
+        name: "received_rate_1m",
+        kind: "number",
+        nullable: false,
+        unit: Some("messages per second"),
+        description: "Receive rate decaying over one minute.",
+    },
+    Field {
+        name: "sent_rate_avg",
+        kind: "number",
+        nullable: false,
+        unit: Some("messages per second"),
+        description: "Average send rate since the channel was created.",
+    },
+    Field {
+        name: "received_rate_avg",
+        kind: "number",
+        nullable: false,
+        unit: Some("messages per second"),
+        description: "Average receive rate since the channel was created.",
+    },
+    Field {
+        name: "instance_count",
+        kind: "integer",
+        nullable: false,
+        unit: None,
+        description: "Channels aggregated into this entry.",
+    },
+    Field {
+        name: "task_id",
+        kind: "integer",
+        nullable: true,
+        unit: None,
+        description: "Id of the tokio task that created the channel.",
+    },
+    Field {
+        name: "runtime",
+        kind: "object",
+        nullable: true,
+        unit: None,
+        description: "Tokio runtime that created the channel, with its `id`, `flavor`, `workers` and `worker`.",
+    },
+    Field {
+        name: "preview",
+        kind: "string",
+        nullable: true,
+        unit: None,
+        description: "`Debug` output of the latest message, with `preview = true`.",
+    },
+    Field {
+        name: "cancelled",
+        kind: "boolean",
+        nullable: false,
+        unit: None,
+        description: "Whether a oneshot receiver was dropped before the value arrived.",
+    },
+    Field {
+        name: "disabled",
+        kind: "boolean",
+        nullable: false,
+        unit: None,
+        description: "Whether counting was turned off with `disable_channel`.",
+    },
+];
+
+/// Description of every field of the channel statistics in the JSON output.
+///
+/// # Examples
+///
+/// ```
+/// let schema = channels_console::channel_stats_schema();
+/// let queued = schema.fields.iter().find(|f| f.name == "queued_bytes").unwrap();
+/// assert_eq!(queued.unit.as_deref(), Some("bytes"));
+/// ```
+pub fn channel_stats_schema() -> ChannelStatsSchema {
+    let fields = FIELDS
+        .iter()
+        .map(|field| FieldSchema {
+            name: field.name.to_string(),
+            kind: field.kind.to_string(),
+            nullable: field.nullable,
+            unit: field.unit.map(str::to_string),
+            description: field.description.to_string(),
+        })
+        .collect();
+    ChannelStatsSchema { fields }
+}
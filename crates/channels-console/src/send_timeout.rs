@@ -0,0 +1,161 @@
+//! Timed sends on instrumented tokio channels, see [`SendTimeoutExt`].
+
+use std::time::Duration;
+#[cfg(feature = "tokio")]
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    sync::{LazyLock, Mutex},
+    time::Instant,
+};
+
+#[cfg(feature = "tokio")]
+use tokio::sync::mpsc::{error::SendTimeoutError, Sender, WeakSender};
+
+use crate::SendTimeoutStats;
+#[cfg(feature = "tokio")]
+use crate::{ChannelEvent, CHANNELS_STATE};
+
+/// Timed sends of a channel, their timeouts and how long they waited.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SendTimeoutTracker {
+    count: u64,
+    timeouts: u64,
+    last_timeout: Duration,
+    total_wait: Duration,
+    max_wait: Duration,
+}
+
+impl SendTimeoutTracker {
+    pub(crate) fn record(&mut self, timeout: Duration, waited: Duration, timed_out: bool) {
+        self.count += 1;
+        if timed_out {
+            self.timeouts += 1;
+        }
+        self.last_timeout = timeout;
+        self.total_wait += waited;
+        self.max_wait = self.max_wait.max(waited);
+    }
+
+    /// Number of timed sends that gave up.
+    pub(crate) fn timeouts(&self) -> u64 {
+        self.timeouts
+    }
+
+    /// Aggregated waits, or `None` if no timed send has been recorded.
+    pub(crate) fn stats(&self) -> Option<SendTimeoutStats> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(SendTimeoutStats {
+            count: self.count,
+            timeout_ns: self.last_timeout.as_nanos() as u64,
+            avg_wait_ns: (self.total_wait.as_nanos() / u128::from(self.count)) as u64,
+            max_wait_ns: self.max_wait.as_nanos() as u64,
+        })
+    }
+}
+
+/// Outer sender of an instrumented bounded channel, kept weak so it doesn't hold the
+/// channel open.
+#[cfg(feature = "tokio")]
+trait OuterSender: Send {
+    fn is_alive(&self) -> bool;
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Send + 'static> OuterSender for WeakSender<T> {
+    fn is_alive(&self) -> bool {
+        self.strong_count() > 0
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Channel ids of the outer senders handed out by `channel!`, the native sender type has
+/// no room for the id.
+#[cfg(feature = "tokio")]
+static OUTER_SENDERS: LazyLock<Mutex<HashMap<u64, Box<dyn OuterSender>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Remember the channel `tx` was handed out for, dropping channels whose senders are gone.
+#[cfg(feature = "tokio")]
+pub(crate) fn register_sender<T: Send + 'static>(id: u64, tx: &Sender<T>) {
+    let mut senders = OUTER_SENDERS.lock().unwrap();
+    senders.retain(|_, sender| sender.is_alive());
+    senders.insert(id, Box::new(tx.downgrade()));
+}
+
+/// Id of the instrumented channel `tx` belongs to, `None` if it isn't instrumented.
+#[cfg(feature = "tokio")]
+fn channel_id<T: Send + 'static>(tx: &Sender<T>) -> Option<u64> {
+    let senders = OUTER_SENDERS.lock().unwrap();
+    senders.iter().find_map(|(id, sender)| {
+        let weak = sender.as_any().downcast_ref::<WeakSender<T>>()?;
+        weak.upgrade()
+            .is_some_and(|outer| outer.same_channel(tx))
+            .then_some(*id)
+    })
+}
+
+/// `send_timeout` that reports its outcome to the channel's statistics.
+///
+/// A timed out send never leaves the caller's sender, so the proxies can't see it. Calling
+/// [`instrumented_send_timeout`](Self::instrumented_send_timeout) instead of
+/// `send_timeout` counts the timeouts in `send_timeout_count` and records the configured
+/// timeout next to how long the sends actually waited in `send_timeout`. On senders of
+/// channels that aren't instrumented it's a plain `send_timeout`.
+///
+/// # Examples
+///
+/// ```
+/// use channels_console::SendTimeoutExt;
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let (tx, rx) = tokio::sync::mpsc::channel::<i32>(1);
+/// let (tx, _rx) = channels_console::channel!((tx, rx));
+///
+/// tx.instrumented_send_timeout(1, Duration::from_millis(10))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub trait SendTimeoutExt<T> {
+    /// Like `send_timeout`, recording whether the send timed out and how long it waited.
+    fn instrumented_send_timeout(
+        &self,
+        value: T,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<(), SendTimeoutError<T>>> + Send;
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Send + 'static> SendTimeoutExt<T> for Sender<T> {
+    async fn instrumented_send_timeout(
+        &self,
+        value: T,
+        timeout: Duration,
+    ) -> Result<(), SendTimeoutError<T>> {
+        let started = Instant::now();
+        let result = self.send_timeout(value, timeout).await;
+        let waited = started.elapsed();
+
+        if let (Some(id), Some((stats_tx, _))) = (channel_id(self), CHANNELS_STATE.get()) {
+            let _ = stats_tx.send(ChannelEvent::TimedSend {
+                id,
+                timeout,
+                waited,
+                timed_out: matches!(result, Err(SendTimeoutError::Timeout(_))),
+            });
+        }
+        result
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::{SendWaitBucket, SendWaitStats};
+
+/// Upper bounds in seconds, from 1µs to 10s.
+const DEFAULT_BUCKETS: &[f64] = &[0.000_001, 0.000_01, 0.000_1, 0.001, 0.01, 0.1, 1.0, 10.0];
+
+/// Bucket bounds shared by all channels, fixed once the first send wait is recorded.
+static BUCKETS: OnceLock<Vec<f64>> = OnceLock::new();
+
+/// Use custom bucket bounds. Returns `false` if the bounds are already in use.
+pub(crate) fn set_buckets(mut buckets: Vec<f64>) -> bool {
+    buckets.retain(|bound| bound.is_finite() && *bound >= 0.0);
+    buckets.sort_by(f64::total_cmp);
+    buckets.dedup();
+    BUCKETS.set(buckets).is_ok()
+}
+
+fn buckets() -> &'static [f64] {
+    BUCKETS.get_or_init(|| DEFAULT_BUCKETS.to_vec())
+}
+
+/// Counts of how long the send forwarder waited for capacity of a bounded channel,
+/// bucketed like a Prometheus histogram.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SendWaitHistogram {
+    /// Non-cumulative count per bound, waits above the last bound are only in `count`.
+    counts: Vec<u64>,
+    sum: Duration,
+    count: u64,
+}
+
+impl SendWaitHistogram {
+    /// Record a wait observed for `weight` messages, the sample rate of the channel.
+    pub(crate) fn record(&mut self, wait: Duration, weight: u64) {
+        let bounds = buckets();
+        if self.counts.is_empty() {
+            self.counts = vec![0; bounds.len()];
+        }
+        let seconds = wait.as_secs_f64();
+        if let Some(idx) = bounds.iter().position(|&le| seconds <= le) {
+            self.counts[idx] += weight;
+        }
+        self.sum += wait.saturating_mul(u32::try_from(weight).unwrap_or(u32::MAX));
+        self.count += weight;
+    }
+
+    /// Cumulative bucket counts, or `None` if no wait has been recorded.
+    pub(crate) fn stats(&self) -> Option<SendWaitStats> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let mut cumulative = 0;
+        let buckets = buckets()
+            .iter()
+            .zip(&self.counts)
+            .map(|(&le, &count)| {
+                cumulative += count;
+                SendWaitBucket {
+                    le,
+                    count: cumulative,
+                }
+            })
+            .collect();
+
+        Some(SendWaitStats {
+            buckets,
+            sum_seconds: self.sum.as_secs_f64(),
+            count: self.count,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+type SizeFn<T> = dyn Fn(&T) -> usize + Send + Sync;
+
+/// Registered size functions, each stored as an `Arc<SizeFn<T>>` keyed by `T`'s type id.
+static SIZERS: LazyLock<RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Register a function returning the size in bytes of a message of type `T`.
+///
+/// By default queued memory is estimated as `size_of::<T>()` per message, which ignores
+/// heap data owned by types like `Vec` or `String`. Channels carrying `T` that are
+/// instrumented after this call use `sizer` instead. Registering again for the same type
+/// replaces the previous function for channels created afterwards.
+///
+/// # Examples
+///
+/// ```
+/// channels_console::register_sizer::<Vec<u8>>(|msg| msg.len());
+///
+/// let (tx, rx) = std::sync::mpsc::channel::<Vec<u8>>();
+/// #[cfg(feature = "channels-console")]
+/// let (tx, rx) = channels_console::channel!((tx, rx));
+/// tx.send(vec![0; 1024]).unwrap();
+/// # rx.recv().unwrap();
+/// ```
+pub fn register_sizer<T: 'static>(sizer: impl Fn(&T) -> usize + Send + Sync + 'static) {
+    let sizer: Arc<SizeFn<T>> = Arc::new(sizer);
+    SIZERS
+        .write()
+        .unwrap()
+        .insert(TypeId::of::<T>(), Box::new(sizer));
+}
+
+/// The size function registered for `T` when a channel was created, if any.
+pub(crate) struct Sizer<T>(Option<Arc<SizeFn<T>>>);
+
+impl<T: 'static> Sizer<T> {
+    pub(crate) fn lookup() -> Self {
+        let sizer = SIZERS
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<T>())
+            .and_then(|sizer| sizer.downcast_ref::<Arc<SizeFn<T>>>())
+            .cloned();
+        Self(sizer)
+    }
+
+    /// Size of `msg` in bytes, `None` when no sizer is registered for `T`.
+    pub(crate) fn size(&self, msg: &T) -> Option<u64> {
+        self.0.as_ref().map(|sizer| sizer(msg) as u64)
+    }
+}
+
+impl<T> Clone for Sizer<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
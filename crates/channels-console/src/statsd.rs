@@ -0,0 +1,100 @@
+//! Push exporter sending channel statistics to a StatsD/DogStatsD agent over UDP.
+
+use std::collections::HashMap;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::ChannelStats;
+
+const METRIC_PREFIX: &str = "channels_console";
+
+/// Pushes gauges and counters on collector ticks, once per interval.
+pub(crate) struct StatsdExporter {
+    socket: UdpSocket,
+    interval: Duration,
+    last_push: Option<Instant>,
+    /// Sent and received counts at the last push, counters are reported as deltas.
+    /// The counts include the messages taken off by resets, so they only go down when a
+    /// failed send is taken back.
+    pushed_counts: HashMap<u64, (u64, u64)>,
+}
+
+impl StatsdExporter {
+    pub(crate) fn connect(addr: &str, interval: Duration) -> std::io::Result<Self> {
+        let target = addr.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "no address resolved")
+        })?;
+        let local = if target.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(target)?;
+        Ok(Self {
+            socket,
+            interval,
+            last_push: None,
+            pushed_counts: HashMap::new(),
+        })
+    }
+
+    fn push(&mut self, stats: &HashMap<u64, ChannelStats>) {
+        self.pushed_counts.retain(|id, _| stats.contains_key(id));
+
+        for channel_stats in stats.values() {
+            let sent = channel_stats.sent_count + channel_stats.reset_received;
+            let received = channel_stats.received_count + channel_stats.reset_received;
+            let (pushed_sent, pushed_received) = self
+                .pushed_counts
+                .insert(channel_stats.id, (sent, received))
+                .unwrap_or_default();
+
+            let label = channel_stats.display_label();
+            let tags = format!("#id:{},label:{}", channel_stats.id, sanitize_tag(&label));
+            let packet = format!(
+                "{METRIC_PREFIX}.queued:{}|g|{tags}\n{METRIC_PREFIX}.sent:{}|c|{tags}\n{METRIC_PREFIX}.received:{}|c|{tags}",
+                channel_stats.queued(),
+                sent.saturating_sub(pushed_sent),
+                received.saturating_sub(pushed_received),
+            );
+
+            // UDP is fire-and-forget, a missing agent must not disturb the collector
+            let _ = self.socket.send(packet.as_bytes());
+        }
+    }
+}
+
+/// Replace characters that delimit metrics and tags in the DogStatsD format.
+fn sanitize_tag(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            ',' | '|' | '#' | ':' | '\n' | '\r' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+static EXPORTER: Mutex<Option<StatsdExporter>> = Mutex::new(None);
+
+pub(crate) fn register(exporter: StatsdExporter) {
+    *EXPORTER.lock().unwrap() = Some(exporter);
+}
+
+/// Push the current statistics if the configured interval has elapsed since the last push.
+pub(crate) fn on_tick(stats: &RwLock<HashMap<u64, ChannelStats>>) {
+    let mut exporter = EXPORTER.lock().unwrap();
+    let Some(exporter) = exporter.as_mut() else {
+        return;
+    };
+    if exporter
+        .last_push
+        .is_some_and(|last| last.elapsed() < exporter.interval)
+    {
+        return;
+    }
+    exporter.last_push = Some(Instant::now());
+    exporter.push(&stats.read().unwrap());
+}
@@ -0,0 +1,99 @@
+//! Broadcast of channel statistics snapshots to async subscribers.
+//!
+//! Subscribe with [`subscribe`](crate::subscribe).
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::{compare_channel_stats, elapsed_ns, ChannelStats, SerializableChannelStats};
+
+/// Minimum time between two published snapshots.
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Snapshots buffered per subscriber before the oldest are dropped.
+const CAPACITY: usize = 16;
+
+/// Statistics of all instrumented channels at one point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    /// Elapsed time since program start in nanoseconds
+    pub current_elapsed_ns: u64,
+    /// Channel statistics, sorted like the `/channels` endpoint
+    pub channels: Vec<SerializableChannelStats>,
+}
+
+static SENDER: LazyLock<broadcast::Sender<StatsSnapshot>> =
+    LazyLock::new(|| broadcast::channel(CAPACITY).0);
+
+/// Set when the collector applied events since the last published snapshot.
+static CHANGED: AtomicBool = AtomicBool::new(false);
+
+static LAST_PUBLISH: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Subscribe to snapshots of the channel statistics.
+///
+/// A snapshot is published after the collector applies new events, at most once every
+/// 250ms, so hot channels don't flood subscribers. Nothing is published while the
+/// statistics are unchanged. A subscriber that falls behind by more than 16 snapshots
+/// receives [`RecvError::Lagged`](tokio::sync::broadcast::error::RecvError::Lagged) and
+/// continues with the most recent ones.
+///
+/// Requires the `tokio` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut updates = channels_console::subscribe();
+/// while let Ok(snapshot) = updates.recv().await {
+///     for channel in snapshot.channels.iter().filter(|c| c.queued > 100) {
+///         eprintln!("{} has {} queued messages", channel.label, channel.queued);
+///     }
+/// }
+/// # }
+/// ```
+pub fn subscribe() -> broadcast::Receiver<StatsSnapshot> {
+    let receiver = SENDER.subscribe();
+    // Give the new subscriber the current state on the next tick
+    CHANGED.store(true, Ordering::Relaxed);
+    receiver
+}
+
+pub(crate) fn mark_changed() {
+    CHANGED.store(true, Ordering::Relaxed);
+}
+
+/// Publish a snapshot if statistics changed and the throttle interval has elapsed.
+pub(crate) fn on_tick(stats: &RwLock<HashMap<u64, ChannelStats>>) {
+    if SENDER.receiver_count() == 0 {
+        return;
+    }
+    let mut last_publish = LAST_PUBLISH.lock().unwrap();
+    if last_publish.is_some_and(|last| last.elapsed() < MIN_INTERVAL) {
+        return;
+    }
+    if !CHANGED.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    *last_publish = Some(Instant::now());
+
+    let stats = stats.read().unwrap();
+    let mut channels: Vec<&ChannelStats> = stats.values().collect();
+    channels.sort_by(|a, b| compare_channel_stats(a, b));
+    let snapshot = StatsSnapshot {
+        current_elapsed_ns: elapsed_ns(Instant::now()),
+        channels: channels
+            .into_iter()
+            .map(SerializableChannelStats::from)
+            .collect(),
+    };
+    drop(stats);
+    // Fails only when all subscribers are gone
+    let _ = SENDER.send(snapshot);
+}
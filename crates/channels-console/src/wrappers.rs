@@ -5,3 +5,231 @@ pub(crate) mod futures;
 pub(crate) mod std;
 #[cfg(feature = "tokio")]
 pub(crate) mod tokio;
+
+use ::std::sync::atomic::{AtomicBool, Ordering};
+use ::std::sync::{Arc, Mutex, Weak};
+use ::std::time::Instant;
+
+use crossbeam_channel::{SendError, Sender as CbSender};
+
+use crate::{channel_switch, ChannelEvent, ChannelOrigin, RuntimeInfo};
+
+/// Number of buffered message events that triggers an immediate flush to the collector.
+const BATCH_SIZE: usize = 64;
+
+/// Buffers of all live forwarders, flushed by the collector on every tick.
+static EVENT_BUFFERS: Mutex<Vec<Weak<EventBufferInner>>> = Mutex::new(Vec::new());
+
+struct EventBufferInner {
+    tx: CbSender<ChannelEvent>,
+    events: Mutex<Vec<ChannelEvent>>,
+}
+
+impl EventBufferInner {
+    /// Send all buffered events as one batch. The lock is held while sending so that
+    /// batches from the same forwarder reach the collector in order.
+    fn flush(&self, event: Option<ChannelEvent>) -> Result<(), SendError<ChannelEvent>> {
+        let mut events = self.events.lock().unwrap();
+        events.extend(event);
+        if events.is_empty() {
+            return Ok(());
+        }
+        let batch = ::std::mem::replace(&mut *events, Vec::with_capacity(BATCH_SIZE));
+        self.tx.send(ChannelEvent::Batch(batch))
+    }
+}
+
+/// Per-channel buffer that delivers message events to the collector in batches.
+///
+/// Message events are collected locally and sent as a single [`ChannelEvent::Batch`]
+/// once [`BATCH_SIZE`] of them pile up. Partially filled buffers are flushed by the
+/// collector tick, so statistics are never more than one tick behind. Lifecycle events
+/// flush the buffer and are delivered immediately, preserving their order.
+///
+/// Both forwarders of a channel share one buffer through clones, so the collector sees
+/// sends and receives in the order they happened and the queue never appears deeper
+/// than it was.
+#[derive(Clone)]
+pub(crate) struct EventBuffer {
+    inner: Arc<EventBufferInner>,
+}
+
+impl EventBuffer {
+    pub(crate) fn new(tx: CbSender<ChannelEvent>) -> Self {
+        let inner = Arc::new(EventBufferInner {
+            tx,
+            events: Mutex::new(Vec::with_capacity(BATCH_SIZE)),
+        });
+        let mut buffers = EVENT_BUFFERS.lock().unwrap();
+        buffers.retain(|buffer| buffer.strong_count() > 0);
+        buffers.push(Arc::downgrade(&inner));
+        Self { inner }
+    }
+
+    pub(crate) fn send(&self, event: ChannelEvent) -> Result<(), SendError<ChannelEvent>> {
+        if matches!(
+            event,
+            ChannelEvent::MessageSent { .. } | ChannelEvent::MessageReceived { .. }
+        ) {
+            let mut events = self.inner.events.lock().unwrap();
+            if events.len() + 1 < BATCH_SIZE {
+                events.push(event);
+                return Ok(());
+            }
+        }
+        self.inner.flush(Some(event))
+    }
+}
+
+/// Send the pending events of all live forwarders to the collector.
+pub(crate) fn flush_event_buffers() {
+    let buffers: Vec<_> = EVENT_BUFFERS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+    for buffer in buffers {
+        let _ = buffer.flush(None);
+    }
+}
+
+/// Outcome of handing a message to the outer receiver of a bounded channel.
+pub(crate) enum Delivery {
+    Delivered,
+    /// The receiver was already gone when the message arrived, so its send failed.
+    SendFailed,
+    /// The receiver was dropped while the message waited for capacity, discarding a
+    /// message that was sent successfully.
+    Discarded,
+}
+
+/// Handle shared by the two forwarders of a channel.
+///
+/// Forwarders report their other events before they finish, so when the last handle is
+/// dropped the collector already has the final statistics and can forget the channel.
+#[derive(Clone)]
+pub(crate) struct Registration {
+    _inner: Arc<RegistrationInner>,
+}
+
+struct RegistrationInner {
+    id: u64,
+    tx: CbSender<ChannelEvent>,
+}
+
+impl Registration {
+    pub(crate) fn new(id: u64, tx: CbSender<ChannelEvent>) -> Self {
+        Self {
+            _inner: Arc::new(RegistrationInner { id, tx }),
+        }
+    }
+}
+
+impl Drop for RegistrationInner {
+    fn drop(&mut self) {
+        let _ = self.tx.send(ChannelEvent::Finished { id: self.id });
+    }
+}
+
+/// Task and runtime creating a channel.
+pub(crate) fn channel_origin() -> Box<ChannelOrigin> {
+    Box::new(ChannelOrigin {
+        task_id: current_task_id(),
+        runtime: current_runtime(),
+    })
+}
+
+/// Id of the tokio task creating a channel, the same id tokio-console displays.
+fn current_task_id() -> Option<u64> {
+    #[cfg(feature = "tokio")]
+    {
+        ::tokio::task::try_id().and_then(|id| id.to_string().parse().ok())
+    }
+    #[cfg(not(feature = "tokio"))]
+    {
+        None
+    }
+}
+
+/// Tokio runtime creating a channel, with the worker thread when tokio exposes it.
+fn current_runtime() -> Option<RuntimeInfo> {
+    #[cfg(feature = "tokio")]
+    {
+        use ::tokio::runtime::{Handle, RuntimeFlavor};
+
+        let handle = Handle::try_current().ok()?;
+        let flavor = match handle.runtime_flavor() {
+            RuntimeFlavor::CurrentThread => "current_thread",
+            _ => "multi_thread",
+        };
+        #[cfg(tokio_unstable)]
+        let worker = ::tokio::runtime::worker_index();
+        #[cfg(not(tokio_unstable))]
+        let worker = None;
+
+        Some(RuntimeInfo {
+            id: handle.id().to_string().parse().ok(),
+            flavor: flavor.to_string(),
+            workers: handle.metrics().num_workers(),
+            worker,
+        })
+    }
+    #[cfg(not(feature = "tokio"))]
+    {
+        None
+    }
+}
+
+/// Start timing a forwarded operation of a sampled message. `None` unless the
+/// `op-durations` feature is enabled, so by default the clock isn't read.
+pub(crate) fn op_timer(sampled: bool) -> Option<Instant> {
+    if cfg!(feature = "op-durations") && sampled {
+        Some(Instant::now())
+    } else {
+        None
+    }
+}
+
+/// Decides which message events are reported to the collector when sampling is enabled.
+///
+/// With a rate of `n`, only every n-th message emits an event and the collector scales
+/// the counts by `n`. No message is reported while the channel is disabled with
+/// [`disable_channel`](crate::disable_channel).
+pub(crate) struct Sampler {
+    rate: u64,
+    seen: u64,
+    enabled: Arc<AtomicBool>,
+}
+
+impl Sampler {
+    /// Samplers for the send and receive forwarders of channel `id`, sharing its switch.
+    pub(crate) fn pair(id: u64, rate: u64) -> (Self, Self) {
+        let enabled = channel_switch(id);
+        let sampler = |enabled| Self {
+            rate: rate.max(1),
+            seen: 0,
+            enabled,
+        };
+        (sampler(Arc::clone(&enabled)), sampler(enabled))
+    }
+
+    /// Effective sampling rate, a requested rate of `0` is treated as `1`.
+    pub(crate) fn rate(&self) -> u64 {
+        self.rate
+    }
+
+    /// Returns `true` if the current message should be reported.
+    pub(crate) fn sample(&mut self) -> bool {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.seen += 1;
+        if self.seen >= self.rate {
+            self.seen = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use crossbeam_channel::{self, Receiver, Sender};
 use std::mem;
-use std::sync::atomic::Ordering;
 
-use crate::{init_channels_state, ChannelEvent, ChannelType, CHANNEL_ID_COUNTER};
+use crate::sizers::Sizer;
+use crate::wrappers::{channel_origin, op_timer, Delivery, EventBuffer, Registration, Sampler};
+use crate::{check_label, init_channels_state, next_channel_id, ChannelEvent, ChannelType};
 
 /// Internal implementation for wrapping bounded crossbeam channels with optional logging.
 fn wrap_bounded_impl<T, F>(
@@ -10,6 +11,7 @@ fn wrap_bounded_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
     mut log_on_send: F,
 ) -> (Sender<T>, Receiver<T>)
 where
@@ -18,13 +20,18 @@ where
 {
     let (inner_tx, inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, to_inner_rx) = crossbeam_channel::bounded::<T>(capacity);
     let (from_inner_tx, outer_rx) = crossbeam_channel::bounded::<T>(capacity);
 
     let (stats_tx, _) = init_channels_state();
 
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -33,16 +40,23 @@ where
         channel_type: ChannelType::Bounded(capacity),
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: true,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, close_signal_rx) = crossbeam_channel::bounded::<()>(1);
 
     // Forward outer -> inner (proxy the send path)
     std::thread::spawn(move || {
+        let _registration = send_registration;
         loop {
             // Check for close signal (non-blocking)
             match close_signal_rx.try_recv() {
@@ -62,16 +76,30 @@ where
             // Try to receive with timeout to periodically check close signal
             match to_inner_rx.recv_timeout(std::time::Duration::from_millis(10)) {
                 Ok(msg) => {
-                    let log = log_on_send(&msg);
+                    let sampled = send_sampler.sample();
+                    let log = if sampled { log_on_send(&msg) } else { None };
+                    let size = if sampled { send_sizer.size(&msg) } else { None };
+                    let send_started = std::time::Instant::now();
+                    let op_started = op_timer(sampled);
                     if inner_tx.send(msg).is_err() {
+                        let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                            id,
+                            reported: false,
+                            size: None,
+                        });
                         // Inner receiver dropped
                         break;
                     }
-                    let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                        id,
-                        log,
-                        timestamp: std::time::Instant::now(),
-                    });
+                    if sampled {
+                        let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                            id,
+                            log,
+                            size,
+                            send_wait: Some(send_started.elapsed()),
+                            op_duration: op_started.map(|started| started.elapsed()),
+                            timestamp: std::time::Instant::now(),
+                        });
+                    }
                 }
                 Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
                     // No message, loop again to check close signal
@@ -89,16 +117,40 @@ where
 
     // Forward inner -> outer (proxy the recv path)
     std::thread::spawn(move || {
+        let _registration = recv_registration;
         while let Ok(msg) = inner_rx.recv() {
-            if from_inner_tx.send(msg).is_err() {
+            let sampled = recv_sampler.sample();
+            let size = if sampled { recv_sizer.size(&msg) } else { None };
+            let op_started = op_timer(sampled);
+            let delivery = match from_inner_tx.try_send(msg) {
+                Ok(()) => Delivery::Delivered,
+                Err(crossbeam_channel::TrySendError::Full(msg)) => match from_inner_tx.send(msg) {
+                    Ok(()) => Delivery::Delivered,
+                    Err(_) => Delivery::Discarded,
+                },
+                Err(crossbeam_channel::TrySendError::Disconnected(_)) => Delivery::SendFailed,
+            };
+            if !matches!(delivery, Delivery::Delivered) {
+                if matches!(delivery, Delivery::SendFailed) {
+                    let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
+                        id,
+                        reported: sampled,
+                        size,
+                    });
+                }
                 // Outer receiver was closed
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                 break;
             }
-            let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
-                id,
-                timestamp: std::time::Instant::now(),
-            });
+            if sampled {
+                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                    id,
+                    size,
+                    op_duration: op_started.map(|started| started.elapsed()),
+                    timestamp: std::time::Instant::now(),
+                });
+            }
         }
         // Channel is closed (either inner sender dropped or outer receiver closed)
         let _ = stats_tx_recv.send(ChannelEvent::Closed { id });
@@ -114,8 +166,9 @@ pub(crate) fn wrap_bounded<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_bounded_impl(inner, source, label, capacity, |_| None)
+    wrap_bounded_impl(inner, source, label, capacity, sample, |_| None)
 }
 
 /// Wrap a bounded crossbeam channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -124,8 +177,9 @@ pub(crate) fn wrap_bounded_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_bounded_impl(inner, source, label, capacity, |msg| {
+    wrap_bounded_impl(inner, source, label, capacity, sample, |msg| {
         Some(format!("{:?}", msg))
     })
 }
@@ -135,6 +189,7 @@ fn wrap_unbounded_impl<T, F>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
     mut log_on_send: F,
 ) -> (Sender<T>, Receiver<T>)
 where
@@ -143,13 +198,18 @@ where
 {
     let (inner_tx, inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, to_inner_rx) = crossbeam_channel::unbounded::<T>();
     let (from_inner_tx, outer_rx) = crossbeam_channel::unbounded::<T>();
 
     let (stats_tx, _) = init_channels_state();
 
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -158,16 +218,23 @@ where
         channel_type: ChannelType::Unbounded,
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: true,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, close_signal_rx) = crossbeam_channel::bounded::<()>(1);
 
     // Forward outer -> inner (proxy the send path)
     std::thread::spawn(move || {
+        let _registration = send_registration;
         loop {
             // Check for close signal (non-blocking)
             match close_signal_rx.try_recv() {
@@ -187,16 +254,29 @@ where
             // Try to receive with timeout to periodically check close signal
             match to_inner_rx.recv_timeout(std::time::Duration::from_millis(10)) {
                 Ok(msg) => {
-                    let log = log_on_send(&msg);
+                    let sampled = send_sampler.sample();
+                    let log = if sampled { log_on_send(&msg) } else { None };
+                    let size = if sampled { send_sizer.size(&msg) } else { None };
+                    let op_started = op_timer(sampled);
                     if inner_tx.send(msg).is_err() {
+                        let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                            id,
+                            reported: false,
+                            size: None,
+                        });
                         // Inner receiver dropped
                         break;
                     }
-                    let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                        id,
-                        log,
-                        timestamp: std::time::Instant::now(),
-                    });
+                    if sampled {
+                        let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                            id,
+                            log,
+                            size,
+                            send_wait: None,
+                            op_duration: op_started.map(|started| started.elapsed()),
+                            timestamp: std::time::Instant::now(),
+                        });
+                    }
                 }
                 Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
                     // No message, loop again to check close signal
@@ -214,16 +294,30 @@ where
 
     // Forward inner -> outer (proxy the recv path)
     std::thread::spawn(move || {
+        let _registration = recv_registration;
         while let Ok(msg) = inner_rx.recv() {
+            let sampled = recv_sampler.sample();
+            let size = if sampled { recv_sizer.size(&msg) } else { None };
+            let op_started = op_timer(sampled);
             if from_inner_tx.send(msg).is_err() {
+                let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
+                    id,
+                    reported: sampled,
+                    size,
+                });
                 // Outer receiver was closed
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                 break;
             }
-            let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
-                id,
-                timestamp: std::time::Instant::now(),
-            });
+            if sampled {
+                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                    id,
+                    size,
+                    op_duration: op_started.map(|started| started.elapsed()),
+                    timestamp: std::time::Instant::now(),
+                });
+            }
         }
         // Channel is closed (either inner sender dropped or outer receiver closed)
         let _ = stats_tx_recv.send(ChannelEvent::Closed { id });
@@ -237,8 +331,9 @@ pub(crate) fn wrap_unbounded<T: Send + 'static>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |_| None)
+    wrap_unbounded_impl(inner, source, label, sample, |_| None)
 }
 
 /// Wrap an unbounded crossbeam channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -246,8 +341,11 @@ pub(crate) fn wrap_unbounded_log<T: Send + std::fmt::Debug + 'static>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_unbounded_impl(inner, source, label, sample, |msg| {
+        Some(format!("{:?}", msg))
+    })
 }
 
 use crate::Instrument;
@@ -261,12 +359,13 @@ impl<T: Send + 'static> Instrument
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
         // Crossbeam uses the same Sender/Receiver types for both bounded and unbounded
         // We check the capacity to determine which type it is
         match self.0.capacity() {
-            Some(capacity) => wrap_bounded(self, source, label, capacity),
-            None => wrap_unbounded(self, source, label),
+            Some(capacity) => wrap_bounded(self, source, label, capacity, sample),
+            None => wrap_unbounded(self, source, label, sample),
         }
     }
 }
@@ -282,12 +381,13 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
         // Crossbeam uses the same Sender/Receiver types for both bounded and unbounded
         // We check the capacity to determine which type it is
         match self.0.capacity() {
-            Some(capacity) => wrap_bounded_log(self, source, label, capacity),
-            None => wrap_unbounded_log(self, source, label),
+            Some(capacity) => wrap_bounded_log(self, source, label, capacity, sample),
+            None => wrap_unbounded_log(self, source, label, sample),
         }
     }
 }
@@ -3,10 +3,13 @@ use futures_channel::mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender
 use futures_channel::oneshot;
 use futures_util::sink::SinkExt;
 use std::mem;
-use std::sync::atomic::Ordering;
 
+use crate::sizers::Sizer;
+use crate::wrappers::{channel_origin, op_timer, Delivery, EventBuffer, Registration, Sampler};
 use crate::RT;
-use crate::{init_channels_state, ChannelEvent, ChannelType, CHANNEL_ID_COUNTER};
+use crate::{
+    check_label, init_channels_state, next_channel_id, ChannelEvent, ChannelState, ChannelType,
+};
 
 /// Internal implementation for wrapping bounded futures channels with optional logging.
 fn wrap_channel_impl<T, F>(
@@ -14,6 +17,7 @@ fn wrap_channel_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
     mut get_msg_log: F,
 ) -> (Sender<T>, Receiver<T>)
 where
@@ -22,6 +26,8 @@ where
 {
     let (mut inner_tx, mut inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, mut to_inner_rx) = mpsc::channel::<T>(capacity);
     let (mut from_inner_tx, outer_rx) = mpsc::channel::<T>(capacity);
@@ -29,7 +35,10 @@ where
     let (stats_tx, _) = init_channels_state();
 
     // Generate unique ID for this channel
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -38,32 +47,53 @@ where
         channel_type: ChannelType::Bounded(capacity),
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, mut close_signal_rx) = tokio::sync::oneshot::channel::<()>();
 
     // Forward outer -> inner (proxy the send path)
     RT.spawn(async move {
+        let _registration = send_registration;
         use futures_util::stream::StreamExt;
         loop {
             tokio::select! {
                 msg = to_inner_rx.next() => {
                     match msg {
                         Some(msg) => {
-                            let log = get_msg_log(&msg);
+                            let sampled = send_sampler.sample();
+                            let log = if sampled { get_msg_log(&msg) } else { None };
+                            let size = if sampled { send_sizer.size(&msg) } else { None };
+                            let send_started = std::time::Instant::now();
+                            let op_started = op_timer(sampled);
                             if inner_tx.send(msg).await.is_err() {
+                                let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                                    id,
+                                    reported: false,
+                                    size: None,
+                                });
                                 to_inner_rx.close();
                                 break;
                             }
-                            let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                                id,
-                                log,
-                                timestamp: std::time::Instant::now(),
-                            });
+                            if sampled {
+                                let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                                    id,
+                                    log,
+                                    size,
+                                    send_wait: Some(send_started.elapsed()),
+                                    op_duration: op_started.map(|started| started.elapsed()),
+                                    timestamp: std::time::Instant::now(),
+                                });
+                            }
                         }
                         None => break, // Outer sender dropped
                     }
@@ -81,16 +111,40 @@ where
 
     // Forward inner -> outer (proxy the recv path)
     RT.spawn(async move {
+        let _registration = recv_registration;
         use futures_util::stream::StreamExt;
         while let Some(msg) = inner_rx.next().await {
-            if from_inner_tx.send(msg).await.is_ok() {
-                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: std::time::Instant::now(),
-                });
+            let sampled = recv_sampler.sample();
+            let size = if sampled { recv_sizer.size(&msg) } else { None };
+            let op_started = op_timer(sampled);
+            let delivery = match from_inner_tx.try_send(msg) {
+                Ok(()) => Delivery::Delivered,
+                Err(e) if e.is_full() => match from_inner_tx.send(e.into_inner()).await {
+                    Ok(()) => Delivery::Delivered,
+                    Err(_) => Delivery::Discarded,
+                },
+                Err(_) => Delivery::SendFailed,
+            };
+            if matches!(delivery, Delivery::Delivered) {
+                if sampled {
+                    let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                        id,
+                        size,
+                        op_duration: op_started.map(|started| started.elapsed()),
+                        timestamp: std::time::Instant::now(),
+                    });
+                }
             } else {
+                if matches!(delivery, Delivery::SendFailed) {
+                    let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
+                        id,
+                        reported: sampled,
+                        size,
+                    });
+                }
                 // Outer receiver was closed
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                 break;
             }
         }
@@ -108,8 +162,9 @@ pub(crate) fn wrap_channel<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, capacity, |_| None)
+    wrap_channel_impl(inner, source, label, capacity, sample, |_| None)
 }
 
 /// Wrap a bounded futures channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -118,8 +173,9 @@ pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, capacity, |msg| {
+    wrap_channel_impl(inner, source, label, capacity, sample, |msg| {
         Some(format!("{:?}", msg))
     })
 }
@@ -129,6 +185,7 @@ fn wrap_unbounded_impl<T, F>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
     mut get_msg_log: F,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>)
 where
@@ -137,6 +194,8 @@ where
 {
     let (inner_tx, mut inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, mut to_inner_rx) = mpsc::unbounded::<T>();
     let (from_inner_tx, outer_rx) = mpsc::unbounded::<T>();
@@ -144,7 +203,10 @@ where
     let (stats_tx, _) = init_channels_state();
 
     // Generate unique ID for this channel
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -153,32 +215,52 @@ where
         channel_type: ChannelType::Unbounded,
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, mut close_signal_rx) = tokio::sync::oneshot::channel::<()>();
 
     // Forward outer -> inner (proxy the send path)
     RT.spawn(async move {
+        let _registration = send_registration;
         use futures_util::stream::StreamExt;
         loop {
             tokio::select! {
                 msg = to_inner_rx.next() => {
                     match msg {
                         Some(msg) => {
-                            let log = get_msg_log(&msg);
+                            let sampled = send_sampler.sample();
+                            let log = if sampled { get_msg_log(&msg) } else { None };
+                            let size = if sampled { send_sizer.size(&msg) } else { None };
+                            let op_started = op_timer(sampled);
                             if inner_tx.unbounded_send(msg).is_err() {
+                                let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                                    id,
+                                    reported: false,
+                                    size: None,
+                                });
                                 to_inner_rx.close();
                                 break;
                             }
-                            let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                                id,
-                                log,
-                                timestamp: std::time::Instant::now(),
-                            });
+                            if sampled {
+                                let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                                    id,
+                                    log,
+                                    size,
+                                    send_wait: None,
+                                    op_duration: op_started.map(|started| started.elapsed()),
+                                    timestamp: std::time::Instant::now(),
+                                });
+                            }
                         }
                         None => break, // Outer sender dropped
                     }
@@ -196,16 +278,30 @@ where
 
     // Forward inner -> outer (proxy the recv path)
     RT.spawn(async move {
+        let _registration = recv_registration;
         use futures_util::stream::StreamExt;
         while let Some(msg) = inner_rx.next().await {
+            let sampled = recv_sampler.sample();
+            let size = if sampled { recv_sizer.size(&msg) } else { None };
+            let op_started = op_timer(sampled);
             if from_inner_tx.unbounded_send(msg).is_ok() {
-                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                if sampled {
+                    let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                        id,
+                        size,
+                        op_duration: op_started.map(|started| started.elapsed()),
+                        timestamp: std::time::Instant::now(),
+                    });
+                }
+            } else {
+                let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
                     id,
-                    timestamp: std::time::Instant::now(),
+                    reported: sampled,
+                    size,
                 });
-            } else {
                 // Outer receiver was closed
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                 break;
             }
         }
@@ -221,8 +317,9 @@ pub(crate) fn wrap_unbounded<T: Send + 'static>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |_| None)
+    wrap_unbounded_impl(inner, source, label, sample, |_| None)
 }
 
 /// Wrap an unbounded futures channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -230,8 +327,11 @@ pub(crate) fn wrap_unbounded_log<T: Send + std::fmt::Debug + 'static>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_unbounded_impl(inner, source, label, sample, |msg| {
+        Some(format!("{:?}", msg))
+    })
 }
 
 /// Internal implementation for wrapping oneshot futures channels with optional logging.
@@ -247,6 +347,8 @@ where
 {
     let (inner_tx, inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, outer_rx_proxy) = oneshot::channel::<T>();
     let (inner_tx_proxy, outer_rx) = oneshot::channel::<T>();
@@ -254,7 +356,8 @@ where
     let (stats_tx, _) = init_channels_state();
 
     // Generate unique ID for this channel
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -263,8 +366,14 @@ where
         channel_type: ChannelType::Oneshot,
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: 1,
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
     let stats_tx_send = stats_tx.clone();
     let stats_tx_recv = stats_tx;
 
@@ -273,24 +382,29 @@ where
 
     // Monitor outer receiver and drop inner receiver when outer is dropped
     RT.spawn(async move {
+        let _registration = recv_registration;
         let mut inner_rx = Some(inner_rx);
-        let mut message_received = false;
-        tokio::select! {
+        let state = tokio::select! {
             msg = async { inner_rx.take().unwrap().await }, if inner_rx.is_some() => {
                 // Message received from inner
                 match msg {
                     Ok(msg) => {
+                        let size = recv_sizer.size(&msg);
+                        let op_started = op_timer(true);
                         if inner_tx_proxy.send(msg).is_ok() {
                             let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
                                 id,
+                                size,
+                                op_duration: op_started.map(|started| started.elapsed()),
                                 timestamp: std::time::Instant::now(),
                             });
-                            message_received = true;
+                            ChannelState::Received
+                        } else {
+                            ChannelState::ReceiverDropped
                         }
                     }
-                    Err(_) => {
-                        // Inner sender was dropped without sending
-                    }
+                    // Inner sender was dropped without sending
+                    Err(_) => ChannelState::SenderDropped,
                 }
             }
             _ = async {
@@ -305,44 +419,51 @@ where
                 // Outer receiver was dropped - drop inner_rx to make sends fail
                 drop(inner_rx);
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
+                ChannelState::ReceiverDropped
             }
-        }
-        // Only send Closed if message was not successfully received
-        if !message_received {
-            let _ = stats_tx_recv.send(ChannelEvent::Closed { id });
-        }
+        };
+        let _ = stats_tx_recv.send(ChannelEvent::OneshotState { id, state });
     });
 
     // Forward outer -> inner (proxy the send path)
     RT.spawn(async move {
-        let mut message_sent = false;
-        tokio::select! {
+        let _registration = send_registration;
+        let state = tokio::select! {
             msg = outer_rx_proxy => {
                 match msg {
                     Ok(msg) => {
                         let log = get_msg_log(&msg);
+                        let size = send_sizer.size(&msg);
+                        let op_started = op_timer(true);
                         if inner_tx.send(msg).is_ok() {
                             let _ = stats_tx_send.send(ChannelEvent::MessageSent {
                                 id,
                                 log,
+                                size,
+                                send_wait: None,
+                                op_duration: op_started.map(|started| started.elapsed()),
                                 timestamp: std::time::Instant::now(),
                             });
-                            let _ = stats_tx_send.send(ChannelEvent::Notified { id });
-                            message_sent = true;
+                            Some(ChannelState::Sent)
+                        } else {
+                            let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                                id,
+                                reported: false,
+                                size: None,
+                            });
+                            Some(ChannelState::ReceiverDropped)
                         }
                     }
-                    Err(_) => {
-                        // Outer sender was dropped without sending
-                    }
+                    // Outer sender was dropped without sending
+                    Err(_) => Some(ChannelState::SenderDropped),
                 }
             }
-            _ = &mut close_signal_rx => {
-                // Outer receiver was closed/dropped before send
-            }
-        }
-        // Only send Closed if message was not successfully sent
-        if !message_sent {
-            let _ = stats_tx_send.send(ChannelEvent::Closed { id });
+            // Outer receiver was closed/dropped before send, reported by the receive side
+            _ = &mut close_signal_rx => None,
+        };
+        if let Some(state) = state {
+            let _ = stats_tx_send.send(ChannelEvent::OneshotState { id, state });
         }
     });
 
@@ -384,11 +505,12 @@ impl<T: Send + 'static> Instrument
         source: &'static str,
         label: Option<String>,
         capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
         if capacity.is_none() {
             panic!("Capacity is required for bounded futures channels, because they don't expose their capacity in a public API");
         }
-        wrap_channel(self, source, label, capacity.unwrap())
+        wrap_channel(self, source, label, capacity.unwrap(), sample)
     }
 }
 
@@ -407,8 +529,9 @@ impl<T: Send + 'static> Instrument
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_unbounded(self, source, label)
+        wrap_unbounded(self, source, label, sample)
     }
 }
 
@@ -427,6 +550,7 @@ impl<T: Send + 'static> Instrument
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        _sample: u64,
     ) -> Self::Output {
         wrap_oneshot(self, source, label)
     }
@@ -449,11 +573,12 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
         if capacity.is_none() {
             panic!("Capacity is required for bounded futures channels, because they don't expose their capacity in a public API");
         }
-        wrap_channel_log(self, source, label, capacity.unwrap())
+        wrap_channel_log(self, source, label, capacity.unwrap(), sample)
     }
 }
 
@@ -472,8 +597,9 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_unbounded_log(self, source, label)
+        wrap_unbounded_log(self, source, label, sample)
     }
 }
 
@@ -492,6 +618,7 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        _sample: u64,
     ) -> Self::Output {
         wrap_oneshot_log(self, source, label)
     }
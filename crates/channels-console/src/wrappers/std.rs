@@ -1,8 +1,9 @@
 use std::mem;
-use std::sync::atomic::Ordering;
 use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
 
-use crate::{init_channels_state, ChannelEvent, ChannelType, CHANNEL_ID_COUNTER};
+use crate::sizers::Sizer;
+use crate::wrappers::{channel_origin, op_timer, Delivery, EventBuffer, Registration, Sampler};
+use crate::{check_label, init_channels_state, next_channel_id, ChannelEvent, ChannelType};
 
 /// Internal implementation for wrapping bounded std channels with optional logging.
 fn wrap_sync_channel_impl<T, F>(
@@ -10,6 +11,7 @@ fn wrap_sync_channel_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
     mut log_on_send: F,
 ) -> (SyncSender<T>, Receiver<T>)
 where
@@ -18,6 +20,8 @@ where
 {
     let (inner_tx, inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, to_inner_rx) = mpsc::sync_channel::<T>(capacity);
     let (from_inner_tx, outer_rx) = mpsc::sync_channel::<T>(capacity);
@@ -25,7 +29,10 @@ where
     let (stats_tx, _) = init_channels_state();
 
     // Generate unique ID for this channel
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -34,16 +41,23 @@ where
         channel_type: ChannelType::Bounded(capacity),
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, close_signal_rx) = mpsc::channel::<()>();
 
     // Forward outer -> inner (proxy the send path)
     std::thread::spawn(move || {
+        let _registration = send_registration;
         loop {
             // Check for close signal (non-blocking)
             match close_signal_rx.try_recv() {
@@ -63,16 +77,30 @@ where
             // Try to receive with timeout to periodically check close signal
             match to_inner_rx.recv_timeout(std::time::Duration::from_millis(10)) {
                 Ok(msg) => {
-                    let log = log_on_send(&msg);
+                    let sampled = send_sampler.sample();
+                    let log = if sampled { log_on_send(&msg) } else { None };
+                    let size = if sampled { send_sizer.size(&msg) } else { None };
+                    let send_started = std::time::Instant::now();
+                    let op_started = op_timer(sampled);
                     if inner_tx.send(msg).is_err() {
+                        let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                            id,
+                            reported: false,
+                            size: None,
+                        });
                         // Inner receiver dropped
                         break;
                     }
-                    let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                        id,
-                        log,
-                        timestamp: std::time::Instant::now(),
-                    });
+                    if sampled {
+                        let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                            id,
+                            log,
+                            size,
+                            send_wait: Some(send_started.elapsed()),
+                            op_duration: op_started.map(|started| started.elapsed()),
+                            timestamp: std::time::Instant::now(),
+                        });
+                    }
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
                     // No message, loop again to check close signal
@@ -90,16 +118,40 @@ where
 
     // Forward inner -> outer (proxy the recv path)
     std::thread::spawn(move || {
+        let _registration = recv_registration;
         while let Ok(msg) = inner_rx.recv() {
-            if from_inner_tx.send(msg).is_err() {
+            let sampled = recv_sampler.sample();
+            let size = if sampled { recv_sizer.size(&msg) } else { None };
+            let op_started = op_timer(sampled);
+            let delivery = match from_inner_tx.try_send(msg) {
+                Ok(()) => Delivery::Delivered,
+                Err(mpsc::TrySendError::Full(msg)) => match from_inner_tx.send(msg) {
+                    Ok(()) => Delivery::Delivered,
+                    Err(_) => Delivery::Discarded,
+                },
+                Err(mpsc::TrySendError::Disconnected(_)) => Delivery::SendFailed,
+            };
+            if !matches!(delivery, Delivery::Delivered) {
+                if matches!(delivery, Delivery::SendFailed) {
+                    let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
+                        id,
+                        reported: sampled,
+                        size,
+                    });
+                }
                 // Outer receiver was closed
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                 break;
             }
-            let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
-                id,
-                timestamp: std::time::Instant::now(),
-            });
+            if sampled {
+                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                    id,
+                    size,
+                    op_duration: op_started.map(|started| started.elapsed()),
+                    timestamp: std::time::Instant::now(),
+                });
+            }
         }
         // Channel is closed (either inner sender dropped or outer receiver closed)
         let _ = stats_tx_recv.send(ChannelEvent::Closed { id });
@@ -115,8 +167,9 @@ pub(crate) fn wrap_sync_channel<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
 ) -> (SyncSender<T>, Receiver<T>) {
-    wrap_sync_channel_impl(inner, source, label, capacity, |_| None)
+    wrap_sync_channel_impl(inner, source, label, capacity, sample, |_| None)
 }
 
 /// Wrap a bounded std channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -125,8 +178,9 @@ pub(crate) fn wrap_sync_channel_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
+    sample: u64,
 ) -> (SyncSender<T>, Receiver<T>) {
-    wrap_sync_channel_impl(inner, source, label, capacity, |msg| {
+    wrap_sync_channel_impl(inner, source, label, capacity, sample, |msg| {
         Some(format!("{:?}", msg))
     })
 }
@@ -136,6 +190,7 @@ fn wrap_channel_impl<T, F>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
     mut log_on_send: F,
 ) -> (Sender<T>, Receiver<T>)
 where
@@ -144,6 +199,8 @@ where
 {
     let (inner_tx, inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, to_inner_rx) = mpsc::channel::<T>();
     let (from_inner_tx, outer_rx) = mpsc::channel::<T>();
@@ -151,7 +208,10 @@ where
     let (stats_tx, _) = init_channels_state();
 
     // Generate unique ID for this channel
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -160,16 +220,23 @@ where
         channel_type: ChannelType::Unbounded,
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, close_signal_rx) = mpsc::channel::<()>();
 
     // Forward outer -> inner (proxy the send path)
     std::thread::spawn(move || {
+        let _registration = send_registration;
         loop {
             // Check for close signal (non-blocking)
             match close_signal_rx.try_recv() {
@@ -189,16 +256,29 @@ where
             // Try to receive with timeout to periodically check close signal
             match to_inner_rx.recv_timeout(std::time::Duration::from_millis(10)) {
                 Ok(msg) => {
-                    let log = log_on_send(&msg);
+                    let sampled = send_sampler.sample();
+                    let log = if sampled { log_on_send(&msg) } else { None };
+                    let size = if sampled { send_sizer.size(&msg) } else { None };
+                    let op_started = op_timer(sampled);
                     if inner_tx.send(msg).is_err() {
+                        let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                            id,
+                            reported: false,
+                            size: None,
+                        });
                         // Inner receiver dropped
                         break;
                     }
-                    let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                        id,
-                        log,
-                        timestamp: std::time::Instant::now(),
-                    });
+                    if sampled {
+                        let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                            id,
+                            log,
+                            size,
+                            send_wait: None,
+                            op_duration: op_started.map(|started| started.elapsed()),
+                            timestamp: std::time::Instant::now(),
+                        });
+                    }
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
                     // No message, loop again to check close signal
@@ -216,16 +296,30 @@ where
 
     // Forward inner -> outer (proxy the recv path)
     std::thread::spawn(move || {
+        let _registration = recv_registration;
         while let Ok(msg) = inner_rx.recv() {
+            let sampled = recv_sampler.sample();
+            let size = if sampled { recv_sizer.size(&msg) } else { None };
+            let op_started = op_timer(sampled);
             if from_inner_tx.send(msg).is_err() {
+                let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
+                    id,
+                    reported: sampled,
+                    size,
+                });
                 // Outer receiver was closed
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                 break;
             }
-            let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
-                id,
-                timestamp: std::time::Instant::now(),
-            });
+            if sampled {
+                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                    id,
+                    size,
+                    op_duration: op_started.map(|started| started.elapsed()),
+                    timestamp: std::time::Instant::now(),
+                });
+            }
         }
         // Channel is closed (either inner sender dropped or outer receiver closed)
         let _ = stats_tx_recv.send(ChannelEvent::Closed { id });
@@ -239,8 +333,9 @@ pub(crate) fn wrap_channel<T: Send + 'static>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |_| None)
+    wrap_channel_impl(inner, source, label, sample, |_| None)
 }
 
 /// Wrap an unbounded std channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -248,8 +343,11 @@ pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_channel_impl(inner, source, label, sample, |msg| {
+        Some(format!("{:?}", msg))
+    })
 }
 
 use crate::Instrument;
@@ -261,8 +359,9 @@ impl<T: Send + 'static> Instrument for (std::sync::mpsc::Sender<T>, std::sync::m
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_channel(self, source, label)
+        wrap_channel(self, source, label, sample)
     }
 }
 
@@ -275,11 +374,12 @@ impl<T: Send + 'static> Instrument
         source: &'static str,
         label: Option<String>,
         capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
         if capacity.is_none() {
             panic!("Capacity is required for bounded std channels, because they don't expose their capacity in a public API");
         }
-        wrap_sync_channel(self, source, label, capacity.unwrap())
+        wrap_sync_channel(self, source, label, capacity.unwrap(), sample)
     }
 }
 
@@ -294,8 +394,9 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_channel_log(self, source, label)
+        wrap_channel_log(self, source, label, sample)
     }
 }
 
@@ -308,10 +409,11 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
         if capacity.is_none() {
             panic!("Capacity is required for bounded std channels, because they don't expose their capacity in a public API");
         }
-        wrap_sync_channel_log(self, source, label, capacity.unwrap())
+        wrap_sync_channel_log(self, source, label, capacity.unwrap(), sample)
     }
 }
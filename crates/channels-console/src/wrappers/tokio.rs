@@ -1,17 +1,61 @@
 use std::mem;
-use std::sync::atomic::Ordering;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender};
 use tokio::sync::oneshot;
 
+use crate::send_timeout;
+use crate::sizers::Sizer;
+use crate::wrappers::{channel_origin, op_timer, Delivery, EventBuffer, Registration, Sampler};
 use crate::RT;
-use crate::{init_channels_state, ChannelEvent, ChannelType, CHANNEL_ID_COUNTER};
+use crate::{
+    check_label, init_channels_state, next_channel_id, ChannelEvent, ChannelState, ChannelType,
+};
+
+/// How often the send forwarders check for sender clones and outstanding permits.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Sender handles of a channel, polled since clones of the native sender can't be observed.
+struct SenderCount {
+    active: u64,
+    created: u64,
+}
+
+impl SenderCount {
+    fn new() -> Self {
+        Self {
+            active: 1,
+            created: 1,
+        }
+    }
+
+    /// Record the current number of live senders, returns whether it changed.
+    fn update(&mut self, active: usize) -> bool {
+        let active = active as u64;
+        if active == self.active {
+            return false;
+        }
+        self.created += active.saturating_sub(self.active);
+        self.active = active;
+        true
+    }
+
+    fn event(&self, id: u64) -> ChannelEvent {
+        ChannelEvent::Senders {
+            id,
+            active: self.active,
+            created: self.created,
+        }
+    }
+}
 
 /// Internal implementation for wrapping bounded Tokio channels with optional logging.
 fn wrap_channel_impl<T, F>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
     mut log_on_send: F,
 ) -> (Sender<T>, Receiver<T>)
 where
@@ -20,14 +64,23 @@ where
 {
     let (inner_tx, mut inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
-
-    let capacity = inner_tx.capacity();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
+
+    // Messages sent before instrumenting take up capacity, so take the one it was created with
+    let capacity = inner_tx.max_capacity();
+    // The outer sender keeps the full capacity so permits and `reserve_many` work as on the
+    // original channel. Messages the consumer hasn't picked up yet wait in the observed inner
+    // channel instead of a second queue behind it, so the outer receiver holds just one.
     let (outer_tx, mut to_inner_rx) = mpsc::channel::<T>(capacity);
-    let (from_inner_tx, outer_rx) = mpsc::channel::<T>(capacity);
+    let (from_inner_tx, outer_rx) = mpsc::channel::<T>(1);
 
     let (stats_tx, _) = init_channels_state();
 
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -36,31 +89,75 @@ where
         channel_type: ChannelType::Bounded(capacity),
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+    send_timeout::register_sender(id, &outer_tx);
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, mut close_signal_rx) = oneshot::channel::<()>();
 
+    // Permits and sender clones are taken from the outer channel without the forwarders
+    // noticing, so the send forwarder polls how many of its slots are held without a
+    // message and how many senders are alive
+    let weak_outer_tx = outer_tx.downgrade();
+
     // Forward outer -> inner (proxy the send path)
     RT.spawn(async move {
+        let _registration = send_registration;
+        let mut poll_tick = tokio::time::interval(POLL_INTERVAL);
+        poll_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut reserved = 0;
+        let mut senders = SenderCount::new();
+        let _ = stats_tx_send.send(senders.event(id));
         loop {
             tokio::select! {
+                _ = poll_tick.tick() => {
+                    let now_reserved = weak_outer_tx.upgrade().map_or(0, |tx| {
+                        (capacity - tx.capacity()).saturating_sub(to_inner_rx.len()) as u64
+                    });
+                    if now_reserved != reserved {
+                        reserved = now_reserved;
+                        let _ = stats_tx_send.send(ChannelEvent::Reserved { id, reserved });
+                    }
+                    if senders.update(weak_outer_tx.strong_count()) {
+                        let _ = stats_tx_send.send(senders.event(id));
+                    }
+                }
                 msg = to_inner_rx.recv() => {
                     match msg {
                         Some(msg) => {
-                            let log = log_on_send(&msg);
+                            let sampled = send_sampler.sample();
+                            let log = if sampled { log_on_send(&msg) } else { None };
+                            let size = if sampled { send_sizer.size(&msg) } else { None };
+                            let send_started = std::time::Instant::now();
+                            let op_started = op_timer(sampled);
                             if inner_tx.send(msg).await.is_err() {
+                                let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                                    id,
+                                    reported: false,
+                                    size: None,
+                                });
                                 to_inner_rx.close();
                                 break;
                             }
-                            let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                                id,
-                                log,
-                                timestamp: std::time::Instant::now(),
-                            });
+                            if sampled {
+                                let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                                    id,
+                                    log,
+                                    size,
+                                    send_wait: Some(send_started.elapsed()),
+                                    op_duration: op_started.map(|started| started.elapsed()),
+                                    timestamp: std::time::Instant::now(),
+                                });
+                            }
                         }
                         None => break, // Outer sender dropped
                     }
@@ -72,24 +169,56 @@ where
                 }
             }
         }
+        if reserved != 0 {
+            let _ = stats_tx_send.send(ChannelEvent::Reserved { id, reserved: 0 });
+        }
+        if senders.update(weak_outer_tx.strong_count()) {
+            let _ = stats_tx_send.send(senders.event(id));
+        }
         // Channel is closed
         let _ = stats_tx_send.send(ChannelEvent::Closed { id });
     });
 
     // Forward inner -> outer (proxy the recv path)
     RT.spawn(async move {
+        let _registration = recv_registration;
         loop {
             tokio::select! {
                 msg = inner_rx.recv() => {
                     match msg {
                         Some(msg) => {
-                            if from_inner_tx.send(msg).await.is_ok() {
-                                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
-                                    id,
-                                    timestamp: std::time::Instant::now(),
-                                });
+                            let sampled = recv_sampler.sample();
+                            let size = if sampled { recv_sizer.size(&msg) } else { None };
+                            let op_started = op_timer(sampled);
+                            let delivery = match from_inner_tx.try_send(msg) {
+                                Ok(()) => Delivery::Delivered,
+                                Err(TrySendError::Full(msg)) => {
+                                    match from_inner_tx.send(msg).await {
+                                        Ok(()) => Delivery::Delivered,
+                                        Err(_) => Delivery::Discarded,
+                                    }
+                                }
+                                Err(TrySendError::Closed(_)) => Delivery::SendFailed,
+                            };
+                            if matches!(delivery, Delivery::Delivered) {
+                                if sampled {
+                                    let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                                        id,
+                                        size,
+                                        op_duration: op_started.map(|started| started.elapsed()),
+                                        timestamp: std::time::Instant::now(),
+                                    });
+                                }
                             } else {
+                                if matches!(delivery, Delivery::SendFailed) {
+                                    let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
+                                        id,
+                                        reported: sampled,
+                                        size,
+                                    });
+                                }
                                 let _ = close_signal_tx.send(());
+                                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                                 break;
                             }
                         }
@@ -99,6 +228,7 @@ where
                 _ = from_inner_tx.closed() => {
                     // Outer receiver was closed/dropped
                     let _ = close_signal_tx.send(());
+                    let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                     break;
                 }
             }
@@ -116,8 +246,9 @@ pub(crate) fn wrap_channel<T: Send + 'static>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |_| None)
+    wrap_channel_impl(inner, source, label, sample, |_| None)
 }
 
 /// Wrap a bounded Tokio channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -125,8 +256,11 @@ pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_channel_impl(inner, source, label, sample, |msg| {
+        Some(format!("{:?}", msg))
+    })
 }
 
 /// Internal implementation for wrapping unbounded Tokio channels with optional logging.
@@ -134,6 +268,7 @@ fn wrap_unbounded_impl<T, F>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
     mut log_on_send: F,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>)
 where
@@ -142,13 +277,18 @@ where
 {
     let (inner_tx, mut inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, mut to_inner_rx) = mpsc::unbounded_channel::<T>();
     let (from_inner_tx, outer_rx) = mpsc::unbounded_channel::<T>();
 
     let (stats_tx, _) = init_channels_state();
 
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
+
+    let (mut send_sampler, mut recv_sampler) = Sampler::pair(id, sample);
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -157,31 +297,63 @@ where
         channel_type: ChannelType::Unbounded,
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: send_sampler.rate(),
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
-    let stats_tx_send = stats_tx.clone();
-    let stats_tx_recv = stats_tx.clone();
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
+    let stats_tx_send = EventBuffer::new(stats_tx.clone());
+    let stats_tx_recv = stats_tx_send.clone();
 
     // Create a signal channel to notify send-forwarder when outer_rx is closed
     let (close_signal_tx, mut close_signal_rx) = oneshot::channel::<()>();
 
+    // Sender clones can't be observed, the send forwarder polls how many are alive
+    let weak_outer_tx = outer_tx.downgrade();
+
     // Forward outer -> inner (proxy the send path)
     RT.spawn(async move {
+        let _registration = send_registration;
+        let mut poll_tick = tokio::time::interval(POLL_INTERVAL);
+        poll_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut senders = SenderCount::new();
+        let _ = stats_tx_send.send(senders.event(id));
         loop {
             tokio::select! {
+                _ = poll_tick.tick() => {
+                    if senders.update(weak_outer_tx.strong_count()) {
+                        let _ = stats_tx_send.send(senders.event(id));
+                    }
+                }
                 msg = to_inner_rx.recv() => {
                     match msg {
                         Some(msg) => {
-                            let log = log_on_send(&msg);
+                            let sampled = send_sampler.sample();
+                            let log = if sampled { log_on_send(&msg) } else { None };
+                            let size = if sampled { send_sizer.size(&msg) } else { None };
+                            let op_started = op_timer(sampled);
                             if inner_tx.send(msg).is_err() {
+                                let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                                    id,
+                                    reported: false,
+                                    size: None,
+                                });
                                 to_inner_rx.close();
                                 break;
                             }
-                            let _ = stats_tx_send.send(ChannelEvent::MessageSent {
-                                id,
-                                log,
-                                timestamp: std::time::Instant::now(),
-                            });
+                            if sampled {
+                                let _ = stats_tx_send.send(ChannelEvent::MessageSent {
+                                    id,
+                                    log,
+                                    size,
+                                    send_wait: None,
+                                    op_duration: op_started.map(|started| started.elapsed()),
+                                    timestamp: std::time::Instant::now(),
+                                });
+                            }
                         }
                         None => break, // Outer sender dropped
                     }
@@ -193,25 +365,42 @@ where
                 }
             }
         }
+        if senders.update(weak_outer_tx.strong_count()) {
+            let _ = stats_tx_send.send(senders.event(id));
+        }
         // Channel is closed
         let _ = stats_tx_send.send(ChannelEvent::Closed { id });
     });
 
     // Forward inner -> outer (proxy the recv path)
     RT.spawn(async move {
+        let _registration = recv_registration;
         loop {
             tokio::select! {
                 msg = inner_rx.recv() => {
                     match msg {
                         Some(msg) => {
+                            let sampled = recv_sampler.sample();
+                            let size = if sampled { recv_sizer.size(&msg) } else { None };
+                            let op_started = op_timer(sampled);
                             if from_inner_tx.send(msg).is_ok() {
-                                let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                                if sampled {
+                                    let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
+                                        id,
+                                        size,
+                                        op_duration: op_started.map(|started| started.elapsed()),
+                                        timestamp: std::time::Instant::now(),
+                                    });
+                                }
+                            } else {
+                                let _ = stats_tx_recv.send(ChannelEvent::SendFailed {
                                     id,
-                                    timestamp: std::time::Instant::now(),
+                                    reported: sampled,
+                                    size,
                                 });
-                            } else {
                                 // Outer receiver was closed
                                 let _ = close_signal_tx.send(());
+                                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                                 break;
                             }
                         }
@@ -221,6 +410,7 @@ where
                 _ = from_inner_tx.closed() => {
                     // Outer receiver was closed/dropped
                     let _ = close_signal_tx.send(());
+                    let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
                     break;
                 }
             }
@@ -237,8 +427,9 @@ pub(crate) fn wrap_unbounded<T: Send + 'static>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |_| None)
+    wrap_unbounded_impl(inner, source, label, sample, |_| None)
 }
 
 /// Wrap an unbounded Tokio channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -246,8 +437,11 @@ pub(crate) fn wrap_unbounded_log<T: Send + std::fmt::Debug + 'static>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
+    sample: u64,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_unbounded_impl(inner, source, label, sample, |msg| {
+        Some(format!("{:?}", msg))
+    })
 }
 
 /// Internal implementation for wrapping oneshot Tokio channels with optional logging.
@@ -263,13 +457,16 @@ where
 {
     let (inner_tx, inner_rx) = inner;
     let type_name = std::any::type_name::<T>();
+    let send_sizer = Sizer::<T>::lookup();
+    let recv_sizer = send_sizer.clone();
 
     let (outer_tx, outer_rx_proxy) = oneshot::channel::<T>();
     let (mut inner_tx_proxy, outer_rx) = oneshot::channel::<T>();
 
     let (stats_tx, _) = init_channels_state();
 
-    let id = CHANNEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    check_label(source, label.as_deref());
+    let id = next_channel_id();
 
     let _ = stats_tx.send(ChannelEvent::Created {
         id,
@@ -278,8 +475,14 @@ where
         channel_type: ChannelType::Oneshot,
         type_name,
         type_size: mem::size_of::<T>(),
+        sample_rate: 1,
+        multi_consumer: false,
+        origin: channel_origin(),
     });
 
+    let send_registration = Registration::new(id, stats_tx.clone());
+    let recv_registration = send_registration.clone();
+
     let stats_tx_send = stats_tx.clone();
     let stats_tx_recv = stats_tx;
 
@@ -288,68 +491,80 @@ where
 
     // Monitor outer receiver and drop inner receiver when outer is dropped
     RT.spawn(async move {
+        let _registration = recv_registration;
         let mut inner_rx = Some(inner_rx);
-        let mut message_received = false;
-        tokio::select! {
+        let state = tokio::select! {
             msg = async { inner_rx.take().unwrap().await }, if inner_rx.is_some() => {
                 // Message received from inner
                 match msg {
                     Ok(msg) => {
+                        let size = recv_sizer.size(&msg);
+                        let op_started = op_timer(true);
                         if inner_tx_proxy.send(msg).is_ok() {
                             let _ = stats_tx_recv.send(ChannelEvent::MessageReceived {
                                 id,
+                                size,
+                                op_duration: op_started.map(|started| started.elapsed()),
                                 timestamp: std::time::Instant::now(),
                             });
-                            message_received = true;
+                            ChannelState::Received
+                        } else {
+                            ChannelState::ReceiverDropped
                         }
                     }
-                    Err(_) => {
-                        // Inner sender was dropped without sending
-                    }
+                    // Inner sender was dropped without sending
+                    Err(_) => ChannelState::SenderDropped,
                 }
             }
             _ = inner_tx_proxy.closed() => {
                 // Outer receiver was dropped - drop inner_rx to make sends fail
                 drop(inner_rx);
                 let _ = close_signal_tx.send(());
+                let _ = stats_tx_recv.send(ChannelEvent::ReceiverDropped { id });
+                ChannelState::ReceiverDropped
             }
-        }
-        // Only send Closed if message was not successfully received
-        if !message_received {
-            let _ = stats_tx_recv.send(ChannelEvent::Closed { id });
-        }
+        };
+        let _ = stats_tx_recv.send(ChannelEvent::OneshotState { id, state });
     });
 
     // Forward outer -> inner (proxy the send path)
     RT.spawn(async move {
-        let mut message_sent = false;
-        tokio::select! {
+        let _registration = send_registration;
+        let state = tokio::select! {
             msg = outer_rx_proxy => {
                 match msg {
                     Ok(msg) => {
                         let log = log_on_send(&msg);
+                        let size = send_sizer.size(&msg);
+                        let op_started = op_timer(true);
                         if inner_tx.send(msg).is_ok() {
                             let _ = stats_tx_send.send(ChannelEvent::MessageSent {
                                 id,
                                 log,
+                                size,
+                                send_wait: None,
+                                op_duration: op_started.map(|started| started.elapsed()),
                                 timestamp: std::time::Instant::now(),
                             });
-                            let _ = stats_tx_send.send(ChannelEvent::Notified { id });
-                            message_sent = true;
+                            Some(ChannelState::Sent)
+                        } else {
+                            let _ = stats_tx_send.send(ChannelEvent::SendFailed {
+                                id,
+                                reported: false,
+                                size: None,
+                            });
+                            Some(ChannelState::ReceiverDropped)
                         }
                     }
-                    Err(_) => {
-                        // Outer sender was dropped without sending
-                    }
+                    // Outer sender was dropped without sending
+                    Err(_) => Some(ChannelState::SenderDropped),
                 }
             }
-            _ = &mut close_signal_rx => {
-                // Outer receiver was closed/dropped before send
-            }
-        }
-        // Only send Closed if message was not successfully sent
-        if !message_sent {
-            let _ = stats_tx_send.send(ChannelEvent::Closed { id });
+            // Outer receiver was closed/dropped before send, reported by the receive side
+            _ = &mut close_signal_rx => None,
+        };
+        if let Some(state) = state {
+            let _ = stats_tx_send.send(ChannelEvent::OneshotState { id, state });
         }
     });
 
@@ -383,8 +598,9 @@ impl<T: Send + 'static> Instrument for (Sender<T>, Receiver<T>) {
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_channel(self, source, label)
+        wrap_channel(self, source, label, sample)
     }
 }
 
@@ -395,8 +611,9 @@ impl<T: Send + 'static> Instrument for (UnboundedSender<T>, UnboundedReceiver<T>
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_unbounded(self, source, label)
+        wrap_unbounded(self, source, label, sample)
     }
 }
 
@@ -407,6 +624,7 @@ impl<T: Send + 'static> Instrument for (oneshot::Sender<T>, oneshot::Receiver<T>
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        _sample: u64,
     ) -> Self::Output {
         wrap_oneshot(self, source, label)
     }
@@ -421,8 +639,9 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog for (Sender<T>, Receiver
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_channel_log(self, source, label)
+        wrap_channel_log(self, source, label, sample)
     }
 }
 
@@ -435,8 +654,9 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        sample: u64,
     ) -> Self::Output {
-        wrap_unbounded_log(self, source, label)
+        wrap_unbounded_log(self, source, label, sample)
     }
 }
 
@@ -449,6 +669,7 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentLog
         source: &'static str,
         label: Option<String>,
         _capacity: Option<usize>,
+        _sample: u64,
     ) -> Self::Output {
         wrap_oneshot_log(self, source, label)
     }
@@ -112,6 +112,44 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_json_lines_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-crossbeam-test",
+                "--example",
+                "json_lines_crossbeam",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let channels: Vec<channels_console::SerializableChannelStats> = stdout
+            .lines()
+            .filter(|line| line.starts_with('{'))
+            .map(|line| serde_json::from_str(line).expect("Failed to parse JSON line"))
+            .collect();
+
+        let labels: Vec<&str> = channels.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["bounded", "unbounded"], "Got:\n{stdout}");
+
+        // Crossbeam receivers can be cloned, so the live receiver count is unknown
+        for channel in &channels {
+            assert_eq!(channel.active_receivers, None);
+        }
+    }
+
     #[test]
     fn test_iter_output() {
         let output = Command::new("cargo")
@@ -139,9 +177,9 @@ pub mod tests {
             "bounded",
             "bounded-2",
             "bounded-3",
-            "examples/iter_crossbeam.rs:16",
-            "examples/iter_crossbeam.rs:16-2",
-            "examples/iter_crossbeam.rs:16-3",
+            "iter_crossbeam@examples/iter_crossbeam.rs:18",
+            "iter_crossbeam@examples/iter_crossbeam.rs:18-2",
+            "iter_crossbeam@examples/iter_crossbeam.rs:18-3",
         ];
 
         for expected in all_expected {
@@ -29,7 +29,7 @@ pub mod tests {
             "bounded-channel",
             "oneshot-labeled",
             "bounded[10]",
-            "notified",
+            "| received |",
         ];
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -109,11 +109,11 @@ pub mod tests {
             closed_count, stdout
         );
 
-        let notified_count = stdout.matches("| notified").count();
+        let received_count = stdout.matches("| received").count();
         assert_eq!(
-            notified_count, 1,
-            "Expected 'notified' state to appear 1 time in table (oneshot), found {}.\nOutput:\n{}",
-            notified_count, stdout
+            received_count, 1,
+            "Expected 'received' state to appear 1 time in table (oneshot), found {}.\nOutput:\n{}",
+            received_count, stdout
         );
     }
 
@@ -143,7 +143,7 @@ pub mod tests {
             stderr
         );
 
-        let all_expected = ["| closed |", "oneshot-closed"];
+        let all_expected = ["| receiver_dropped |", "oneshot-closed"];
 
         for expected in all_expected {
             assert!(
@@ -203,7 +203,7 @@ pub mod tests {
             "bounded-channel",
             "oneshot-labeled",
             "bounded[10]",
-            "notified",
+            r#""state":"received""#,
         ];
         for expected in all_expected {
             assert!(
@@ -263,9 +263,9 @@ pub mod tests {
             "bounded",
             "bounded-2",
             "bounded-3",
-            "examples/iter_futures.rs:59",
-            "examples/iter_futures.rs:59-2",
-            "examples/iter_futures.rs:59-3",
+            "iter_futures@examples/iter_futures.rs:61",
+            "iter_futures@examples/iter_futures.rs:61-2",
+            "iter_futures@examples/iter_futures.rs:61-3",
         ];
 
         for expected in all_expected {
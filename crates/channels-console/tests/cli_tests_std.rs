@@ -56,7 +56,49 @@ pub mod tests {
             output.status
         );
 
-        let all_expected = ["\"label\": \"unbounded\"", "\"label\": \"bounded\""];
+        let all_expected = [
+            "\"label\": \"unbounded\"",
+            "\"label\": \"bounded\"",
+            "\"channel_type\": \"bounded[10]\"",
+            "\"kind\": \"bounded\"",
+            "\"capacity\": 10",
+            "\"kind\": \"unbounded\"",
+            "\"capacity\": null",
+            "\"runtime_seconds\": ",
+        ];
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_basic_yaml_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "basic_yaml_std",
+                "--features",
+                "yaml",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let all_expected = ["channels:", "label: unbounded", "label: bounded"];
 
         let stdout = String::from_utf8_lossy(&output.stdout);
 
@@ -68,6 +110,139 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_yaml_fallback_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "basic_yaml_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("Format::Yaml requires the `yaml` feature, printing JSON instead"),
+            "Expected the fallback warning\n\nGot:\n{stderr}",
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("No JSON output found");
+        let json: serde_json::Value =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        let labels: Vec<&str> = json["channels"]
+            .as_array()
+            .expect("channels is not an array")
+            .iter()
+            .filter_map(|channel| channel["label"].as_str())
+            .collect();
+        assert!(
+            labels.contains(&"unbounded") && labels.contains(&"bounded"),
+            "Expected both channels\n\nGot:\n{stdout}",
+        );
+    }
+
+    #[test]
+    fn test_sampled_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "sampled_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        for (label, sample_rate) in [
+            ("sampled", 10),
+            ("sampled-log", 5),
+            ("sampled-zero", 1),
+            ("sampled-zero-builder", 1),
+        ] {
+            let channel = stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found"));
+            assert_eq!(channel.sample_rate, sample_rate);
+            assert_eq!(channel.sent_count, 1000);
+            assert_eq!(channel.received_count, 1000);
+        }
+    }
+
+    #[test]
+    fn test_shutdown_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "shutdown_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("[Main] Collector shut down"));
+        assert!(
+            stdout.contains("[Main] Metrics server stopped: true"),
+            "Expected the metrics server to be stopped, got:\n{stdout}"
+        );
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "before-shutdown")
+            .expect("Channel not found");
+        assert_eq!(channel.sent_count, 3);
+        assert_eq!(channel.received_count, 3);
+    }
+
     #[test]
     fn test_closed_channels_output() {
         let output = Command::new("cargo")
@@ -245,4 +420,1862 @@ pub mod tests {
             stdout
         );
     }
+
+    #[test]
+    fn test_statsd_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "statsd_std",
+                "--features",
+                "statsd",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{stdout}",
+            output.status
+        );
+
+        assert!(
+            stdout.contains("[Agent] Total sent: 7"),
+            "Expected all sends to be pushed, got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Agent] Total received: 7"),
+            "Expected receives around the reset to be pushed once, got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("|c|#id:") && stdout.contains(",label:pushed"),
+            "Expected tagged counters, got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Agent] channels_console.queued:"),
+            "Expected queued gauge, got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_mmap_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "mmap_std",
+                "--features",
+                "mmap",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{stdout}",
+            output.status
+        );
+
+        assert!(
+            stdout.contains("[Reader] version=1 records=1"),
+            "Expected a version 1 snapshot, got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Reader] mapped sent=3 capacity=10 kind=0"),
+            "Expected the bounded channel record, got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_thread_names_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "thread_names_std",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_COLLECTOR_THREAD_NAME", "app-collector")
+            .env("CHANNELS_CONSOLE_METRICS_THREAD_NAME", "app-metrics")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in ["[Thread] app-collector", "[Thread] app-metrics"] {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}"
+            );
+        }
+        assert!(!stdout.contains("channel-stats"), "Got:\n{stdout}");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_unix_socket_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "unix_socket_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{stdout}",
+            output.status
+        );
+
+        let all_expected = [
+            "Channel metrics server listening on unix:",
+            "[Client] HTTP/1.1 200 OK",
+            "\"label\":\"over-uds\"",
+            "[Client] TCP listening: false",
+            "[Client] Socket removed: true",
+        ];
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_queue_bar_output() {
+        let run = |envs: &[(&str, &str)]| {
+            let output = Command::new("cargo")
+                .args([
+                    "run",
+                    "-p",
+                    "channels-console-std-test",
+                    "--example",
+                    "queue_bar_std",
+                    "--features",
+                    "channels-console",
+                ])
+                .env_remove("NO_COLOR")
+                .env_remove("LC_ALL")
+                .env_remove("LC_CTYPE")
+                .envs(envs.iter().copied())
+                .output()
+                .expect("Failed to execute command");
+
+            assert!(
+                output.status.success(),
+                "Command failed with status: {}",
+                output.status
+            );
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+
+        let row = |stdout: &str, label: &str| {
+            stdout
+                .lines()
+                .find(|line| line.contains(label))
+                .unwrap_or_else(|| panic!("Row {label} not found in:\n{stdout}"))
+                .to_string()
+        };
+
+        let unicode = run(&[("LANG", "en_US.UTF-8")]);
+        assert!(unicode.contains("| Load "), "Got:\n{unicode}");
+        assert!(row(&unicode, "full-queue").contains("| ████████ |"));
+        assert!(row(&unicode, "idle-queue").contains("|          |"));
+
+        let ascii = run(&[("LANG", "en_US.UTF-8"), ("NO_COLOR", "1")]);
+        assert!(row(&ascii, "full-queue").contains("| ######## |"));
+        assert!(row(&ascii, "idle-queue").contains("| ........ |"));
+        assert!(!ascii.contains('█'), "Got:\n{ascii}");
+    }
+
+    #[test]
+    fn test_thousands_separator_output() {
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .args([
+                    "run",
+                    "-p",
+                    "channels-console-std-test",
+                    "--example",
+                    "thousands_std",
+                    "--features",
+                    "channels-console",
+                    "--",
+                ])
+                .args(args)
+                .output()
+                .expect("Failed to execute command");
+
+            assert!(
+                output.status.success(),
+                "Command failed with status: {}",
+                output.status
+            );
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+
+        let default = run(&[]);
+        assert!(
+            default.contains("| 1,234 | 1,234    |"),
+            "Expected comma separated counts, got:\n{default}"
+        );
+
+        let dotted = run(&["."]);
+        assert!(
+            dotted.contains("| 1.234 | 1.234    |"),
+            "Expected dot separated counts, got:\n{dotted}"
+        );
+    }
+
+    #[test]
+    fn test_sizer_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "sizer_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let channel = |label: &str| {
+            stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found"))
+        };
+
+        let sized = channel("sized");
+        assert_eq!(sized.queued, 4);
+        assert_eq!(sized.queued_bytes, 4 * 1000);
+
+        let plain = channel("plain");
+        assert_eq!(plain.queued, 4);
+        assert_eq!(plain.queued_bytes, 4 * std::mem::size_of::<String>() as u64);
+    }
+
+    #[test]
+    fn test_idle_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "idle_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = |label: &str| {
+            stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found"))
+        };
+
+        let stale = channel("stale");
+        let busy = channel("busy");
+        assert!(
+            stale.idle_for_ms >= 300,
+            "stale idle: {}",
+            stale.idle_for_ms
+        );
+        assert!(busy.idle_for_ms < stale.idle_for_ms);
+
+        let stale_first = stale.first_sent_ns.expect("stale first_sent_ns");
+        let stale_last = stale.last_message_ns.expect("stale last_message_ns");
+        assert!(stale_first <= stale_last);
+        assert!(busy.last_message_ns.expect("busy last_message_ns") > stale_last);
+
+        let unused = channel("unused");
+        assert_eq!(unused.first_sent_ns, None);
+        assert_eq!(unused.last_message_ns, None);
+        assert_eq!(unused.drain_ratio, None);
+    }
+
+    #[test]
+    fn test_deregister_closed_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "deregister_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let labels: Vec<&str> = stats.channels.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["live"], "Got: {labels:?}");
+        assert_eq!(stats.channels[0].sent_count, 1);
+    }
+
+    #[test]
+    fn test_aggregate_duplicates_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "aggregate_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        assert_eq!(stats.channels.len(), 1, "Got: {:?}", stats.channels);
+        let worker = &stats.channels[0];
+        assert_eq!(worker.label, "worker");
+        assert_eq!(worker.instance_count, 3);
+        assert_eq!(worker.sent_count, 6);
+        assert_eq!(worker.received_count, 6);
+        assert_eq!(worker.received_bytes, 6 * 4);
+        assert_eq!(worker.received_bytes_human, "24 B");
+        assert_eq!(worker.queued_bytes_human, "0 B");
+        assert_eq!(worker.drain_ratio, Some(1.0));
+    }
+
+    #[test]
+    fn test_instrument_builder_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "builder_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        assert_eq!(stats.channels.len(), 3, "Got: {:?}", stats.channels);
+
+        let ingest = stats
+            .channels
+            .iter()
+            .find(|c| c.source == "ingest-queue")
+            .expect("Channel ingest-queue not found");
+        assert_eq!(ingest.label, "ingest");
+        assert!(ingest.has_custom_label);
+        assert_eq!(ingest.sent_count, 1);
+
+        let workers = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "workers")
+            .expect("Group workers not found");
+        assert_eq!(workers.instance_count, 2);
+        assert_eq!(workers.sent_count, 3);
+        assert_eq!(workers.received_count, 3);
+
+        let located = stats
+            .channels
+            .iter()
+            .find(|c| !c.has_custom_label)
+            .expect("Channel without label not found");
+        assert!(
+            located.source.ends_with("examples/builder_std.rs:31"),
+            "Got: {}",
+            located.source
+        );
+        assert_eq!(located.label, "examples/builder_std.rs:31");
+    }
+
+    #[test]
+    fn test_strict_labels_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "strict_labels_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        // The copy-pasted label panics, the loop sharing a label doesn't
+        assert!(!output.status.success(), "Duplicate label was accepted");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stdout.contains("[Strict] worker channels registered"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            !stdout.contains("[Strict] results channel registered"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            stderr.contains("Channel label \"jobs\" at strict_labels_std@"),
+            "Got:\n{stderr}"
+        );
+        assert!(
+            stderr.contains(
+                "examples/strict_labels_std.rs:25 is already used by the channel at \
+                 strict_labels_std@"
+            ),
+            "Got:\n{stderr}"
+        );
+    }
+
+    #[test]
+    fn test_strict_relabel_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "strict_relabel_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        // set_label checks the new label like channel! does
+        assert!(!output.status.success(), "Duplicate label was accepted");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stdout.contains("[Strict] results channel registered"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            !stdout.contains("[Strict] results channel relabeled"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            stderr.contains(
+                "examples/strict_relabel_std.rs:14 is already used by the channel at \
+                 strict_relabel_std@"
+            ),
+            "Got:\n{stderr}"
+        );
+    }
+
+    #[test]
+    fn test_preview_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "preview_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // The latest message is kept, long ones are cut to 120 characters and an ellipsis
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Preview] jobs chars=28 preview=Job { id: 3, name: \"job-3\" }"),
+            "Got:\n{stdout}"
+        );
+        let expected = format!("[Preview] logs chars=121 preview=\"{}…", "x".repeat(119));
+        assert!(stdout.contains(&expected), "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_disable_channel_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "disable_channel_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        let all_expected = [
+            "[Disable] found=true",
+            "[Disable] disabled=true sent=3 received=3",
+            "[Enable] disabled=false sent=5 received=5",
+            " (disabled) |",
+        ];
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+
+        // The other channel is still counted
+        let quiet = stdout
+            .lines()
+            .find(|line| line.contains("| quiet "))
+            .unwrap_or_else(|| panic!("No quiet row, got:\n{stdout}"));
+        assert!(quiet.contains("| 100 "), "Got: {quiet}");
+        assert!(!quiet.contains("(disabled)"), "Got: {quiet}");
+    }
+
+    #[test]
+    fn test_channel_views_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "channel_views_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        for expected in [
+            "[Views] views bounded[10] sent=5 first_sent=true sent_logs=5",
+            "[Views] max_queued_within_capacity=true",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Expected: {expected}\nGot:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_avg_queued_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "avg_queued_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        for expected in [
+            "[AvgQueued] burst below_one=true",
+            "[AvgQueued] backlog below_one=false",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Expected: {expected}\nGot:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rates_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "rates_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        assert!(
+            stdout.contains("[Rates] decaying=true below_lifetime_avg=true"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_channel_id_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "channel_id_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        let all_expected = [
+            "[Id] source=ingest-queue label=ingest-queue sent=3",
+            "[Id] source=results label=Results sent=3",
+            "| ingest-queue ",
+        ];
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+        assert!(!stdout.contains("channel_id_std.rs"), "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_total_queued_bytes_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "total_queued_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // Each stalled channel holds two u64 messages
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Total] total_queued_bytes=32 sum=32"),
+            "Got:\n{stdout}"
+        );
+        assert!(stdout.contains("\nTotal queued: 32 B\n"), "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_metrics_port_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "metrics_port_std",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6782")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Port before: None"), "Got: {stdout}");
+        assert!(stdout.contains("Port after: 6782"), "Got: {stdout}");
+    }
+
+    #[test]
+    fn test_metrics_host_ipv6_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "metrics_port_std",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_HOST", "[::1]")
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6785")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("Channel metrics server listening on http://[::1]:6785"),
+            "Got: {stdout}"
+        );
+        assert!(stdout.contains("Port after: 6785"), "Got: {stdout}");
+    }
+
+    #[test]
+    fn test_send_wait_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "send_wait_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let send_wait = stats.channels[0]
+            .send_wait
+            .as_ref()
+            .expect("No send wait recorded");
+        assert_eq!(send_wait.count, 10);
+        let bounds: Vec<f64> = send_wait.buckets.iter().map(|b| b.le).collect();
+        assert_eq!(bounds, [0.001, 1.0]);
+        // The consumer takes a message every 20ms, so most sends wait longer than 1ms
+        assert!(send_wait.buckets[0].count < 10, "Got: {send_wait:?}");
+        assert_eq!(send_wait.buckets[1].count, 10);
+        assert!(send_wait.sum_seconds > 0.05, "Got: {send_wait:?}");
+    }
+
+    #[test]
+    fn test_sort_by_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "sort_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let labels: Vec<&str> = stats.channels.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["b-three", "c-two", "a-one"]);
+    }
+
+    #[test]
+    fn test_print_interval_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "print_interval_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let snapshots: Vec<channels_console::SerializableChannelStats> = stdout
+            .lines()
+            .filter(|line| line.starts_with('{'))
+            .map(|line| serde_json::from_str(line).expect("Failed to parse JSON line"))
+            .collect();
+
+        // Periodic snapshots followed by the final one printed on drop
+        assert!(
+            snapshots.len() >= 3,
+            "Expected several snapshots, got {}:\n{}",
+            snapshots.len(),
+            stdout
+        );
+        assert!(snapshots.iter().all(|c| c.label == "periodic"));
+        assert!(snapshots
+            .windows(2)
+            .all(|pair| pair[0].sent_count <= pair[1].sent_count));
+        assert_eq!(snapshots.last().unwrap().sent_count, 5);
+    }
+
+    #[test]
+    fn test_disabled_feature_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "disabled_std",
+                "--features",
+                "disabled",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("No instrumented channels or streams found."),
+            "Expected no instrumented channels:\n{}",
+            stdout
+        );
+    }
+
+    #[test]
+    fn test_send_errors_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "send_errors_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "send-errors")
+            .expect("Channel not found");
+
+        // The message sent after the receiver was dropped is an error, not a send
+        assert_eq!(channel.sent_count, 3);
+        assert_eq!(channel.received_count, 3);
+        assert_eq!(channel.send_errors, 1);
+        assert_eq!(channel.queued, 0);
+    }
+
+    #[test]
+    fn test_snapshot_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "snapshot_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // Statistics are available without a guard, proxies forward unbounded messages
+        // to the receiver right away
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Snapshot] snapshot sent=4 received=4"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_channels_matching_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "matching_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let matching: Vec<&str> = stdout
+            .lines()
+            .filter(|line| line.starts_with("[Matching]"))
+            .collect();
+        assert_eq!(matching.len(), 1, "Got:\n{stdout}");
+        assert!(
+            matching[0].starts_with("[Matching] backlog queued="),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_zero_sized_messages_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "zst_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let row = stdout
+            .lines()
+            .find(|line| line.contains("| signals"))
+            .expect("No signals row found");
+        assert!(row.contains("| unbounded (ZST) |"), "Got:\n{stdout}");
+        assert!(row.contains("| 0 B "), "Got:\n{stdout}");
+        assert!(
+            stdout.contains("(ZST) channels carry zero-sized messages"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_summary_filters_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "filter_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("[Filter] tracked=3"), "Got:\n{stdout}");
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let labels: Vec<&str> = stats.channels.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["backlog"], "Got: {labels:?}");
+    }
+
+    #[test]
+    fn test_format_env_var_output() {
+        let run = |example: &str, format: &str| {
+            let output = Command::new("cargo")
+                .args([
+                    "run",
+                    "-p",
+                    "channels-console-std-test",
+                    "--example",
+                    example,
+                    "--features",
+                    "channels-console",
+                ])
+                .env("CHANNELS_CONSOLE_FORMAT", format)
+                .output()
+                .expect("Failed to execute command");
+            assert!(
+                output.status.success(),
+                "Command failed with status: {}",
+                output.status
+            );
+            output
+        };
+
+        let output = run("closed_std", "json");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        assert_eq!(stats.channels.len(), 2, "Got: {:?}", stats.channels);
+
+        // An explicitly set format wins over the environment
+        let output = run("aggregate_std", "table");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.lines().any(|line| line.starts_with('{')),
+            "Got:\n{stdout}"
+        );
+
+        let output = run("closed_std", "xml");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("Ignoring CHANNELS_CONSOLE_FORMAT, invalid format: xml"),
+            "Got:\n{stderr}"
+        );
+        assert!(stdout.contains("=== Statistics"), "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_set_label_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "relabel_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let labels: Vec<&str> = stats.channels.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["connection-10.0.0.7:5432"]);
+        assert_eq!(stats.channels[0].sent_count, 1);
+    }
+
+    #[test]
+    fn test_peak_queued_bytes_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "peak_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let burst = &stats.channels[0];
+        assert_eq!(burst.queued_bytes, 0);
+        assert!(burst.peak_queued_bytes > 0, "Got: {burst:?}");
+        assert_eq!(burst.peak_queued_bytes % 4, 0, "Got: {burst:?}");
+    }
+
+    #[test]
+    fn test_diff_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "idle_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let before: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        // A regression run where "busy" sent more messages and "unused" is gone
+        let mut after = before.clone();
+        after.channels.retain(|c| c.label != "unused");
+        let busy = after
+            .channels
+            .iter_mut()
+            .find(|c| c.label == "busy")
+            .expect("Channel busy not found");
+        busy.sent_count += 5;
+        let mut added = busy.clone();
+        added.label = "new-channel".to_string();
+        after.channels.push(added);
+
+        let dir =
+            std::env::temp_dir().join(format!("channels-console-diff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        let before_path = dir.join("before.json");
+        let after_path = dir.join("after.json");
+        std::fs::write(&before_path, serde_json::to_string(&before).unwrap()).unwrap();
+        std::fs::write(&after_path, serde_json::to_string(&after).unwrap()).unwrap();
+
+        let output = Command::new("cargo")
+            .args(["run", "-p", "channels-console", "--features", "tui", "--"])
+            .arg("diff")
+            .arg(&before_path)
+            .arg(&after_path)
+            .output()
+            .expect("Failed to execute command");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(
+            output.status.success(),
+            "Diff failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let row = |label: &str| {
+            stdout
+                .lines()
+                .find(|line| line.starts_with(&format!("| {label} ")))
+                .unwrap_or_else(|| panic!("Row {label} not found:\n{stdout}"))
+        };
+        assert!(row("busy").contains("| 6 (+5) "), "Got:\n{stdout}");
+        assert!(row("unused").contains("| removed "), "Got:\n{stdout}");
+        assert!(row("new-channel").contains("| added "), "Got:\n{stdout}");
+        assert!(row("new-channel").contains("| 6 (+6) "), "Got:\n{stdout}");
+        assert!(row("stale").contains("| 1 "), "Got:\n{stdout}");
+        assert!(
+            stdout.contains("Channels: 1 added, 1 removed"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_diff_old_snapshot_output() {
+        // Snapshots exported by earlier versions only have the original fields
+        let snapshot = |sent: u64| {
+            serde_json::json!({
+                "current_elapsed_ns": 1_000_000,
+                "channels": [{
+                    "id": 1,
+                    "source": "examples/old.rs:1",
+                    "label": "jobs",
+                    "has_custom_label": true,
+                    "channel_type": "bounded[10]",
+                    "state": "active",
+                    "sent_count": sent,
+                    "received_count": 2,
+                    "queued": sent - 2,
+                    "type_name": "i32",
+                    "type_size": 4,
+                    "queued_bytes": (sent - 2) * 4,
+                    "iter": 0
+                }],
+                "streams": [{
+                    "id": 2,
+                    "source": "examples/old.rs:2",
+                    "label": "ticks",
+                    "has_custom_label": true,
+                    "state": "active",
+                    "items_yielded": 1,
+                    "type_name": "i32",
+                    "type_size": 4,
+                    "iter": 0
+                }]
+            })
+            .to_string()
+        };
+
+        let dir =
+            std::env::temp_dir().join(format!("channels-console-diff-old-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        let before_path = dir.join("before.json");
+        let after_path = dir.join("after.json");
+        std::fs::write(&before_path, snapshot(3)).unwrap();
+        std::fs::write(&after_path, snapshot(5)).unwrap();
+
+        let output = Command::new("cargo")
+            .args(["run", "-p", "channels-console", "--features", "tui", "--"])
+            .arg("diff")
+            .arg(&before_path)
+            .arg(&after_path)
+            .output()
+            .expect("Failed to execute command");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(
+            output.status.success(),
+            "Diff failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let row = stdout
+            .lines()
+            .find(|line| line.starts_with("| jobs "))
+            .unwrap_or_else(|| panic!("Row jobs not found:\n{stdout}"));
+        assert!(row.contains("| 5 (+2) "), "Got:\n{stdout}");
+        assert!(
+            stdout.contains("Channels: 0 added, 0 removed"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_reset_channel_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "reset_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in [
+            "[Reset] first phase: phases sent=3 received=3",
+            "[Reset] found=true missing=false",
+            "[Reset] after reset: phases sent=0 received=0",
+            "[Reset] generation before=0 after=1",
+            "[Reset] second phase: phases sent=2 received=2",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Missing {expected:?}, got:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_module_path_labels_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "modules_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in [
+            "[Modules] modules_std::net@examples/modules_std.rs:5",
+            "[Modules] modules_std::db@examples/modules_std.rs:14",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Missing {expected:?}, got:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_line_ids_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "modules_std",
+                "--features",
+                "file-line-ids",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Modules] examples/modules_std.rs:5"),
+            "Got:\n{stdout}"
+        );
+        assert!(!stdout.contains('@'), "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_nested_path_labels_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "nested_std",
+                "--features",
+                "file-line-ids",
+            ])
+            .env("CHANNELS_CONSOLE_LABEL_PATH_COMPONENTS", "1")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // The second file ending with `ingest/mod.rs` keeps a third component, the label
+        // of the first one was made before and stays as it was
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in [
+            "[Before] mod.rs:4\n",
+            "[Nested] mod.rs:4\n",
+            "[Nested] west/ingest/mod.rs:4\n",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Missing {expected:?}, got:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_label_path_components_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "nested_std",
+                "--features",
+                "file-line-ids",
+            ])
+            .env("CHANNELS_CONSOLE_LABEL_PATH_COMPONENTS", "4")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in [
+            "[Nested] nested_std/east/ingest/mod.rs:4",
+            "[Nested] nested_std/west/ingest/mod.rs:4",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Missing {expected:?}, got:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset_endpoint() {
+        use std::process::Stdio;
+        use std::{thread::sleep, time::Duration};
+
+        let child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "reset_http_std",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6786")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn command");
+
+        // Reset once the example's messages are counted
+        let mut sent = None;
+        for _attempt in 0..20 {
+            sleep(Duration::from_millis(500));
+            let Ok(mut response) = ureq::get("http://127.0.0.1:6786/channels?filter=jobs").call()
+            else {
+                continue;
+            };
+            let channels: channels_console::ChannelsJson = response
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse JSON");
+            sent = channels.channels.first().map(|c| c.sent_count);
+            if sent == Some(3) {
+                break;
+            }
+        }
+        assert_eq!(sent, Some(3), "Messages were never counted");
+
+        // The example exits right after the reset, so the rejected requests go first
+        let missing = ureq::post("http://127.0.0.1:6786/reset?channel=missing").send_empty();
+        assert!(
+            matches!(missing, Err(ureq::Error::StatusCode(404))),
+            "Got: {missing:?}"
+        );
+        let get = ureq::get("http://127.0.0.1:6786/reset").call();
+        assert!(
+            matches!(get, Err(ureq::Error::StatusCode(405))),
+            "Got: {get:?}"
+        );
+
+        let reset = ureq::post("http://127.0.0.1:6786/reset?channel=jobs")
+            .send_empty()
+            .expect("Failed to call /reset")
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read response body");
+        assert_eq!(reset, r#"{"found":true}"#);
+
+        let output = child
+            .wait_with_output()
+            .expect("Failed to wait for example");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Reset] jobs reset over HTTP"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_metrics_path_endpoints() {
+        use std::process::Stdio;
+        use std::{thread::sleep, time::Duration};
+
+        let child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "reset_http_std",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6787")
+            .env("CHANNELS_CONSOLE_METRICS_PATH", "internal/")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut sent = None;
+        for _attempt in 0..20 {
+            sleep(Duration::from_millis(500));
+            let Ok(mut response) =
+                ureq::get("http://127.0.0.1:6787/internal/channels?filter=jobs").call()
+            else {
+                continue;
+            };
+            let channels: channels_console::ChannelsJson = response
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse JSON");
+            sent = channels.channels.first().map(|c| c.sent_count);
+            if sent == Some(3) {
+                break;
+            }
+        }
+        assert_eq!(sent, Some(3), "Messages were never counted");
+
+        // Endpoints are only served under the prefix
+        for path in ["/channels", "/health", "/internalchannels", "/internal"] {
+            let response = ureq::get(format!("http://127.0.0.1:6787{path}")).call();
+            assert!(
+                matches!(response, Err(ureq::Error::StatusCode(404))),
+                "{path} got: {response:?}"
+            );
+        }
+        ureq::get("http://127.0.0.1:6787/internal/health")
+            .call()
+            .expect("Failed to call /internal/health");
+
+        ureq::post("http://127.0.0.1:6787/internal/reset?channel=jobs")
+            .send_empty()
+            .expect("Failed to call /internal/reset");
+
+        let output = child
+            .wait_with_output()
+            .expect("Failed to wait for example");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("Channel metrics server listening on http://127.0.0.1:6787/internal\n"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Reset] jobs reset over HTTP"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_summary_format_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "summary_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let summary = stdout
+            .lines()
+            .find(|line| line.starts_with("channels="))
+            .unwrap_or_else(|| panic!("No summary line, got:\n{stdout}"));
+        assert!(
+            summary.starts_with("channels=2 sent=1.5K recv=1.5K queued="),
+            "Got: {summary}"
+        );
+        assert!(summary.contains(" mem="), "Got: {summary}");
+        assert!(!stdout.contains("Statistics"), "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_long_labels_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "long_labels_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // The table keeps the end of the label, the ellipsis is ASCII without UTF-8
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let row = stdout
+            .lines()
+            .find(|line| line.contains("warehouse-replenishment |"))
+            .unwrap_or_else(|| panic!("No truncated row, got:\n{stdout}"));
+        assert!(
+            row.starts_with("| …warehouse-replenishment |")
+                || row.starts_with("| ...ehouse-replenishment |"),
+            "Got: {row}"
+        );
+        assert!(
+            stdout.contains("[Full] orders::fulfillment::warehouse-replenishment\n"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_narrow_label_width_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-std-test",
+                "--example",
+                "narrow_labels_std",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // A width of 0 is raised to 4, keeping the end of the label after the ellipsis
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("| …ent ") || stdout.contains("| ...t "),
+            "Got:\n{stdout}"
+        );
+    }
 }
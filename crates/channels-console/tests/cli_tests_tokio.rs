@@ -31,7 +31,7 @@ pub mod tests {
             "unbounded",
             "bounded[10]",
             "oneshot",
-            "notified",
+            "| received |",
         ];
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -65,8 +65,9 @@ pub mod tests {
         );
 
         let all_expected = [
-            "\"label\": \"examples/basic_json_tokio.rs:",
+            "\"label\": \"basic_json_tokio@examples/basic_json_tokio.rs:",
             "\"label\": \"hello-there\"",
+            "\"queued_p99\":",
         ];
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -79,6 +80,189 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_dwell_time_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "basic_json_tokio",
+                "--features",
+                "dwell-time",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let all_expected = ["\"dwell_time\": {", "\"p99_ns\":"];
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_op_durations_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "basic_json_tokio",
+                "--features",
+                "op-durations",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(&stdout[json_start..]).expect("Failed to parse JSON");
+
+        let timed = stats
+            .channels
+            .iter()
+            .filter(|c| c.sent_count > 0)
+            .collect::<Vec<_>>();
+        assert!(!timed.is_empty(), "Got: {:?}", stats.channels);
+        for channel in timed {
+            let send = channel.send_duration.expect("Missing send_duration");
+            assert!(send.count > 0, "Got: {send:?}");
+            assert!(send.max_ns >= send.avg_ns, "Got: {send:?}");
+            assert_eq!(send.avg_ns, send.total_ns / send.count, "Got: {send:?}");
+        }
+        assert!(
+            stats
+                .channels
+                .iter()
+                .any(|c| c.recv_duration.is_some_and(|recv| recv.count > 0)),
+            "Got: {:?}",
+            stats.channels
+        );
+    }
+
+    #[test]
+    fn test_alert_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "alert_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let alerts = stdout.matches("[Alert] backlog").count();
+        assert_eq!(alerts, 2, "Expected two alerts, got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_leak_warning_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "leak_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_LEAK_WINDOW_MS", "1000")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("channel 'leaky' may be leaking"),
+            "Expected leak warning, got:\n{stderr}"
+        );
+    }
+
+    #[test]
+    fn test_is_closed_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "is_closed_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in ["[Sender] is_closed: true", "[Receiver] is_closed: true"] {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        for (label, active_receivers) in [("receiver-dropped", 0), ("sender-dropped", 1)] {
+            let channel = stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found"));
+            assert_eq!(channel.state, channels_console::ChannelState::Closed);
+            assert_eq!(channel.state_code, 1);
+            assert_eq!(channel.active_receivers, Some(active_receivers));
+        }
+    }
+
     #[test]
     fn test_closed_channels_output() {
         let output = Command::new("cargo")
@@ -110,11 +294,11 @@ pub mod tests {
             closed_count, stdout
         );
 
-        let notified_count = stdout.matches("| notified").count();
+        let received_count = stdout.matches("| received").count();
         assert_eq!(
-            notified_count, 1,
-            "Expected 'notified' state to appear 1 time in table (oneshot), found {}.\nOutput:\n{}",
-            notified_count, stdout
+            received_count, 1,
+            "Expected 'received' state to appear 1 time in table (oneshot), found {}.\nOutput:\n{}",
+            received_count, stdout
         );
     }
 
@@ -144,14 +328,155 @@ pub mod tests {
             stderr
         );
 
-        let all_expected = ["| closed |", "oneshot_closed_tokio.rs:"];
+        let all_expected = ["| receiver_dropped |", "oneshot_closed_tokio.rs:"];
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_cancelled_oneshot_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "cancelled_oneshot_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        let all_expected = [
+            "[Cancelled] answered state=received cancelled=false",
+            "[Cancelled] timed-out state=receiver_dropped cancelled=true",
+        ];
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+
+        let summary = stdout
+            .lines()
+            .find(|line| line.starts_with("channels="))
+            .unwrap_or_else(|| panic!("No summary line, got:\n{stdout}"));
+        assert!(
+            summary.starts_with("channels=2 sent=1 recv=1 ") && summary.ends_with(" cancelled=1"),
+            "Got: {summary}"
+        );
+    }
+
+    #[test]
+    fn test_axum_router_endpoints() {
+        use std::process::Stdio;
+        use std::{thread::sleep, time::Duration};
+
+        let child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "axum_router_tokio",
+                "--features",
+                "axum",
+            ])
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut sent = None;
+        for _attempt in 0..40 {
+            sleep(Duration::from_millis(500));
+            let Ok(mut response) =
+                ureq::get("http://127.0.0.1:6788/admin/channels/channels?filter=jobs").call()
+            else {
+                continue;
+            };
+            let channels: channels_console::ChannelsJson = response
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse JSON");
+            sent = channels.channels.first().map(|c| c.sent_count);
+            if sent == Some(3) {
+                break;
+            }
+        }
+        assert_eq!(sent, Some(3), "Messages were never counted");
+
+        let mut response = ureq::get("http://127.0.0.1:6788/admin/channels/health")
+            .call()
+            .expect("Failed to call /admin/channels/health");
+        let health: channels_console::HealthJson = response
+            .body_mut()
+            .read_json()
+            .expect("Failed to parse JSON");
+        assert_eq!(health.total_queued_bytes, 0);
+
+        // The application's own routes stay untouched
+        let body = ureq::get("http://127.0.0.1:6788/")
+            .call()
+            .expect("Failed to call /")
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read body");
+        assert_eq!(body, "my app");
+
+        let response = ureq::get("http://127.0.0.1:6788/admin/channels/reset")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .call()
+            .expect("Failed to call /admin/channels/reset");
+        assert_eq!(response.status(), 405);
+        assert_eq!(
+            response
+                .headers()
+                .get("allow")
+                .and_then(|v| v.to_str().ok()),
+            Some("POST")
+        );
+        ureq::post("http://127.0.0.1:6788/admin/channels/reset?channel=jobs")
+            .send_empty()
+            .expect("Failed to call /admin/channels/reset");
 
+        let output = child
+            .wait_with_output()
+            .expect("Failed to wait for example");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let all_expected = [
+            "[Axum] embedded server port=None",
+            "[Axum] jobs reset over HTTP",
+        ];
         for expected in all_expected {
             assert!(
                 stdout.contains(expected),
                 "Expected:\n{expected}\n\nGot:\n{stdout}",
             );
         }
+        assert!(
+            !stdout.contains("Channel metrics server listening"),
+            "Got:\n{stdout}"
+        );
     }
 
     #[test]
@@ -210,6 +535,11 @@ pub mod tests {
         // Test /logs/:id endpoint
         let channels: channels_console::ChannelsJson =
             serde_json::from_str(&json_text).expect("Failed to parse channels JSON");
+        assert!(
+            channels.runtime_seconds > 0.0,
+            "Expected the server runtime, got {}",
+            channels.runtime_seconds
+        );
 
         if let Some(first_channel) = channels.channels.first() {
             let logs_url = format!("http://127.0.0.1:6770/channels/{}/logs", first_channel.id);
@@ -224,6 +554,238 @@ pub mod tests {
             );
         }
 
+        // Test /channels?sort=...&order=... query parameters
+        let sorted: channels_console::ChannelsJson =
+            ureq::get("http://127.0.0.1:6770/channels?sort=sent&order=desc")
+                .call()
+                .expect("Failed to call sorted /channels endpoint")
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse sorted channels JSON");
+        let sent_counts: Vec<u64> = sorted.channels.iter().map(|c| c.sent_count).collect();
+        let mut expected_counts = sent_counts.clone();
+        expected_counts.sort_by(|a, b| b.cmp(a));
+        assert_eq!(
+            sent_counts, expected_counts,
+            "Expected descending sent counts"
+        );
+
+        // Test /channels?filter=... query parameter
+        let filtered: channels_console::ChannelsJson =
+            ureq::get("http://127.0.0.1:6770/channels?filter=hello")
+                .call()
+                .expect("Failed to call filtered /channels endpoint")
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse filtered channels JSON");
+        let labels: Vec<&str> = filtered.channels.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["hello-there"]);
+
+        let unmatched: channels_console::ChannelsJson =
+            ureq::get("http://127.0.0.1:6770/channels?filter=no-such-channel")
+                .call()
+                .expect("Failed to call filtered /channels endpoint")
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse filtered channels JSON");
+        assert!(unmatched.channels.is_empty());
+
+        // Test /channels?deltas=true&client=... counts since the client's last scrape
+        let scrape = || -> channels_console::ChannelsJson {
+            ureq::get("http://127.0.0.1:6770/channels?deltas=true&client=test")
+                .call()
+                .expect("Failed to call /channels with deltas")
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse channels JSON with deltas")
+        };
+        let first = scrape();
+        let second = scrape();
+        assert!(second.sequence > first.sequence);
+        let first_deltas = first.deltas.expect("Expected deltas");
+        let second_deltas = second.deltas.expect("Expected deltas");
+        for channel in &second.channels {
+            let previous = first.channels.iter().find(|c| c.id == channel.id);
+            let first_delta = first_deltas.iter().find(|d| d.id == channel.id);
+            let delta = second_deltas.iter().find(|d| d.id == channel.id).unwrap();
+            if let (Some(previous), Some(first_delta)) = (previous, first_delta) {
+                assert_eq!(first_delta.sent_delta, previous.sent_count);
+                assert_eq!(delta.sent_delta, channel.sent_count - previous.sent_count);
+                assert_eq!(
+                    delta.received_delta,
+                    channel.received_count - previous.received_count
+                );
+            }
+        }
+        assert!(unmatched.deltas.is_none());
+
+        let missing_client = ureq::get("http://127.0.0.1:6770/channels?deltas=true").call();
+        assert!(
+            matches!(missing_client, Err(ureq::Error::StatusCode(400))),
+            "Got: {missing_client:?}"
+        );
+
+        // Test /channels/influx line protocol endpoint
+        let influx = ureq::get("http://127.0.0.1:6770/channels/influx")
+            .call()
+            .expect("Failed to call /channels/influx endpoint")
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read influx body");
+        let bounded_line = influx
+            .lines()
+            .find(|line| line.contains("label=bounded-channel"))
+            .unwrap_or_else(|| panic!("Expected bounded-channel line, got:\n{influx}"));
+        assert!(
+            bounded_line.starts_with("channel,id="),
+            "Got: {bounded_line}"
+        );
+        assert!(
+            bounded_line.contains(",type=bounded sent="),
+            "Got: {bounded_line}"
+        );
+        assert!(
+            bounded_line.contains("queued_bytes="),
+            "Got: {bounded_line}"
+        );
+        assert!(
+            influx.contains("label=Actor\\ 1,type=unbounded"),
+            "Expected escaped label, got:\n{influx}"
+        );
+        assert_eq!(influx.lines().count(), channels.channels.len());
+
+        // Test /channels/openmetrics endpoint
+        let mut response = ureq::get("http://127.0.0.1:6770/channels/openmetrics")
+            .call()
+            .expect("Failed to call /channels/openmetrics endpoint");
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(
+            content_type.starts_with("application/openmetrics-text"),
+            "Got content type: {content_type}"
+        );
+        let openmetrics = response
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read openmetrics body");
+        for expected in [
+            "# TYPE channels_console_sent counter\n",
+            "# UNIT channels_console_queued_bytes bytes\n",
+            "label=\"bounded-channel\",type=\"bounded\"} ",
+            "channels_console_capacity{",
+            "# TYPE channels_console_send_wait_seconds histogram\n",
+            "# UNIT channels_console_send_wait_seconds seconds\n",
+            "# TYPE channels_console_state gauge\n",
+            "# TYPE channels_console_send_errors counter\n",
+            "# TYPE channels_console_total_queued_bytes gauge\n",
+            "\nchannels_console_total_queued_bytes ",
+            "# TYPE channels_console_generation gauge\n",
+            "# UNIT channels_console_runtime_seconds seconds\n",
+            "\nchannels_console_runtime_seconds ",
+            "# TYPE channels_console_active_senders gauge\n",
+            "\nchannels_console_active_senders{",
+            "\nchannels_console_active_receivers{",
+        ] {
+            assert!(
+                openmetrics.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{openmetrics}"
+            );
+        }
+        assert!(openmetrics.contains("\nchannels_console_sent_total{"));
+        assert!(!openmetrics.contains("\nchannels_console_sent{"));
+        assert!(openmetrics.ends_with("# EOF\n"), "Got:\n{openmetrics}");
+
+        // Test /health endpoint
+        let health: channels_console::HealthJson = ureq::get("http://127.0.0.1:6770/health")
+            .call()
+            .expect("Failed to call /health endpoint")
+            .body_mut()
+            .read_json()
+            .expect("Failed to parse health JSON");
+        assert!(health.pending_channel_events < 100, "Got: {health:?}");
+        assert!(
+            (0.0..1000.0).contains(&health.collector_lag_ms),
+            "Got: {health:?}"
+        );
+
+        // Test /metrics/schema endpoint documents exactly the serialized fields
+        let schema: channels_console::ChannelStatsSchema =
+            ureq::get("http://127.0.0.1:6770/metrics/schema")
+                .call()
+                .expect("Failed to call /metrics/schema endpoint")
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse schema JSON");
+        let channels: serde_json::Value = ureq::get("http://127.0.0.1:6770/channels")
+            .call()
+            .expect("Failed to call /channels endpoint")
+            .body_mut()
+            .read_json()
+            .expect("Failed to parse channels JSON");
+        let serialized: Vec<&str> = channels["channels"][0]
+            .as_object()
+            .expect("No channel object")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let documented: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        for field in &serialized {
+            assert!(documented.contains(field), "Field {field} not in schema");
+        }
+        for field in &documented {
+            assert!(
+                serialized.contains(field),
+                "Schema field {field} not serialized"
+            );
+        }
+        assert!(schema.fields.iter().all(|f| !f.description.is_empty()));
+
+        let invalid = ureq::get("http://127.0.0.1:6770/channels?sort=bogus").call();
+        assert!(
+            matches!(invalid, Err(ureq::Error::StatusCode(400))),
+            "Expected 400 for invalid sort key, got {:?}",
+            invalid.map(|r| r.status())
+        );
+
+        // Wrong methods on existing endpoints get 405 with the allowed ones
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        let wrong_methods = [
+            agent.post("http://127.0.0.1:6770/channels").send_empty(),
+            agent
+                .post("http://127.0.0.1:6770/channels/1/logs")
+                .send_empty(),
+            agent.get("http://127.0.0.1:6770/reset").call(),
+            agent.put("http://127.0.0.1:6770/reset").send_empty(),
+        ];
+        for (response, allow) in
+            wrong_methods
+                .into_iter()
+                .zip(["GET, HEAD", "GET, HEAD", "POST", "POST"])
+        {
+            let response = response.expect("Request failed");
+            assert_eq!(response.status(), 405);
+            assert_eq!(
+                response
+                    .headers()
+                    .get("allow")
+                    .and_then(|v| v.to_str().ok()),
+                Some(allow)
+            );
+        }
+        let unknown = agent
+            .post("http://127.0.0.1:6770/nope")
+            .send_empty()
+            .expect("Request failed");
+        assert_eq!(unknown.status(), 404);
+        assert!(unknown.headers().get("allow").is_none());
+
         let _ = child.kill();
         let _ = child.wait();
     }
@@ -255,12 +817,12 @@ pub mod tests {
             "Actor 1",
             "Actor 1-2",
             "Actor 1-3",
-            "examples/iter_tokio.rs:38",
-            "examples/iter_tokio.rs:38-2",
-            "examples/iter_tokio.rs:38-3",
-            "examples/iter_tokio.rs:53",
-            "examples/iter_tokio.rs:53-2",
-            "examples/iter_tokio.rs:53-3",
+            "iter_tokio@examples/iter_tokio.rs:38",
+            "iter_tokio@examples/iter_tokio.rs:38-2",
+            "iter_tokio@examples/iter_tokio.rs:38-3",
+            "iter_tokio@examples/iter_tokio.rs:53",
+            "iter_tokio@examples/iter_tokio.rs:53-2",
+            "iter_tokio@examples/iter_tokio.rs:53-3",
         ];
 
         for expected in all_expected {
@@ -303,4 +865,946 @@ pub mod tests {
             stdout
         );
     }
+
+    #[test]
+    fn test_send_timeout_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "send_timeout_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let count = |prefix: &str| -> u64 {
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .unwrap_or_else(|| panic!("{prefix} not found in:\n{stdout}"))
+                .trim()
+                .parse()
+                .expect("Invalid count")
+        };
+        let delivered = count("[Sender] delivered:");
+        let timed_out = count("[Sender] timed out:");
+        assert!(timed_out > 0, "Got:\n{stdout}");
+        assert_eq!(count("[Receiver] received:"), delivered);
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "timed-sends")
+            .expect("Channel not found");
+        assert_eq!(channel.sent_count, delivered);
+        assert_eq!(channel.received_count, delivered);
+        assert!(channel.full_events >= 1);
+        assert_eq!(channel.send_timeout_count, timed_out);
+
+        // Every timed out send waited at least its timeout
+        let send_timeout = channel.send_timeout.expect("No timed sends recorded");
+        assert_eq!(send_timeout.count, 20);
+        assert_eq!(send_timeout.timeout_ns, 20_000_000);
+        assert!(send_timeout.max_wait_ns >= send_timeout.timeout_ns);
+    }
+
+    #[test]
+    fn test_cancelled_send_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "cancelled_send_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let count = |prefix: &str| -> u64 {
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .unwrap_or_else(|| panic!("{prefix} not found in:\n{stdout}"))
+                .trim()
+                .parse()
+                .expect("Invalid count")
+        };
+        let delivered = count("[Sender] delivered:");
+        assert_eq!(count("[Sender] cancelled:"), 5);
+        assert_eq!(count("[Receiver] received:"), delivered);
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "cancelled-sends")
+            .expect("Channel not found");
+        assert_eq!(channel.sent_count, delivered);
+        assert_eq!(channel.received_count, delivered);
+        assert_eq!(channel.queued, 0);
+    }
+
+    #[test]
+    fn test_dropped_send_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "dropped_send_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Sender] timed out: true"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Sender] lost race: true"),
+            "Got:\n{stdout}"
+        );
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "dropped-sends")
+            .expect("Channel not found");
+        assert_eq!(channel.sent_count, 0);
+        assert_eq!(channel.received_count, 0);
+        assert_eq!(channel.queued, 0);
+    }
+
+    #[test]
+    fn test_reserve_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "reserve_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Receiver] received: [0, 1, 2, 3]"),
+            "Got:\n{stdout}"
+        );
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "permits")
+            .expect("Channel not found");
+        // The dropped permit is not counted
+        assert_eq!(channel.sent_count, 4);
+        assert_eq!(channel.received_count, 4);
+    }
+
+    #[test]
+    fn test_reserved_permits_output() {
+        use std::{thread::sleep, time::Duration};
+
+        let child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "reserved_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6784")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn command");
+
+        // The example may still be compiling, it holds the permits for 3s once started
+        let mut held = None;
+        for _attempt in 0..40 {
+            sleep(Duration::from_millis(300));
+            let Ok(mut response) = ureq::get("http://127.0.0.1:6784/channels").call() else {
+                continue;
+            };
+            let body = response.body_mut().read_to_string().unwrap();
+            let stats: channels_console::ChannelsJson =
+                serde_json::from_str(&body).expect("Failed to parse JSON");
+            if stats.channels[0].reserved > 0 {
+                held = Some(stats.channels[0].clone());
+                break;
+            }
+        }
+        let held = held.expect("No reserved permits reported");
+        assert_eq!(held.reserved, 2);
+        assert_eq!(held.available, Some(2));
+
+        let output = child
+            .wait_with_output()
+            .expect("Failed to wait for example");
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = &stats.channels[0];
+        assert_eq!(channel.reserved, 0);
+        assert_eq!(channel.available, Some(4));
+        assert_eq!(channel.sent_count, 1);
+    }
+
+    #[test]
+    fn test_sender_counts_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "senders_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // The original sender and the long-lived clone are alive, three more came and went
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Senders] producers active=Some(2) created=Some(5)"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_cloned_senders_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "clones_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // Ten clones sent five messages each into the same entry
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Clones] received 50 messages"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Clones] fan-in sent=50 received=50 state=closed"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_prefilled_capacity_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "capacity_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // Two messages were queued before instrumenting, the capacity is still the full one
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Capacity] received 3 messages"),
+            "Got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("[Capacity] prefilled bounded[5] capacity=Some(5)"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_queue_percentiles_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "percentiles_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let line = stdout
+            .lines()
+            .find(|line| line.starts_with("[Percentiles] bursty capacity=Some(10)"))
+            .unwrap_or_else(|| panic!("Got:\n{stdout}"));
+        let value = |key: &str| -> u64 {
+            line.split_whitespace()
+                .find_map(|part| part.strip_prefix(key))
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_else(|| panic!("No {key} in {line}"))
+        };
+        let (p50, p95, p99) = (value("p50="), value("p95="), value("p99="));
+
+        // The channel is repeatedly filled to capacity and drained
+        assert!(p50 <= p95 && p95 <= p99 && p99 <= 10, "Got: {line}");
+        assert!(p99 >= 9, "Got: {line}");
+    }
+
+    #[test]
+    fn test_full_events_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "full_events_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        // Filled to capacity 3 distinct times, each counted once
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("[Full] refilled full_events=3 queued=0"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_reenable_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "reenable_tokio",
+                "--features",
+                "dwell-time",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let value = |prefix: &str| -> u64 {
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .unwrap_or_else(|| panic!("{prefix} not found in:\n{stdout}"))
+                .parse()
+                .expect("Invalid value")
+        };
+
+        // Pairing the later receives with the 300ms old sends would report them as waiting
+        assert!(value("[Dwell] paused max_ms=") < 100, "Got:\n{stdout}");
+        // The burst of 8 before disabling is forgotten once enabled
+        assert!(value("[Depth] steady p99=") < 3, "Got:\n{stdout}");
+    }
+
+    #[test]
+    fn test_backpressure_output() {
+        let run = |features: &[&str]| {
+            let mut args = vec![
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "backpressure_tokio",
+            ];
+            args.extend_from_slice(features);
+            let output = Command::new("cargo")
+                .args(&args)
+                .output()
+                .expect("Failed to execute command");
+            assert!(
+                output.status.success(),
+                "Command failed with status: {}",
+                output.status
+            );
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+
+        // The first blocking send happens at the configured capacity
+        let stdout = run(&[]);
+        assert!(
+            stdout.contains("[Sender] accepted before blocking: 4"),
+            "Got:\n{stdout}"
+        );
+
+        // Instrumented, the proxy adds capacity + 3 slots in front of the observed queue
+        let stdout = run(&["--features", "channels-console"]);
+        assert!(
+            stdout.contains("[Sender] accepted before blocking: 11"),
+            "Got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn test_utilization_output() {
+        let run = |format: &str| {
+            let output = Command::new("cargo")
+                .args([
+                    "run",
+                    "-p",
+                    "channels-console-tokio-test",
+                    "--example",
+                    "utilization_tokio",
+                    "--features",
+                    "channels-console",
+                ])
+                .env("CHANNELS_CONSOLE_FORMAT", format)
+                .output()
+                .expect("Failed to execute command");
+            assert!(
+                output.status.success(),
+                "Command failed with status: {}",
+                output.status
+            );
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+
+        let stdout = run("json");
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let utilization = |label: &str| {
+            stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found"))
+                .utilization
+        };
+        // 2 of 4 slots in use
+        assert_eq!(utilization("half-full"), Some(50.0));
+        assert_eq!(utilization("unbounded"), None);
+        assert_eq!(utilization("oneshot"), None);
+        assert!(
+            json_line.contains("\"utilization\":null"),
+            "Got:\n{json_line}"
+        );
+
+        let stdout = run("table");
+        assert!(stdout.contains("Util%"), "Got:\n{stdout}");
+        let row = |label: &str| {
+            stdout
+                .lines()
+                .find(|line| line.starts_with(&format!("| {label} ")))
+                .unwrap_or_else(|| panic!("No row for {label} in:\n{stdout}"))
+                .split('|')
+                .map(str::trim)
+                .collect::<Vec<_>>()
+        };
+        // Columns: Channel, Type, State, Sent, Received, Queued, Util%
+        assert_eq!(row("half-full")[7], "50.0%");
+        assert_eq!(row("unbounded")[7], "-");
+        assert_eq!(row("oneshot")[7], "-");
+    }
+
+    #[test]
+    fn test_blocking_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "blocking_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("[Consumer] received: 10"), "Got:\n{stdout}");
+
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        let channel = stats
+            .channels
+            .iter()
+            .find(|c| c.label == "blocking")
+            .expect("Channel not found");
+        assert_eq!(channel.sent_count, 10);
+        assert_eq!(channel.received_count, 10);
+        assert_eq!(channel.state, channels_console::ChannelState::Closed);
+    }
+
+    #[test]
+    fn test_task_id_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "task_id_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let task_id: u64 = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("Task id: "))
+            .expect("No task id printed")
+            .parse()
+            .expect("Invalid task id");
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let channel = |label: &str| {
+            stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("No {label} channel"))
+        };
+        assert_eq!(channel("inside-task").task_id, Some(task_id));
+        assert_eq!(channel("outside-task").task_id, None);
+    }
+
+    #[test]
+    fn test_no_runtime_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "no_runtime_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        for expected in [
+            "[NoRuntime] std sent=1 received=1 runtime=false",
+            "[NoRuntime] mpsc sent=1 received=1 runtime=false",
+            "[NoRuntime] oneshot sent=1 received=1 runtime=false",
+        ] {
+            assert!(
+                stdout.contains(expected),
+                "Expected: {expected}\nGot:\n{stdout}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_runtime_info() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "runtime_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let runtime_id: u64 = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("Runtime id: "))
+            .expect("No runtime id printed")
+            .parse()
+            .expect("Invalid runtime id");
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+
+        let channel = |label: &str| {
+            stats
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("No {label} channel"))
+        };
+        let runtime = channel("on-runtime")
+            .runtime
+            .clone()
+            .expect("No runtime captured");
+        assert_eq!(runtime.id, Some(runtime_id));
+        assert_eq!(runtime.flavor, "multi_thread");
+        assert_eq!(runtime.workers, 2);
+        assert_eq!(channel("off-runtime").runtime, None);
+    }
+
+    #[test]
+    fn test_unusual_label_exports() {
+        use std::{thread::sleep, time::Duration};
+
+        const LABEL: &str = "say \"hi\"\nback\\slash, a=b";
+
+        let child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "unusual_labels_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6783")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let get = |path: &str| {
+            ureq::get(format!("http://127.0.0.1:6783{path}"))
+                .call()
+                .map(|mut response| response.body_mut().read_to_string().unwrap())
+        };
+
+        // The example may still be compiling, it serves for 3s once started
+        let mut channels_json = None;
+        for _attempt in 0..40 {
+            sleep(Duration::from_millis(300));
+            if let Ok(body) = get("/channels") {
+                channels_json = Some(body);
+                break;
+            }
+        }
+        let channels_json = channels_json.expect("Metrics server didn't start");
+        // The server starts with the channel, give the collector a tick to apply the send
+        sleep(Duration::from_millis(300));
+        let openmetrics = get("/channels/openmetrics").expect("Failed to get openmetrics");
+        let influx = get("/channels/influx").expect("Failed to get influx");
+        let output = child
+            .wait_with_output()
+            .expect("Failed to wait for example");
+
+        let channels: channels_console::ChannelsJson =
+            serde_json::from_str(&channels_json).expect("Failed to parse JSON");
+        assert_eq!(channels.channels[0].label, LABEL);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_line = stdout
+            .lines()
+            .find(|line| line.starts_with('{'))
+            .expect("No JSON output found");
+        let stats: channels_console::CombinedJson =
+            serde_json::from_str(json_line).expect("Failed to parse JSON");
+        assert_eq!(stats.channels[0].label, LABEL);
+
+        let escaped = r#"label="say \"hi\"\nback\\slash, a=b""#;
+        let sent_line = openmetrics
+            .lines()
+            .find(|line| line.starts_with("channels_console_sent_total{"))
+            .unwrap_or_else(|| panic!("No sent sample, got:\n{openmetrics}"));
+        assert!(sent_line.contains(escaped), "Got: {sent_line}");
+        assert!(sent_line.ends_with("} 1"), "Got: {sent_line}");
+        assert!(openmetrics.ends_with("# EOF\n"), "Got:\n{openmetrics}");
+
+        assert_eq!(influx.lines().count(), 1, "Got:\n{influx}");
+        assert!(
+            influx.contains(r#"label=say\ "hi"\ back\slash\,\ a\=b,type=bounded "#),
+            "Got:\n{influx}"
+        );
+    }
+
+    #[test]
+    fn test_subscribe_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "subscribe_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let line = stdout
+            .lines()
+            .find(|line| line.starts_with("[Subscriber]"))
+            .unwrap_or_else(|| panic!("No subscriber output found:\n{stdout}"));
+        let value = |key: &str| -> u64 {
+            line.split_whitespace()
+                .find_map(|part| part.strip_prefix(key))
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(|| panic!("Missing {key} in {line}"))
+        };
+
+        // The channel changes on every tick, but snapshots are throttled to one per 250ms
+        let snapshots = value("snapshots=");
+        assert!((2..=6).contains(&snapshots), "Got: {line}");
+        assert!(value("last_sent=") > 0, "Got: {line}");
+    }
+
+    #[test]
+    fn test_export_output() {
+        use std::{process::Command, thread::sleep, time::Duration};
+
+        let export = |path: &std::path::Path, format: &str| {
+            Command::new("cargo")
+                .args(["run", "-p", "channels-console", "--features", "tui", "--"])
+                .arg("export")
+                .arg(path)
+                .args(["--format", format, "--metrics-port", "6781"])
+                .output()
+                .expect("Failed to execute command")
+        };
+
+        let dir =
+            std::env::temp_dir().join(format!("channels-console-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        let json_path = dir.join("snapshot.json");
+
+        // Build the CLI first so its compilation doesn't eat into the example's runtime
+        let build = Command::new("cargo")
+            .args(["build", "-p", "channels-console", "--features", "tui"])
+            .status()
+            .expect("Failed to build CLI");
+        assert!(build.success(), "Failed to build CLI");
+
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "channels-console-tokio-test",
+                "--example",
+                "export_tokio",
+                "--features",
+                "channels-console",
+            ])
+            .env("CHANNELS_CONSOLE_METRICS_PORT", "6781")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut last_output = None;
+        for _attempt in 0..20 {
+            sleep(Duration::from_millis(500));
+            let output = export(&json_path, "json");
+            if output.status.success() {
+                last_output = None;
+                break;
+            }
+            last_output = Some(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+        if let Some(stderr) = last_output {
+            let _ = child.kill();
+            panic!("Export failed after 20 retries:\n{stderr}");
+        }
+
+        let csv_path = dir.join("snapshot.csv");
+        let csv_output = export(&csv_path, "csv");
+        let table_path = dir.join("snapshot.txt");
+        let table_output = export(&table_path, "table");
+        let _ = child.kill();
+        let _ = child.wait();
+        assert!(csv_output.status.success(), "CSV export failed");
+        assert!(table_output.status.success(), "Table export failed");
+
+        let snapshot: channels_console::CombinedJson = serde_json::from_str(
+            &std::fs::read_to_string(&json_path).expect("Failed to read JSON snapshot"),
+        )
+        .expect("Failed to parse JSON snapshot");
+        assert!(
+            snapshot.channels.iter().any(|c| c.label == "exported"),
+            "Expected exported channel"
+        );
+
+        let csv = std::fs::read_to_string(&csv_path).expect("Failed to read CSV snapshot");
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("id,label,source,type,state,sent,received,queued,queued_bytes,utilization,full_events,type_name")
+        );
+        assert!(
+            lines.any(|line| line.split(',').nth(1) == Some("exported")),
+            "Got:\n{csv}"
+        );
+
+        let table = std::fs::read_to_string(&table_path).expect("Failed to read table snapshot");
+        assert!(table.contains("Channels:"), "Got:\n{table}");
+        assert!(table.contains("| exported"), "Got:\n{table}");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }